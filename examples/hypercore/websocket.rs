@@ -55,13 +55,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ws.subscribe(Subscription::AllMids { dex: None });
 
     while let Some(item) = ws.next().await {
-        if let Incoming::AllMids { dex: _, mids } = item {
-            if let Some(price) = mids.get(&khype.name) {
-                println!(
-                    "Price of {}/{} is {}",
-                    khype.tokens[0].name, khype.tokens[1].name, price
-                );
-            }
+        if let Incoming::AllMids { dex: _, mids } = item
+            && let Some(price) = mids.get(&khype.name)
+        {
+            println!(
+                "Price of {}/{} is {}",
+                khype.tokens[0].name, khype.tokens[1].name, price
+            );
         }
     }
 
@@ -0,0 +1,289 @@
+//! Server settings.
+//!
+//! Every setting can be given as a CLI flag, an environment variable or a key of the
+//! TOML file passed with `--config`, in that order of precedence. Unset settings fall
+//! back to [`Config::default`].
+//!
+//! ```toml
+//! chain = "testnet"
+//! base-url = "https://api.hyperliquid-testnet.xyz"
+//! evm-rpc-url = "https://rpc.hyperliquid-testnet.xyz/evm"
+//! bind = "0.0.0.0:8000"
+//! cors-origins = ["https://app.example.com"]
+//! graphiql = false
+//! metrics = true
+//! max-depth = 12
+//! max-complexity = 500
+//! max-weight = 200
+//! rate-limit = 600
+//! keystore = "/etc/hypersdk/agent.json"
+//! proposals-db = "/var/lib/hypersdk/proposals.db"
+//!
+//! [auth]
+//! jwt = { secret = "change-me", issuer = "desk-sso" }
+//!
+//! [[auth.keys]]
+//! name = "desk-a"
+//! key = "change-me-too"
+//! role = "trader"
+//! budget = { requests = 120, per-seconds = 60 }
+//! ```
+//!
+//! Credentials (`auth`) can only be set in the file.
+
+use std::{env, fs, net::SocketAddr, path::PathBuf};
+
+use anyhow::Context;
+use clap::{Args, ValueEnum};
+use hypersdk::{
+    hypercore::{Chain, rate_limit::WEIGHT_LIMIT},
+    hypergraphql::auth::AuthConfig,
+};
+use serde::Deserialize;
+use url::Url;
+
+/// Network the server talks to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    #[default]
+    Mainnet,
+    Testnet,
+}
+
+impl From<Network> for Chain {
+    fn from(network: Network) -> Self {
+        match network {
+            Network::Mainnet => Chain::Mainnet,
+            Network::Testnet => Chain::Testnet,
+        }
+    }
+}
+
+/// Settings that can be set from the CLI, the environment or the TOML file.
+///
+/// All fields are optional so that layers can be merged with [`Settings::or`].
+#[derive(Debug, Clone, Default, Args, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Settings {
+    /// Network to connect to
+    #[arg(long, env = "HYPERSDK_CHAIN")]
+    pub chain: Option<Network>,
+    /// Upstream API base URL, defaults to the network's public API
+    #[arg(long, env = "HYPERSDK_BASE_URL")]
+    pub base_url: Option<Url>,
+    /// HyperEVM RPC URL, defaults to the public RPC on mainnet and none on testnet
+    #[arg(long, env = "HYPERSDK_EVM_RPC_URL")]
+    pub evm_rpc_url: Option<Url>,
+    /// Address to listen on [default: 127.0.0.1:8000]
+    #[arg(long, env = "HYPERSDK_BIND")]
+    pub bind: Option<SocketAddr>,
+    /// Allowed CORS origins, comma separated. `*` allows any origin
+    #[arg(long, env = "HYPERSDK_CORS_ORIGINS", value_delimiter = ',')]
+    pub cors_origins: Option<Vec<String>>,
+    /// Serve the GraphiQL playground at `GET /` [default: true]
+    #[arg(long, env = "HYPERSDK_GRAPHIQL")]
+    pub graphiql: Option<bool>,
+    /// Serve Prometheus metrics at `GET /metrics` [default: true]
+    #[arg(long, env = "HYPERSDK_METRICS")]
+    pub metrics: Option<bool>,
+    /// Maximum query depth
+    #[arg(long, env = "HYPERSDK_MAX_DEPTH")]
+    pub max_depth: Option<usize>,
+    /// Maximum query complexity
+    #[arg(long, env = "HYPERSDK_MAX_COMPLEXITY")]
+    pub max_complexity: Option<usize>,
+    /// Maximum estimated Hyperliquid request weight of an operation
+    #[arg(long, env = "HYPERSDK_MAX_WEIGHT")]
+    pub max_weight: Option<u32>,
+    /// Upstream request weight the server may spend per minute, 0 disables the limit
+    /// [default: 1200]
+    #[arg(long, env = "HYPERSDK_RATE_LIMIT")]
+    pub rate_limit: Option<u32>,
+    /// Path of an encrypted keystore holding the agent key used to sign mutations
+    #[arg(long, env = "HYPERSDK_KEYSTORE")]
+    pub keystore: Option<PathBuf>,
+    /// SQLite database keeping multisig proposals [default:
+    /// $XDG_DATA_HOME/hypersdk/proposals.db, or ~/.local/share/hypersdk/proposals.db]
+    #[arg(long, env = "HYPERSDK_PROPOSALS_DB")]
+    pub proposals_db: Option<PathBuf>,
+    /// Accepted API keys and JWTs, the server is open to anyone if omitted
+    #[arg(skip)]
+    pub auth: Option<AuthConfig>,
+}
+
+impl Settings {
+    /// Reads settings from a TOML file.
+    pub fn from_file(path: &PathBuf) -> anyhow::Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// Fills the settings missing from `self` with the ones of `other`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            chain: self.chain.or(other.chain),
+            base_url: self.base_url.or(other.base_url),
+            evm_rpc_url: self.evm_rpc_url.or(other.evm_rpc_url),
+            bind: self.bind.or(other.bind),
+            cors_origins: self.cors_origins.or(other.cors_origins),
+            graphiql: self.graphiql.or(other.graphiql),
+            metrics: self.metrics.or(other.metrics),
+            max_depth: self.max_depth.or(other.max_depth),
+            max_complexity: self.max_complexity.or(other.max_complexity),
+            max_weight: self.max_weight.or(other.max_weight),
+            rate_limit: self.rate_limit.or(other.rate_limit),
+            keystore: self.keystore.or(other.keystore),
+            proposals_db: self.proposals_db.or(other.proposals_db),
+            auth: self.auth.or(other.auth),
+        }
+    }
+}
+
+/// Resolved server configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub chain: Network,
+    pub base_url: Option<Url>,
+    pub evm_rpc_url: Option<Url>,
+    pub bind: SocketAddr,
+    pub cors_origins: Vec<String>,
+    pub graphiql: bool,
+    pub metrics: bool,
+    pub max_depth: Option<usize>,
+    pub max_complexity: Option<usize>,
+    pub max_weight: Option<u32>,
+    pub rate_limit: u32,
+    pub keystore: Option<PathBuf>,
+    /// `None` if not set and the user has no data directory.
+    pub proposals_db: Option<PathBuf>,
+    pub auth: Option<AuthConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            chain: Network::Mainnet,
+            base_url: None,
+            evm_rpc_url: None,
+            bind: SocketAddr::from(([127, 0, 0, 1], 8000)),
+            cors_origins: vec![],
+            graphiql: true,
+            metrics: true,
+            max_depth: None,
+            max_complexity: None,
+            max_weight: None,
+            rate_limit: WEIGHT_LIMIT,
+            keystore: None,
+            proposals_db: default_proposals_db(),
+            auth: None,
+        }
+    }
+}
+
+impl From<Settings> for Config {
+    fn from(settings: Settings) -> Self {
+        let default = Self::default();
+        Self {
+            chain: settings.chain.unwrap_or(default.chain),
+            base_url: settings.base_url,
+            evm_rpc_url: settings.evm_rpc_url,
+            bind: settings.bind.unwrap_or(default.bind),
+            cors_origins: settings.cors_origins.unwrap_or(default.cors_origins),
+            graphiql: settings.graphiql.unwrap_or(default.graphiql),
+            metrics: settings.metrics.unwrap_or(default.metrics),
+            max_depth: settings.max_depth,
+            max_complexity: settings.max_complexity,
+            max_weight: settings.max_weight,
+            rate_limit: settings.rate_limit.unwrap_or(default.rate_limit),
+            keystore: settings.keystore,
+            proposals_db: settings.proposals_db.or(default.proposals_db),
+            auth: settings.auth,
+        }
+    }
+}
+
+/// Returns `hypersdk/proposals.db` in the user's data directory, `$XDG_DATA_HOME` or
+/// `~/.local/share`.
+fn default_proposals_db() -> Option<PathBuf> {
+    let data_dir = env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(data_dir.join("hypersdk").join("proposals.db"))
+}
+
+#[cfg(test)]
+mod tests {
+    use hypersdk::hypergraphql::auth::Role;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_toml() {
+        let settings: Settings = toml::from_str(
+            r#"
+            chain = "testnet"
+            base-url = "http://localhost:3001"
+            bind = "0.0.0.0:9000"
+            cors-origins = ["https://app.example.com"]
+            graphiql = false
+            metrics = false
+            max-depth = 10
+            max-weight = 300
+
+            [[auth.keys]]
+            name = "desk-a"
+            key = "k1"
+            role = "trader"
+            budget = { requests = 10, per-seconds = 60 }
+            "#,
+        )
+        .unwrap();
+
+        let config = Config::from(settings);
+        assert_eq!(config.chain, Network::Testnet);
+        assert_eq!(config.bind, "0.0.0.0:9000".parse().unwrap());
+        assert_eq!(config.cors_origins, ["https://app.example.com"]);
+        assert!(!config.graphiql);
+        assert!(!config.metrics);
+        assert_eq!(config.max_depth, Some(10));
+        assert_eq!(config.max_complexity, None);
+        assert_eq!(config.max_weight, Some(300));
+        assert_eq!(config.rate_limit, 1200);
+        assert_eq!(config.proposals_db, default_proposals_db());
+        assert!(
+            config
+                .proposals_db
+                .is_none_or(|path| path.ends_with("hypersdk/proposals.db"))
+        );
+
+        let auth = config.auth.unwrap();
+        assert_eq!(auth.keys[0].role, Role::Trader);
+        assert_eq!(auth.keys[0].budget.unwrap().requests, 10);
+        assert!(auth.jwt.is_none());
+
+        assert!(toml::from_str::<Settings>("port = 8000").is_err());
+    }
+
+    #[test]
+    fn test_cli_overrides_file() {
+        let cli = Settings {
+            bind: Some("127.0.0.1:9000".parse().unwrap()),
+            ..Default::default()
+        };
+        let file = Settings {
+            chain: Some(Network::Testnet),
+            bind: Some("0.0.0.0:8000".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let config = Config::from(cli.or(file));
+        assert_eq!(config.chain, Network::Testnet);
+        assert_eq!(config.bind, "127.0.0.1:9000".parse().unwrap());
+        assert!(config.graphiql);
+        assert_eq!(Config::from(Settings::default()), Config::default());
+    }
+}
@@ -0,0 +1,323 @@
+//! GraphQL server over the HyperCore API.
+//!
+//! Serves the GraphQL endpoint at `POST /`, subscriptions at `/ws` and, unless disabled,
+//! GraphiQL at `GET /`. See [`config`] for the available settings.
+//!
+//! Mutations sign with an agent key read from `HYPERSDK_AGENT_KEY`, or from the
+//! keystore at `--keystore` decrypted with `HYPERSDK_KEYSTORE_PASSWORD`. Without a key
+//! the server runs read-only.
+//!
+//! Callers authenticate with the API keys or JWTs listed in the `auth` section of the
+//! configuration file. Trading requires the `trader` role, administration `admin`.
+//! With an agent key, the server refuses to start without `auth` unless it listens on a
+//! loopback address or `--insecure-no-auth` is passed.
+//!
+//! Multisig proposals are kept in the SQLite database at `--proposals-db`, by default
+//! `hypersdk/proposals.db` in the user's data directory.
+//!
+//! Upstream calls are charged to a shared budget of Hyperliquid request weight
+//! (`--rate-limit`, 1200 per minute by default). Operations estimated heavier than
+//! `--max-weight`, or than the weight left in the budget, are rejected before they run.
+//!
+//! Each operation and resolver is traced with `tracing` spans, logged at the level set by
+//! `RUST_LOG` (`info` by default, `debug` includes resolvers). Prometheus metrics are
+//! served at `GET /metrics` unless disabled, to authenticated callers if `auth` is set,
+//! see [`telemetry`](hypergraphql::telemetry).
+//!
+//! The server stops accepting connections on SIGINT or SIGTERM and exits once the
+//! in-flight requests and open subscriptions have finished.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run --bin hypersdk-graphql-server --features graphql -- --config server.toml
+//! HYPERSDK_CHAIN=testnet cargo run --bin hypersdk-graphql-server --features graphql -- --bind 0.0.0.0:8000
+//! ```
+//!
+//! The `schema` subcommand prints the schema for code generators, or checks it against a
+//! previous version and fails on breaking changes (see [`compat`](hypergraphql::compat)):
+//!
+//! ```bash
+//! hypersdk-graphql-server schema --sdl > schema.graphql
+//! hypersdk-graphql-server schema --check schema.graphql
+//! ```
+
+mod config;
+
+use std::{env, fs, path::PathBuf, str::FromStr, sync::Arc, time::Duration};
+
+use anyhow::Context;
+use axum::{Extension, http::HeaderValue};
+use clap::{Args, Parser, Subcommand};
+use hypersdk::{
+    hypercore::{self, PrivateKeySigner, RateLimiter, rate_limit},
+    hypergraphql::{
+        self, Agent, Evm, Proposals,
+        auth::Auth,
+        compat,
+        multisig::SqliteStore,
+        telemetry::{self, Telemetry},
+        weight::QueryWeight,
+    },
+};
+use tokio::net::TcpListener;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tracing_subscriber::EnvFilter;
+
+use crate::config::{Config, Settings};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    /// TOML configuration file
+    #[arg(short, long, env = "HYPERSDK_CONFIG")]
+    config: Option<PathBuf>,
+    #[command(flatten)]
+    settings: Settings,
+    /// Raw agent private key in hex, only read from the environment to keep it out of
+    /// the process arguments
+    #[arg(skip = env::var("HYPERSDK_AGENT_KEY").ok())]
+    agent_key: Option<String>,
+    /// Password of the keystore
+    #[arg(long, env = "HYPERSDK_KEYSTORE_PASSWORD", hide_env_values = true)]
+    keystore_password: Option<String>,
+    /// Allow signing mutations without `auth` on a non-loopback address
+    #[arg(long)]
+    insecure_no_auth: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Prints or checks the GraphQL schema instead of serving it
+    Schema(SchemaArgs),
+}
+
+#[derive(Args, Debug)]
+#[group(required = true, multiple = false)]
+struct SchemaArgs {
+    /// Print the schema in SDL
+    #[arg(long)]
+    sdl: bool,
+    /// Compare the schema with a previous SDL file, failing on breaking changes
+    #[arg(long, value_name = "FILE")]
+    check: Option<PathBuf>,
+}
+
+impl SchemaArgs {
+    fn run(&self) -> anyhow::Result<()> {
+        let sdl = hypergraphql::build_schema(hypercore::mainnet()).sdl();
+        let Some(path) = &self.check else {
+            print!("{sdl}");
+            return Ok(());
+        };
+
+        let old =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let changes =
+            compat::diff(&old, &sdl).with_context(|| format!("parsing {}", path.display()))?;
+        for change in &changes {
+            println!("{change}");
+        }
+        let breaking = changes.iter().filter(|change| change.is_breaking()).count();
+        anyhow::ensure!(breaking == 0, "{breaking} breaking changes");
+        Ok(())
+    }
+}
+
+impl Cli {
+    /// Merges the CLI and environment settings over the configuration file.
+    fn config(&self) -> anyhow::Result<Config> {
+        let file = match &self.config {
+            Some(path) => Settings::from_file(path)?,
+            None => Settings::default(),
+        };
+        Ok(self.settings.clone().or(file).into())
+    }
+
+    /// Loads the agent signer, if any.
+    fn agent(&self, config: &Config) -> anyhow::Result<Option<Agent>> {
+        let signer = match (&self.agent_key, &config.keystore) {
+            (Some(_), Some(_)) => anyhow::bail!("set either an agent key or a keystore, not both"),
+            (Some(key), None) => PrivateKeySigner::from_str(key).context("invalid agent key")?,
+            (None, Some(path)) => {
+                let password = self
+                    .keystore_password
+                    .as_ref()
+                    .context("HYPERSDK_KEYSTORE_PASSWORD is required with a keystore")?;
+                PrivateKeySigner::decrypt_keystore(path, password)
+                    .with_context(|| format!("decrypting {}", path.display()))?
+            }
+            (None, None) => return Ok(None),
+        };
+        Ok(Some(Agent::new(signer)))
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Cli::parse();
+    if let Some(Command::Schema(schema)) = &args.command {
+        return schema.run();
+    }
+
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
+        .init();
+
+    let config = args.config()?;
+
+    let mut client = hypercore::HttpClient::new(config.chain.into());
+    if let Some(base_url) = config.base_url.clone() {
+        client = client.with_url(base_url);
+    }
+    if config.rate_limit > 0 {
+        let limiter = RateLimiter::new(config.rate_limit, rate_limit::WEIGHT_PERIOD);
+        client = client.with_rate_limiter(Arc::new(limiter));
+    }
+
+    let mut weight = QueryWeight::new();
+    if let Some(max_weight) = config.max_weight {
+        weight = weight.limit(max_weight);
+    }
+    let proposals_db = config
+        .proposals_db
+        .as_ref()
+        .context("no data directory for the proposals database, set --proposals-db")?;
+    if let Some(dir) = proposals_db.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    }
+    let store = SqliteStore::open(proposals_db)?;
+    let mut builder = hypergraphql::schema_builder(client)
+        .extension(Telemetry)
+        .extension(weight)
+        .data(Proposals::new(store));
+    if let Some(url) = config.evm_rpc_url.clone() {
+        builder = builder.data(Evm::with_url(url));
+    }
+    if let Some(depth) = config.max_depth {
+        builder = builder.limit_depth(depth);
+    }
+    if let Some(complexity) = config.max_complexity {
+        builder = builder.limit_complexity(complexity);
+    }
+    let agent = args.agent(&config)?;
+    if agent.is_some()
+        && config.auth.is_none()
+        && !config.bind.ip().is_loopback()
+        && !args.insecure_no_auth
+    {
+        anyhow::bail!(
+            "refusing to sign mutations for anyone reaching {} without authentication: \
+             configure `auth`, bind a loopback address or pass --insecure-no-auth",
+            config.bind
+        );
+    }
+    match agent {
+        Some(agent) => {
+            log::info!("Signing mutations with agent {}", agent.address());
+            builder = builder.data(agent);
+        }
+        None => log::info!("No agent key configured, mutations are disabled"),
+    }
+    let schema = builder.finish();
+
+    let app = if config.graphiql {
+        hypergraphql::router(schema)
+    } else {
+        hypergraphql::api_router(schema)
+    };
+    let app = if config.metrics {
+        let handle = telemetry::install_recorder()?;
+        let upkeep = handle.clone();
+        tokio::spawn(async move {
+            // drains the histograms between scrapes
+            let mut interval = tokio::time::interval(Duration::from_secs(5));
+            loop {
+                interval.tick().await;
+                upkeep.run_upkeep();
+            }
+        });
+        log::info!("Serving metrics at http://{}/metrics", config.bind);
+        app.merge(telemetry::metrics_router(handle))
+    } else {
+        app
+    };
+    let app = match config.auth.clone() {
+        Some(auth) => {
+            log::info!(
+                "Authentication enabled: {} API keys, JWTs {}",
+                auth.keys.len(),
+                if auth.jwt.is_some() {
+                    "accepted"
+                } else {
+                    "rejected"
+                }
+            );
+            app.layer(Extension(Arc::new(Auth::new(auth))))
+        }
+        None => {
+            log::warn!("No authentication configured, anyone reaching the server can use it");
+            app
+        }
+    };
+    let app = app.layer(cors(&config.cors_origins)?);
+
+    let listener = TcpListener::bind(config.bind)
+        .await
+        .with_context(|| format!("binding {}", config.bind))?;
+    log::info!("Listening on http://{}", config.bind);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown())
+        .await?;
+
+    log::info!("Shut down");
+
+    Ok(())
+}
+
+/// Builds the CORS layer. No origins disables CORS, `*` allows any origin.
+fn cors(origins: &[String]) -> anyhow::Result<CorsLayer> {
+    let origin = if origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::from(Any)
+    } else {
+        let origins = origins
+            .iter()
+            .map(|origin| {
+                HeaderValue::from_str(origin).with_context(|| format!("invalid origin {origin}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        AllowOrigin::list(origins)
+    };
+    Ok(CorsLayer::new()
+        .allow_origin(origin)
+        .allow_methods(Any)
+        .allow_headers(Any))
+}
+
+/// Resolves on SIGINT or SIGTERM.
+async fn shutdown() {
+    let interrupt = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for SIGINT");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = interrupt => {},
+        _ = terminate => {},
+    }
+    log::info!("Shutting down");
+}
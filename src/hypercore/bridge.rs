@@ -0,0 +1,125 @@
+//! Arbitrum bridge for USDC deposits.
+//!
+//! HyperCore credits USDC sent to the bridge contract on Arbitrum to the perp
+//! balance of the sender, usually within a minute. Withdrawals go the other way
+//! with [`HttpClient::withdraw`](super::HttpClient::withdraw).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::hypercore::{Chain, PrivateKeySigner, bridge};
+//! use hypersdk::hyperevm::ProviderBuilder;
+//! use rust_decimal::dec;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let signer: PrivateKeySigner = "your_key".parse()?;
+//! let provider = ProviderBuilder::new()
+//!     .wallet(signer)
+//!     .connect("https://arb1.arbitrum.io/rpc")
+//!     .await?;
+//!
+//! let tx_hash = bridge::deposit(&provider, Chain::Mainnet, dec!(100)).await?;
+//! println!("deposit sent in {tx_hash}");
+//! # Ok(())
+//! # }
+//! ```
+
+use alloy::primitives::{Address, B256, address};
+use rust_decimal::{Decimal, dec};
+
+use super::Chain;
+use crate::hyperevm::{ERC20, Provider, to_wei};
+
+/// Bridge contract on Arbitrum One.
+pub const BRIDGE_MAINNET_ADDRESS: Address = address!("0x2Df1c51E09aECF9cacB7bc98cB1742757f163dF7");
+
+/// Bridge contract on Arbitrum Sepolia.
+pub const BRIDGE_TESTNET_ADDRESS: Address = address!("0x08cfc1B6b2dCF36A1480b99353A354AA8AC56f89");
+
+/// Native USDC on Arbitrum One.
+pub const USDC_MAINNET_ADDRESS: Address = address!("0xaf88d065e77c8cC2239327C5EDb3A432268e5831");
+
+/// USDC accepted by the testnet bridge on Arbitrum Sepolia.
+pub const USDC_TESTNET_ADDRESS: Address = address!("0x1baAbB04529D43a73232B713C0FE471f7c7334d5");
+
+/// Decimals of USDC on Arbitrum.
+pub const USDC_DECIMALS: u32 = 6;
+
+/// Smallest deposit credited by the bridge.
+///
+/// Smaller deposits are not credited and are lost.
+pub const MIN_DEPOSIT: Decimal = dec!(5);
+
+/// Returns the bridge contract for `chain`.
+#[must_use]
+pub fn bridge_address(chain: Chain) -> Address {
+    if chain.is_mainnet() {
+        BRIDGE_MAINNET_ADDRESS
+    } else {
+        BRIDGE_TESTNET_ADDRESS
+    }
+}
+
+/// Returns the USDC contract accepted by the bridge of `chain`.
+#[must_use]
+pub fn usdc_address(chain: Chain) -> Address {
+    if chain.is_mainnet() {
+        USDC_MAINNET_ADDRESS
+    } else {
+        USDC_TESTNET_ADDRESS
+    }
+}
+
+/// Deposits `amount` USDC from Arbitrum to HyperCore.
+///
+/// `provider` must be connected to Arbitrum (One for mainnet, Sepolia for testnet)
+/// with a wallet; the USDC is credited to the wallet's address on HyperCore. Waits
+/// for the transfer to be mined and returns its hash.
+///
+/// Fails without sending anything if `amount` is below [`MIN_DEPOSIT`] or has more
+/// than [`USDC_DECIMALS`] decimals.
+pub async fn deposit<P: Provider>(
+    provider: &P,
+    chain: Chain,
+    amount: Decimal,
+) -> anyhow::Result<B256> {
+    if amount < MIN_DEPOSIT {
+        anyhow::bail!("deposit of {amount} USDC is below the minimum of {MIN_DEPOSIT} USDC");
+    }
+    if amount.normalize().scale() > USDC_DECIMALS {
+        anyhow::bail!("deposit of {amount} USDC has more than {USDC_DECIMALS} decimals");
+    }
+
+    let usdc = ERC20::new(usdc_address(chain), provider);
+    let tx_hash = usdc
+        .transfer(bridge_address(chain), to_wei(amount, USDC_DECIMALS))
+        .send()
+        .await?
+        .watch()
+        .await?;
+    Ok(tx_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::providers::ProviderBuilder;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_deposit_checks_amount() {
+        let provider = ProviderBuilder::new().connect_http("http://127.0.0.1:1".parse().unwrap());
+
+        let err = deposit(&provider, Chain::Mainnet, dec!(4.99))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("minimum"));
+        let err = deposit(&provider, Chain::Mainnet, dec!(5.0000001))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("decimals"));
+
+        assert_eq!(bridge_address(Chain::Testnet), BRIDGE_TESTNET_ADDRESS);
+        assert_eq!(usdc_address(Chain::Mainnet), USDC_MAINNET_ADDRESS);
+    }
+}
@@ -44,6 +44,15 @@ pub enum Error {
         message: String,
     },
 
+    /// Invalid parameters of a non-order action.
+    ///
+    /// A transfer, vault or account action doesn't meet exchange requirements.
+    /// Common causes: amount not positive or too precise, empty name.
+    InvalidAction {
+        /// Description of what's wrong with the action
+        message: String,
+    },
+
     /// WebSocket connection error.
     ///
     /// Failed to establish or maintain WebSocket connection for real-time data.
@@ -92,6 +101,45 @@ impl Error {
         )
     }
 
+    /// Returns a stable, machine-readable code for the error variant.
+    ///
+    /// | Variant | Code |
+    /// |---|---|
+    /// | `Network` | `NETWORK_ERROR` |
+    /// | `Api` | `API_ERROR` |
+    /// | `Json` | `JSON_ERROR` |
+    /// | `Signing` | `SIGNING_ERROR` |
+    /// | `InvalidOrder` | `INVALID_ORDER` |
+    /// | `InvalidAction` | `INVALID_ACTION` |
+    /// | `WebSocket` | `WEBSOCKET_ERROR` |
+    /// | `InvalidAddress` | `INVALID_ADDRESS` |
+    /// | `Timeout` | `TIMEOUT` |
+    /// | `Other` | `INTERNAL_ERROR` |
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use hypersdk::hypercore::Error;
+    ///
+    /// assert_eq!(Error::Timeout.code(), "TIMEOUT");
+    /// assert_eq!(Error::Api("Insufficient margin".into()).code(), "API_ERROR");
+    /// ```
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Network(_) => "NETWORK_ERROR",
+            Error::Api(_) => "API_ERROR",
+            Error::Json(_) => "JSON_ERROR",
+            Error::Signing(_) => "SIGNING_ERROR",
+            Error::InvalidOrder { .. } => "INVALID_ORDER",
+            Error::InvalidAction { .. } => "INVALID_ACTION",
+            Error::WebSocket(_) => "WEBSOCKET_ERROR",
+            Error::InvalidAddress(_) => "INVALID_ADDRESS",
+            Error::Timeout => "TIMEOUT",
+            Error::Other(_) => "INTERNAL_ERROR",
+        }
+    }
+
     /// Returns true if this is a network-related error.
     #[must_use]
     pub fn is_network_error(&self) -> bool {
@@ -113,6 +161,7 @@ impl fmt::Display for Error {
             Error::Json(e) => write!(f, "JSON error: {}", e),
             Error::Signing(e) => write!(f, "Signing error: {}", e),
             Error::InvalidOrder { message } => write!(f, "Invalid order: {}", message),
+            Error::InvalidAction { message } => write!(f, "Invalid action: {}", message),
             Error::WebSocket(e) => write!(f, "WebSocket error: {}", e),
             Error::InvalidAddress(e) => write!(f, "Invalid address: {}", e),
             Error::Timeout => write!(f, "Operation timed out"),
@@ -168,10 +217,26 @@ impl From<std::io::Error> for Error {
     }
 }
 
-// Allow converting anyhow errors to our error type for compatibility
+// Allow converting anyhow errors to our error type for compatibility.
+// Errors the SDK wrapped in `anyhow` keep their variant.
 impl From<anyhow::Error> for Error {
     fn from(e: anyhow::Error) -> Self {
-        Error::Other(e.to_string())
+        let e = match e.downcast::<Error>() {
+            Ok(e) => return e,
+            Err(e) => e,
+        };
+        let e = match e.downcast::<reqwest::Error>() {
+            Ok(e) => return e.into(),
+            Err(e) => e,
+        };
+        let e = match e.downcast::<serde_json::Error>() {
+            Ok(e) => return e.into(),
+            Err(e) => e,
+        };
+        match e.downcast::<SignerError>() {
+            Ok(e) => e.into(),
+            Err(e) => Error::Other(e.to_string()),
+        }
     }
 }
 
@@ -233,3 +298,24 @@ where
 }
 
 impl<T> std::error::Error for ActionError<T> where T: fmt::Display + fmt::Debug {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anyhow_keeps_variant() {
+        let err = Error::from(anyhow::Error::new(Error::Api("Insufficient margin".into())));
+        assert_eq!(err.code(), "API_ERROR");
+        assert_eq!(err.to_string(), "API error: Insufficient margin");
+
+        let json = serde_json::from_str::<u64>("x").unwrap_err();
+        let err = Error::from(anyhow::Error::new(json));
+        assert!(matches!(err, Error::Json(_)));
+        assert!(!err.is_retryable());
+
+        let err = Error::from(anyhow::anyhow!("boom"));
+        assert_eq!(err.code(), "INTERNAL_ERROR");
+        assert_eq!(err.to_string(), "boom");
+    }
+}
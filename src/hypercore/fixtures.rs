@@ -0,0 +1,45 @@
+//! Markets and tokens shared by the unit tests.
+
+use super::{PerpMarket, SpotToken, build_perp_price_ticks};
+
+/// USDC, the collateral of the default DEX.
+pub(crate) fn usdc() -> SpotToken {
+    SpotToken {
+        name: "USDC".into(),
+        index: 0,
+        token_id: Default::default(),
+        evm_contract: None,
+        cross_chain_address: None,
+        sz_decimals: 8,
+        wei_decimals: 8,
+        evm_extra_decimals: 0,
+    }
+}
+
+/// PURR, a spot token with 5 wei decimals.
+pub(crate) fn purr() -> SpotToken {
+    SpotToken {
+        name: "PURR".into(),
+        index: 1,
+        token_id: "0xc1fb593aeffbeb02f85e0308e9956a90".parse().unwrap(),
+        evm_contract: None,
+        cross_chain_address: None,
+        sz_decimals: 0,
+        wei_decimals: 5,
+        evm_extra_decimals: 0,
+    }
+}
+
+/// BTC perpetual of the default DEX, cross margin up to 40x.
+pub(crate) fn btc() -> PerpMarket {
+    PerpMarket {
+        name: "BTC".into(),
+        index: 0,
+        sz_decimals: 5,
+        collateral: usdc(),
+        max_leverage: 40,
+        isolated_margin: false,
+        margin_mode: None,
+        table: build_perp_price_ticks(5),
+    }
+}
@@ -38,36 +38,53 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Metrics
+//!
+//! With the `metrics` cargo feature, every request is recorded through the
+//! [metrics](https://docs.rs/metrics) facade, to whichever recorder the application
+//! installs:
+//!
+//! - `hypersdk_upstream_request_duration_seconds` (histogram): request latency, by
+//!   `endpoint` (`info` or `exchange`) and `type` (info request or action type)
+//! - `hypersdk_upstream_errors_total` (counter): failed requests, by `endpoint`, `type`
+//!   and `code`, the [`Error::code`] of the failure. Actions rejected by the exchange
+//!   count as `API_ERROR`.
 
 use std::{
     collections::{HashMap, VecDeque},
-    time::Duration,
+    sync::Arc,
+    time::{Duration, Instant},
 };
 
 use alloy::{
-    primitives::Address,
+    primitives::{Address, B128},
     signers::{Signer, SignerSync},
 };
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use serde::Deserialize;
+use serde::{Deserialize, de::DeserializeOwned};
 use url::Url;
 
-use super::signing::*;
+use super::{Error, rate_limit, signing::*};
 use crate::hypercore::{
-    ActionError, ApiAgent, CandleInterval, Chain, Cloid, Dex, MultiSigConfig, OidOrCloid,
-    PerpMarket, Signature, SpotMarket, SpotToken,
+    ActionError, ApiAgent, CandleInterval, Chain, Cloid, Dex, MarketSnapshot, MultiSigConfig,
+    OidOrCloid, PerpMarket, RateLimiter, Signature, SpotMarket, SpotMarketSnapshot, SpotToken,
     api::{
-        Action, ActionRequest, ApproveAgent, ConvertToMultiSigUser, OkResponse, Response,
-        SignersConfig,
+        Action, ActionRequest, ApproveAgent, ConvertToMultiSigUser, CreateSubAccount, CreateVault,
+        OkResponse, Response, SignersConfig, SubAccountModify, SubAccountSpotTransfer,
+        SubAccountTransfer, TopUpIsolatedOnlyMargin, UpdateIsolatedMargin, UpdateLeverage,
+        VaultDistribute, VaultModify, VaultTransfer,
     },
     mainnet_url, testnet_url,
     types::{
         BasicOrder, BatchCancel, BatchCancelCloid, BatchModify, BatchOrder, ClearinghouseState,
-        Fill, FundingRate, InfoRequest, OrderResponseStatus, OrderUpdate, ScheduleCancel,
-        SendAsset, SendToken, SpotSend, SubAccount, UsdSend, UserBalance, UserRole,
-        UserVaultEquity, VaultDetails,
+        Fill, FrontendOrder, FundingPayment, FundingRate, FundingSpread, InfoRequest, L2Book,
+        LedgerUpdate, OrderResponseStatus, OrderUpdate, PredictedFundings, ScheduleCancel,
+        SendAsset, SendToken, SpotSend, SubAccount, TokenDetails, TwapCancel, TwapCancelStatus,
+        TwapOrder, TwapOrderStatus, TwapPlacement, UsdSend, UserBalance, UserFees, UserRateLimit,
+        UserRole, UserVaultEquity, VaultDetails, VaultPortfolio, Withdraw,
     },
 };
 
@@ -84,10 +101,12 @@ use crate::hypercore::{
 /// let client = hypercore::mainnet();
 /// // Use client for API calls
 /// ```
+#[derive(Clone)]
 pub struct Client {
     http_client: reqwest::Client,
     base_url: Url,
     chain: Chain,
+    rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 impl Client {
@@ -129,6 +148,7 @@ impl Client {
             http_client,
             base_url,
             chain,
+            rate_limiter: None,
         }
     }
 
@@ -151,12 +171,60 @@ impl Client {
         Self { base_url, ..self }
     }
 
+    /// Charges the weight of every request to a shared [`RateLimiter`].
+    ///
+    /// Requests wait until their base weight is available, and endpoints charged by
+    /// response size charge the extra weight once the response arrives. Clones of the
+    /// client share the limiter. See [`rate_limit`](super::rate_limit).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use hypersdk::hypercore::{self, RateLimiter};
+    ///
+    /// let limiter = Arc::new(RateLimiter::default());
+    /// let client = hypercore::mainnet().with_rate_limiter(limiter.clone());
+    /// assert_eq!(client.rate_limiter().unwrap().available(), 1200);
+    /// ```
+    pub fn with_rate_limiter(self, rate_limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            rate_limiter: Some(rate_limiter),
+            ..self
+        }
+    }
+
+    /// Returns the rate limiter requests are charged to, if any.
+    #[must_use]
+    pub fn rate_limiter(&self) -> Option<&Arc<RateLimiter>> {
+        self.rate_limiter.as_ref()
+    }
+
     /// Returns the chain this client is configured for.
     #[must_use]
     pub const fn chain(&self) -> Chain {
         self.chain
     }
 
+    /// Returns the WebSocket URL (`wss://<host>/ws`) matching this client's base URL.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use hypersdk::hypercore;
+    ///
+    /// let url = hypercore::mainnet().websocket_url();
+    /// assert_eq!(url.as_str(), "wss://api.hyperliquid.xyz/ws");
+    /// ```
+    #[must_use]
+    pub fn websocket_url(&self) -> Url {
+        let mut url = self.base_url.clone();
+        let _ = url.set_scheme("wss");
+        url.set_path("/ws");
+        url
+    }
+
     /// Creates a WebSocket connection using the same base URL as this HTTP client.
     ///
     /// # Example
@@ -172,10 +240,7 @@ impl Client {
     /// # }
     /// ```
     pub fn websocket(&self) -> super::WebSocket {
-        let mut url = self.base_url.clone();
-        let _ = url.set_scheme("wss");
-        url.set_path("/ws");
-        super::WebSocket::new(url)
+        super::WebSocket::new(self.websocket_url())
     }
 
     /// Creates a WebSocket connection without TLS (uses `ws://` instead of `wss://`).
@@ -207,7 +272,7 @@ impl Client {
     /// ```
     #[inline(always)]
     pub async fn perps(&self) -> Result<Vec<PerpMarket>> {
-        super::perp_markets(self.base_url.clone(), self.http_client.clone(), None).await
+        self.perp_markets(None).await
     }
 
     /// Fetches perpetual markets from a specific DEX.
@@ -242,7 +307,19 @@ impl Client {
     /// ```
     #[inline(always)]
     pub async fn perps_from(&self, dex: Dex) -> Result<Vec<PerpMarket>> {
-        super::perp_markets(self.base_url.clone(), self.http_client.clone(), Some(dex)).await
+        self.perp_markets(Some(dex)).await
+    }
+
+    async fn perp_markets(&self, dex: Option<Dex>) -> Result<Vec<PerpMarket>> {
+        // spotMeta for the collateral token, then meta
+        let spot = self.info(&InfoRequest::SpotMeta).await?;
+        let data = self
+            .info(&InfoRequest::Meta {
+                dex: dex.as_ref().map(|dex| dex.name.clone()),
+            })
+            .await?;
+
+        Ok(super::build_perp_markets(data, &spot, dex.as_ref()))
     }
 
     /// Fetches all available perpetual futures DEXes.
@@ -266,7 +343,9 @@ impl Client {
     /// ```
     #[inline(always)]
     pub async fn perp_dexs(&self) -> Result<Vec<Dex>> {
-        super::perp_dexs(self.base_url.clone(), self.http_client.clone()).await
+        let data = self.info(&InfoRequest::PerpDexs).await?;
+
+        Ok(super::build_perp_dexs(data))
     }
 
     /// Fetches all available spot markets.
@@ -288,7 +367,76 @@ impl Client {
     /// ```
     #[inline(always)]
     pub async fn spot(&self) -> Result<Vec<SpotMarket>> {
-        super::spot_markets(self.base_url.clone(), self.http_client.clone()).await
+        let data = self.info(&InfoRequest::SpotMeta).await?;
+
+        Ok(super::build_spot_markets(data))
+    }
+
+    /// Fetches the perpetual markets of the default DEX with their live statistics.
+    ///
+    /// Each [`MarketSnapshot`] joins the market metadata with its mark and oracle
+    /// prices, open interest, 24h volume, premium, impact prices and funding rate.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let mut snapshots = client.meta_and_asset_ctxs().await?;
+    /// snapshots.sort_by(|a, b| b.ctx.day_ntl_vlm.cmp(&a.ctx.day_ntl_vlm));
+    ///
+    /// for snapshot in snapshots.iter().take(10) {
+    ///     println!("{}: {} 24h volume", snapshot.market.name, snapshot.ctx.day_ntl_vlm);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn meta_and_asset_ctxs(&self) -> Result<Vec<MarketSnapshot>> {
+        self.market_snapshots(None).await
+    }
+
+    /// Fetches the perpetual markets of a HIP-3 DEX with their live statistics.
+    ///
+    /// See [`meta_and_asset_ctxs`](Self::meta_and_asset_ctxs).
+    pub async fn meta_and_asset_ctxs_from(&self, dex: Dex) -> Result<Vec<MarketSnapshot>> {
+        self.market_snapshots(Some(dex)).await
+    }
+
+    /// Fetches the spot markets with their live statistics.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    ///
+    /// for snapshot in client.spot_meta_and_asset_ctxs().await? {
+    ///     println!("{}: {} ({:?})",
+    ///         snapshot.market.symbol(), snapshot.ctx.mark_px, snapshot.ctx.day_change());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn spot_meta_and_asset_ctxs(&self) -> Result<Vec<SpotMarketSnapshot>> {
+        let data = self.info(&InfoRequest::SpotMetaAndAssetCtxs).await?;
+
+        Ok(super::build_spot_market_snapshots(data))
+    }
+
+    async fn market_snapshots(&self, dex: Option<Dex>) -> Result<Vec<MarketSnapshot>> {
+        // spotMeta for the collateral token, then metaAndAssetCtxs
+        let spot = self.info(&InfoRequest::SpotMeta).await?;
+        let data = self
+            .info(&InfoRequest::MetaAndAssetCtxs {
+                dex: dex.as_ref().map(|dex| dex.name.clone()),
+            })
+            .await?;
+
+        Ok(super::build_market_snapshots(data, &spot, dex.as_ref()))
     }
 
     /// Fetches all available spot tokens.
@@ -310,7 +458,32 @@ impl Client {
     /// ```
     #[inline(always)]
     pub async fn spot_tokens(&self) -> Result<Vec<SpotToken>> {
-        super::spot_tokens(self.base_url.clone(), self.http_client.clone()).await
+        let data = self.info(&InfoRequest::SpotMeta).await?;
+
+        Ok(super::build_spot_tokens(data))
+    }
+
+    /// Returns the supply, decimals, prices and deployment details of a spot token.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let tokens = client.spot_tokens().await?;
+    /// let hype = tokens.iter().find(|token| token.name == "HYPE").unwrap();
+    ///
+    /// let details = client.token_details(hype.token_id).await?;
+    /// println!("circulating supply: {}", details.circulating_supply);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn token_details(&self, token_id: B128) -> Result<TokenDetails> {
+        let data = self.info(&InfoRequest::TokenDetails { token_id }).await?;
+
+        Ok(data)
     }
 
     /// Returns all open orders for a user.
@@ -333,17 +506,18 @@ impl Client {
     /// # }
     /// ```
     pub async fn open_orders(&self, user: Address) -> Result<Vec<BasicOrder>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
+        let data = self.info(&InfoRequest::FrontendOpenOrders { user }).await?;
 
-        let data = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::FrontendOpenOrders { user })
-            .send()
-            .await?
-            .json()
-            .await?;
+        Ok(data)
+    }
+
+    /// Returns all open orders for a user, with their trigger and TP/SL details.
+    ///
+    /// Unlike [`open_orders`](Self::open_orders), trigger orders are reported with their
+    /// trigger price and condition, and TP/SL orders attached to an order are listed as
+    /// its `children`.
+    pub async fn frontend_open_orders(&self, user: Address) -> Result<Vec<FrontendOrder>> {
+        let data = self.info(&InfoRequest::FrontendOpenOrders { user }).await?;
 
         Ok(data)
     }
@@ -368,16 +542,44 @@ impl Client {
     /// # }
     /// ```
     pub async fn all_mids(&self) -> Result<HashMap<String, Decimal>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
+        let data = self.info(&InfoRequest::AllMids).await?;
+
+        Ok(data)
+    }
 
+    /// Returns an order book snapshot of a market.
+    ///
+    /// Levels are aggregated to `n_sig_figs` significant figures (2 to 5), or not at all
+    /// if `None`. `mantissa` (1, 2 or 5) further aggregates 5 significant figures. At
+    /// most 20 levels are returned per side.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let book = client.l2_book("BTC", Some(5), Some(2)).await?;
+    ///
+    /// if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+    ///     println!("BTC: {} / {}", bid.px, ask.px);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn l2_book(
+        &self,
+        coin: impl Into<String>,
+        n_sig_figs: Option<u32>,
+        mantissa: Option<u32>,
+    ) -> Result<L2Book> {
         let data = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::AllMids)
-            .send()
-            .await?
-            .json()
+            .info(&InfoRequest::L2Book {
+                coin: coin.into(),
+                n_sig_figs,
+                mantissa,
+            })
             .await?;
 
         Ok(data)
@@ -385,47 +587,163 @@ impl Client {
 
     /// Returns the user's historical orders.
     pub async fn historical_orders(&self, user: Address) -> Result<Vec<BasicOrder>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
+        let data = self.info(&InfoRequest::HistoricalOrders { user }).await?;
+
+        Ok(data)
+    }
 
+    /// Returns the user's fills.
+    pub async fn user_fills(&self, user: Address) -> Result<Vec<Fill>> {
+        let data = self.info(&InfoRequest::UserFills { user }).await?;
+
+        Ok(data)
+    }
+
+    /// Returns the user's fills within a time range.
+    ///
+    /// At most 2000 fills are returned per call, starting from `start_time`. Only the
+    /// 10000 most recent fills of a user are available.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user = "0x0000000000000000000000000000000000000000".parse()?;
+    ///
+    /// let end_time = chrono::Utc::now().timestamp_millis() as u64;
+    /// let start_time = end_time - 7 * 24 * 60 * 60 * 1000; // 7 days ago
+    ///
+    /// let fills = client.user_fills_by_time(user, start_time, Some(end_time)).await?;
+    /// println!("{} fills this week", fills.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn user_fills_by_time(
+        &self,
+        user: Address,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<Fill>> {
         let data = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::HistoricalOrders { user })
-            .send()
-            .await?
-            .json()
+            .info(&InfoRequest::UserFillsByTime {
+                user,
+                start_time,
+                end_time,
+            })
             .await?;
 
         Ok(data)
     }
 
-    /// Returns the user's fills.
-    pub async fn user_fills(&self, user: Address) -> Result<Vec<Fill>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
+    /// Returns the user's funding payments within a time range.
+    ///
+    /// At most 500 payments are returned per call, starting from `start_time`.
+    pub async fn user_funding(
+        &self,
+        user: Address,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<FundingPayment>> {
+        let data = self
+            .info(&InfoRequest::UserFunding {
+                user,
+                start_time,
+                end_time,
+            })
+            .await?;
+
+        Ok(data)
+    }
 
+    /// Returns the user's deposits, withdrawals, transfers and vault operations within a
+    /// time range.
+    ///
+    /// At most 500 updates are returned per call, starting from `start_time`.
+    pub async fn user_non_funding_ledger_updates(
+        &self,
+        user: Address,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<LedgerUpdate>> {
         let data = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::UserFills { user })
-            .send()
-            .await?
-            .json()
+            .info(&InfoRequest::UserNonFundingLedgerUpdates {
+                user,
+                start_time,
+                end_time,
+            })
             .await?;
 
         Ok(data)
     }
 
-    /// Returns the status of an order.
+    /// Returns the user's fee rates and daily volume.
+    pub async fn user_fees(&self, user: Address) -> Result<UserFees> {
+        let data = self.info(&InfoRequest::UserFees { user }).await?;
+
+        Ok(data)
+    }
+
+    /// Returns the user's address-based request rate limit.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    /// use hypersdk::Address;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user: Address = "0x...".parse()?;
+    ///
+    /// let limit = client.user_rate_limit(user).await?;
+    /// println!("{} requests left", limit.remaining());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn user_rate_limit(&self, user: Address) -> Result<UserRateLimit> {
+        let data = self.info(&InfoRequest::UserRateLimit { user }).await?;
+
+        Ok(data)
+    }
+
+    /// Returns the user's account value and PnL history.
+    ///
+    /// Returns a list of period name (`day`, `week`, `month`, `allTime`, and the same
+    /// for perps only, e.g. `perpDay`) and portfolio data.
+    pub async fn portfolio(&self, user: Address) -> Result<Vec<(String, VaultPortfolio)>> {
+        let data = self.info(&InfoRequest::Portfolio { user }).await?;
+
+        Ok(data)
+    }
+
+    /// Returns the status of an order, by order ID or client order ID.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, Cloid};
+    /// use hypersdk::Address;
+    /// use either::Either;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user: Address = "0x...".parse()?;
+    /// let cloid: Cloid = "0x1234567890abcdef1234567890abcdef".parse()?;
+    ///
+    /// if let Some(order) = client.order_status(user, Either::Right(cloid)).await? {
+    ///     println!("{:?}", order.status);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn order_status(
         &self,
         user: Address,
         oid: OidOrCloid,
     ) -> Result<Option<OrderUpdate>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
         #[serde(tag = "status")]
@@ -434,14 +752,7 @@ impl Client {
             UnknownOid,
         }
 
-        let data: Response = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::OrderStatus { user, oid })
-            .send()
-            .await?
-            .json()
-            .await?;
+        let data: Response = self.info(&InfoRequest::OrderStatus { user, oid }).await?;
 
         Ok(match data {
             Response::Order { order } => Some(order),
@@ -498,9 +809,6 @@ impl Client {
         start_time: u64,
         end_time: u64,
     ) -> Result<Vec<super::types::Candle>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
         let req = super::types::CandleSnapshotRequest {
             coin: coin.into(),
             interval,
@@ -508,14 +816,7 @@ impl Client {
             end_time,
         };
 
-        let data = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::CandleSnapshot { req })
-            .send()
-            .await?
-            .json()
-            .await?;
+        let data = self.info(&InfoRequest::CandleSnapshot { req }).await?;
 
         Ok(data)
     }
@@ -542,21 +843,13 @@ impl Client {
     /// # }
     /// ```
     pub async fn user_balances(&self, user: Address) -> Result<Vec<UserBalance>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
         #[derive(Deserialize)]
         struct Balances {
             balances: Vec<UserBalance>,
         }
 
         let data: Balances = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::SpotClearinghouseState { user })
-            .send()
-            .await?
-            .json()
+            .info(&InfoRequest::SpotClearinghouseState { user })
             .await?;
 
         Ok(data.balances)
@@ -601,17 +894,7 @@ impl Client {
     /// # }
     /// ```
     pub async fn clearinghouse_state(&self, user: Address) -> Result<ClearinghouseState> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
-        let data = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::ClearinghouseState { user })
-            .send()
-            .await?
-            .json()
-            .await?;
+        let data = self.info(&InfoRequest::ClearinghouseState { user }).await?;
         Ok(data)
     }
 
@@ -658,20 +941,64 @@ impl Client {
         start_time: u64,
         end_time: Option<u64>,
     ) -> Result<Vec<FundingRate>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
         let data = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::FundingHistory {
+            .info(&InfoRequest::FundingHistory {
                 coin: coin.into(),
                 start_time,
                 end_time,
             })
-            .send()
-            .await?
-            .json()
+            .await?;
+
+        Ok(data)
+    }
+
+    /// Returns the predicted next funding of every coin on Hyperliquid and the other
+    /// venues it reports.
+    ///
+    /// Use [`FundingSpread::rank`](super::FundingSpread::rank) or
+    /// [`funding_spreads`](Self::funding_spreads) to compare venues.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    ///
+    /// for fundings in client.predicted_fundings().await? {
+    ///     if let Some(hl) = fundings.hyperliquid() {
+    ///         println!("{}: {} annualized", fundings.coin, hl.annualized_rate());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn predicted_fundings(&self) -> Result<Vec<PredictedFundings>> {
+        let data = self.info(&InfoRequest::PredictedFundings).await?;
+
+        Ok(data)
+    }
+
+    /// Returns coins ranked by their widest annualized funding spread between venues,
+    /// widest first.
+    ///
+    /// See [`FundingSpread::rank`](super::FundingSpread::rank).
+    pub async fn funding_spreads(&self) -> Result<Vec<FundingSpread>> {
+        let fundings = self.predicted_fundings().await?;
+
+        Ok(FundingSpread::rank(&fundings))
+    }
+
+    /// Returns the names of the perpetual markets at their open interest cap, on the
+    /// given DEX or the default one.
+    ///
+    /// Orders increasing the open interest of these markets are rejected.
+    pub async fn perps_at_open_interest_cap(&self, dex: Option<Dex>) -> Result<Vec<String>> {
+        let data = self
+            .info(&InfoRequest::PerpsAtOpenInterestCap {
+                dex: dex.map(|dex| dex.name),
+            })
             .await?;
 
         Ok(data)
@@ -710,16 +1037,8 @@ impl Client {
     /// # }
     /// ```
     pub async fn multi_sig_config(&self, user: Address) -> Result<MultiSigConfig> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
         let resp = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::UserToMultiSigSigners { user })
-            .send()
-            .await?
-            .json()
+            .info(&InfoRequest::UserToMultiSigSigners { user })
             .await?;
         Ok(resp)
     }
@@ -746,17 +1065,7 @@ impl Client {
     /// }
     /// ```
     pub async fn api_agents(&self, user: Address) -> Result<Vec<ApiAgent>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
-        let resp = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::ExtraAgents { user })
-            .send()
-            .await?
-            .json()
-            .await?;
+        let resp = self.info(&InfoRequest::ExtraAgents { user }).await?;
         Ok(resp)
     }
 
@@ -800,19 +1109,11 @@ impl Client {
         vault_address: Address,
         user: Option<Address>,
     ) -> Result<VaultDetails> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
         let resp = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::VaultDetails {
+            .info(&InfoRequest::VaultDetails {
                 vault_address,
                 user,
             })
-            .send()
-            .await?
-            .json()
             .await?;
         Ok(resp)
     }
@@ -846,17 +1147,7 @@ impl Client {
     ///
     /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint#retrieve-a-users-vault-deposits>
     pub async fn user_vault_equities(&self, user: Address) -> Result<Vec<UserVaultEquity>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
-        let resp = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::UserVaultEquities { user })
-            .send()
-            .await?
-            .json()
-            .await?;
+        let resp = self.info(&InfoRequest::UserVaultEquities { user }).await?;
         Ok(resp)
     }
 
@@ -893,17 +1184,7 @@ impl Client {
     ///
     /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint#query-a-users-role>
     pub async fn user_role(&self, user: Address) -> Result<UserRole> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
-        let resp = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::UserRole { user })
-            .send()
-            .await?
-            .json()
-            .await?;
+        let resp = self.info(&InfoRequest::UserRole { user }).await?;
         Ok(resp)
     }
 
@@ -918,81 +1199,539 @@ impl Client {
     ///
     /// # Notes
     ///
-    /// Subaccounts do not have private keys. To perform actions on behalf of a
-    /// subaccount, signing should be done by the master account with the
-    /// `vault_address` parameter set to the subaccount's address.
+    /// Subaccounts do not have private keys. To perform actions on behalf of a
+    /// subaccount, signing should be done by the master account with the
+    /// `vault_address` parameter set to the subaccount's address.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore;
+    /// use hypersdk::Address;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let master: Address = "0x...".parse()?;
+    ///
+    /// let subaccounts = client.subaccounts(master).await?;
+    /// for sub in subaccounts {
+    ///     println!("Subaccount '{}': {:?}", sub.name, sub.sub_account_user);
+    ///     println!("  Account value: {}", sub.clearinghouse_state.margin_summary.account_value);
+    ///     println!("  Withdrawable: {}", sub.clearinghouse_state.withdrawable);
+    ///
+    ///     // Check spot balances
+    ///     for balance in &sub.spot_state.balances {
+    ///         println!("  {}: {}", balance.coin, balance.total);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint#retrieve-a-users-subaccounts>
+    pub async fn subaccounts(&self, user: Address) -> Result<Vec<SubAccount>> {
+        let resp = self.info(&InfoRequest::SubAccounts { user }).await?;
+        Ok(resp)
+    }
+
+    /// Creates a subaccount named `name` and returns its address.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, PrivateKeySigner};
+    /// use rust_decimal::dec;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    ///
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    /// let sub = client.create_subaccount(&signer, "basis", nonce, None).await?;
+    ///
+    /// // Fund it from the master account
+    /// let sub = client
+    ///     .subaccounts(signer.address())
+    ///     .await?
+    ///     .into_iter()
+    ///     .find(|account| account.sub_account_user == sub)
+    ///     .unwrap();
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    /// client
+    ///     .subaccount_transfer(&signer, sub.deposit(dec!(1000))?, nonce, None)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_subaccount<S: SignerSync>(
+        &self,
+        signer: &S,
+        name: &str,
+        nonce: u64,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<Address> {
+        let action = CreateSubAccount::new(name)?;
+        let resp = self
+            .sign_and_send_sync(signer, action, nonce, None, expires_after)
+            .await?;
+
+        match resp {
+            Response::Ok(OkResponse::CreateSubAccount(sub_account)) => Ok(sub_account),
+            Response::Err(err) => {
+                anyhow::bail!(Error::Api(format!("create_subaccount: {err}")))
+            }
+            _ => anyhow::bail!("create_subaccount: unexpected response type: {resp:?}"),
+        }
+    }
+
+    /// Renames a subaccount of `signer`.
+    pub async fn rename_subaccount<S: SignerSync>(
+        &self,
+        signer: &S,
+        sub_account: Address,
+        name: &str,
+        nonce: u64,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let action = SubAccountModify::new(sub_account, name)?;
+        self.send_default(
+            "rename_subaccount",
+            signer,
+            action,
+            nonce,
+            None,
+            expires_after,
+        )
+        .await
+    }
+
+    /// Transfers USDC between the perp balances of `signer` and one of its subaccounts.
+    ///
+    /// Build the transfer with [`SubAccount::deposit`] or [`SubAccount::withdraw`].
+    pub async fn subaccount_transfer<S: SignerSync>(
+        &self,
+        signer: &S,
+        transfer: SubAccountTransfer,
+        nonce: u64,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.send_default(
+            "subaccount_transfer",
+            signer,
+            transfer,
+            nonce,
+            None,
+            expires_after,
+        )
+        .await
+    }
+
+    /// Transfers a spot token between `signer` and one of its subaccounts.
+    ///
+    /// Build the transfer with [`SubAccount::spot_deposit`] or [`SubAccount::spot_withdraw`].
+    pub async fn subaccount_spot_transfer<S: SignerSync>(
+        &self,
+        signer: &S,
+        transfer: SubAccountSpotTransfer,
+        nonce: u64,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.send_default(
+            "subaccount_spot_transfer",
+            signer,
+            transfer,
+            nonce,
+            None,
+            expires_after,
+        )
+        .await
+    }
+
+    /// Schedule cancellation.
+    pub async fn schedule_cancel<S: SignerSync>(
+        &self,
+        signer: &S,
+        nonce: u64,
+        when: DateTime<Utc>,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let resp = self
+            .sign_and_send_sync(
+                signer,
+                ScheduleCancel {
+                    time: Some(when.timestamp_millis() as u64),
+                },
+                nonce,
+                vault_address,
+                expires_after,
+            )
+            .await?;
+
+        match resp {
+            Response::Ok(OkResponse::Default) => Ok(()),
+            Response::Err(err) => {
+                anyhow::bail!(Error::Api(format!("schedule_cancel: {err}")))
+            }
+            _ => anyhow::bail!("schedule_cancel: unexpected response type: {resp:?}"),
+        }
+    }
+
+    /// Places a native TWAP order and returns its TWAP ID.
+    ///
+    /// The duration must be between [`TwapOrder::MIN_MINUTES`] and
+    /// [`TwapOrder::MAX_MINUTES`]. Slice fills and lifecycle events can be followed
+    /// with [`Subscription::UserTwapSliceFills`](crate::hypercore::types::Subscription::UserTwapSliceFills)
+    /// and [`Subscription::UserTwapHistory`](crate::hypercore::types::Subscription::UserTwapHistory).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, PrivateKeySigner, types::TwapOrder};
+    /// use rust_decimal::dec;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    ///
+    /// let twap = TwapOrder {
+    ///     asset: 0,
+    ///     is_buy: true,
+    ///     sz: dec!(1),
+    ///     reduce_only: false,
+    ///     minutes: 60,
+    ///     randomize: false,
+    /// };
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    /// let twap_id = client.twap_order(&signer, twap, nonce, None, None).await?;
+    /// println!("TWAP {twap_id} running");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn twap_order<S: SignerSync>(
+        &self,
+        signer: &S,
+        twap: TwapOrder,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<u64> {
+        if !(TwapOrder::MIN_MINUTES..=TwapOrder::MAX_MINUTES).contains(&twap.minutes) {
+            anyhow::bail!(Error::InvalidOrder {
+                message: format!(
+                    "TWAP duration of {} minutes is outside {}..={}",
+                    twap.minutes,
+                    TwapOrder::MIN_MINUTES,
+                    TwapOrder::MAX_MINUTES
+                ),
+            });
+        }
+
+        let resp = self
+            .sign_and_send_sync(
+                signer,
+                TwapPlacement { twap },
+                nonce,
+                vault_address,
+                expires_after,
+            )
+            .await?;
+
+        match resp {
+            Response::Ok(OkResponse::TwapOrder { status }) => match status {
+                TwapOrderStatus::Running { twap_id } => Ok(twap_id),
+                TwapOrderStatus::Error(err) => {
+                    anyhow::bail!(Error::Api(format!("twap_order: {err}")))
+                }
+            },
+            Response::Err(err) => {
+                anyhow::bail!(Error::Api(format!("twap_order: {err}")))
+            }
+            _ => anyhow::bail!("twap_order: unexpected response type: {resp:?}"),
+        }
+    }
+
+    /// Cancels a running TWAP order.
+    pub async fn twap_cancel<S: SignerSync>(
+        &self,
+        signer: &S,
+        asset: usize,
+        twap_id: u64,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let resp = self
+            .sign_and_send_sync(
+                signer,
+                TwapCancel { asset, twap_id },
+                nonce,
+                vault_address,
+                expires_after,
+            )
+            .await?;
+
+        match resp {
+            Response::Ok(OkResponse::TwapCancel { status }) => match status {
+                TwapCancelStatus::Success => Ok(()),
+                TwapCancelStatus::Error(err) => {
+                    anyhow::bail!(Error::Api(format!("twap_cancel: {err}")))
+                }
+            },
+            Response::Err(err) => {
+                anyhow::bail!(Error::Api(format!("twap_cancel: {err}")))
+            }
+            _ => anyhow::bail!("twap_cancel: unexpected response type: {resp:?}"),
+        }
+    }
+
+    /// Sets the margin mode and leverage of a perpetual market.
+    ///
+    /// Applies to the open position in `market`, if any, and to orders placed afterwards.
+    /// The leverage is checked with [`PerpMarket::check_leverage`] before signing.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, PrivateKeySigner};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    /// let perps = client.perps().await?;
+    /// let btc = perps.iter().find(|market| market.name == "BTC").unwrap();
+    ///
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    /// client.update_leverage(&signer, btc, true, 10, nonce, None, None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_leverage<S: SignerSync>(
+        &self,
+        signer: &S,
+        market: &PerpMarket,
+        is_cross: bool,
+        leverage: u32,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let action = UpdateLeverage::new(market, is_cross, leverage)?;
+        self.send_default(
+            "update_leverage",
+            signer,
+            action,
+            nonce,
+            vault_address,
+            expires_after,
+        )
+        .await
+    }
+
+    /// Adds margin to an isolated position, or removes it if `amount` is negative.
+    ///
+    /// `amount` is in USD with at most 6 decimals; `is_buy` selects the long or short
+    /// position. See [`UpdateIsolatedMargin::new`] for the checks done before signing.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_isolated_margin<S: SignerSync>(
+        &self,
+        signer: &S,
+        market: &PerpMarket,
+        is_buy: bool,
+        amount: Decimal,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let action = UpdateIsolatedMargin::new(market, is_buy, amount)?;
+        self.send_default(
+            "update_isolated_margin",
+            signer,
+            action,
+            nonce,
+            vault_address,
+            expires_after,
+        )
+        .await
+    }
+
+    /// Adds the margin needed to bring the position in an isolated-only market down to
+    /// `leverage`.
+    ///
+    /// The leverage is checked with [`PerpMarket::check_top_up_leverage`] before signing.
+    pub async fn top_up_isolated_only_margin<S: SignerSync>(
+        &self,
+        signer: &S,
+        market: &PerpMarket,
+        leverage: Decimal,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let action = TopUpIsolatedOnlyMargin::new(market, leverage)?;
+        self.send_default(
+            "top_up_isolated_only_margin",
+            signer,
+            action,
+            nonce,
+            vault_address,
+            expires_after,
+        )
+        .await
+    }
+
+    /// Deposits `amount` USD from the perp balance into `vault`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, PrivateKeySigner};
+    /// use rust_decimal::dec;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    /// let vault = "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303".parse()?;
+    ///
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    /// client.vault_deposit(&signer, vault, dec!(1000), nonce, None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/exchange-endpoint#deposit-or-withdraw-from-a-vault>
+    pub async fn vault_deposit<S: SignerSync>(
+        &self,
+        signer: &S,
+        vault: Address,
+        amount: Decimal,
+        nonce: u64,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let action = VaultTransfer::deposit(vault, amount)?;
+        self.send_default("vault_deposit", signer, action, nonce, None, expires_after)
+            .await
+    }
+
+    /// Withdraws `amount` USD from `vault` to the perp balance.
+    ///
+    /// Fails if the deposits are still locked up, see [`VaultTransfer`].
+    pub async fn vault_withdraw<S: SignerSync>(
+        &self,
+        signer: &S,
+        vault: Address,
+        amount: Decimal,
+        nonce: u64,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let action = VaultTransfer::withdraw(vault, amount)?;
+        self.send_default("vault_withdraw", signer, action, nonce, None, expires_after)
+            .await
+    }
+
+    /// Creates a vault led by `signer`, seeded with `initial_usd`, and returns its address.
+    ///
+    /// See [`CreateVault::new`] for the checks done before signing.
     ///
     /// # Example
     ///
     /// ```no_run
-    /// use hypersdk::hypercore;
-    /// use hypersdk::Address;
+    /// use hypersdk::hypercore::{self, PrivateKeySigner};
+    /// use rust_decimal::dec;
     ///
     /// # async fn example() -> anyhow::Result<()> {
     /// let client = hypercore::mainnet();
-    /// let master: Address = "0x...".parse()?;
-    ///
-    /// let subaccounts = client.subaccounts(master).await?;
-    /// for sub in subaccounts {
-    ///     println!("Subaccount '{}': {:?}", sub.name, sub.sub_account_user);
-    ///     println!("  Account value: {}", sub.clearinghouse_state.margin_summary.account_value);
-    ///     println!("  Withdrawable: {}", sub.clearinghouse_state.withdrawable);
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
     ///
-    ///     // Check spot balances
-    ///     for balance in &sub.spot_state.balances {
-    ///         println!("  {}: {}", balance.coin, balance.total);
-    ///     }
-    /// }
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    /// let vault = client
+    ///     .create_vault(&signer, "Alpha", "Delta neutral basis trades", dec!(100), nonce, None)
+    ///     .await?;
+    /// println!("created vault {vault}");
     /// # Ok(())
     /// # }
     /// ```
-    ///
-    /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint#retrieve-a-users-subaccounts>
-    pub async fn subaccounts(&self, user: Address) -> Result<Vec<SubAccount>> {
-        let mut api_url = self.base_url.clone();
-        api_url.set_path("/info");
-
-        let resp = self
-            .http_client
-            .post(api_url)
-            .json(&InfoRequest::SubAccounts { user })
-            .send()
-            .await?
-            .json()
-            .await?;
-        Ok(resp)
-    }
-
-    /// Schedule cancellation.
-    pub async fn schedule_cancel<S: SignerSync>(
+    pub async fn create_vault<S: SignerSync>(
         &self,
         signer: &S,
+        name: &str,
+        description: &str,
+        initial_usd: Decimal,
         nonce: u64,
-        when: DateTime<Utc>,
-        vault_address: Option<Address>,
         expires_after: Option<DateTime<Utc>>,
-    ) -> Result<()> {
+    ) -> Result<Address> {
+        let action = CreateVault::new(name, description, initial_usd, nonce)?;
         let resp = self
-            .sign_and_send_sync(
-                signer,
-                ScheduleCancel {
-                    time: Some(when.timestamp_millis() as u64),
-                },
-                nonce,
-                vault_address,
-                expires_after,
-            )
+            .sign_and_send_sync(signer, action, nonce, None, expires_after)
             .await?;
 
         match resp {
-            Response::Ok(OkResponse::Default) => Ok(()),
+            Response::Ok(OkResponse::CreateVault(vault)) => Ok(vault),
             Response::Err(err) => {
-                anyhow::bail!("schedule_cancel: {err}")
+                anyhow::bail!(Error::Api(format!("create_vault: {err}")))
             }
-            _ => anyhow::bail!("schedule_cancel: unexpected response type: {resp:?}"),
+            _ => anyhow::bail!("create_vault: unexpected response type: {resp:?}"),
         }
     }
 
+    /// Changes the settings of a vault led by `signer`.
+    ///
+    /// Settings passed as `None` are left unchanged.
+    pub async fn vault_modify<S: SignerSync>(
+        &self,
+        signer: &S,
+        vault: Address,
+        allow_deposits: Option<bool>,
+        always_close_on_withdraw: Option<bool>,
+        nonce: u64,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let action = VaultModify {
+            vault_address: vault,
+            allow_deposits,
+            always_close_on_withdraw,
+        };
+        self.send_default("vault_modify", signer, action, nonce, None, expires_after)
+            .await
+    }
+
+    /// Distributes `amount` USD from a vault led by `signer` to its followers.
+    pub async fn vault_distribute<S: SignerSync>(
+        &self,
+        signer: &S,
+        vault: Address,
+        amount: Decimal,
+        nonce: u64,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let action = VaultDistribute::new(vault, amount)?;
+        self.send_default(
+            "vault_distribute",
+            signer,
+            action,
+            nonce,
+            None,
+            expires_after,
+        )
+        .await
+    }
+
+    /// Closes a vault led by `signer`, returning all funds to its followers.
+    ///
+    /// The vault must have no open positions.
+    pub async fn close_vault<S: SignerSync>(
+        &self,
+        signer: &S,
+        vault: Address,
+        nonce: u64,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let action = VaultDistribute::close(vault);
+        self.send_default("close_vault", signer, action, nonce, None, expires_after)
+            .await
+    }
+
     /// Places a batch of orders.
     ///
     /// Submits one or more orders to the exchange. Each order must be signed with your private key.
@@ -1198,7 +1937,7 @@ impl Client {
         match resp {
             Response::Ok(OkResponse::Default) => Ok(()),
             Response::Err(err) => {
-                anyhow::bail!("approve_agent: {err}")
+                anyhow::bail!(Error::Api(format!("approve_agent: {err}")))
             }
             _ => anyhow::bail!("approve_agent: unexpected response type: {resp:?}"),
         }
@@ -1266,7 +2005,7 @@ impl Client {
         match resp {
             Response::Ok(OkResponse::Default) => Ok(()),
             Response::Err(err) => {
-                anyhow::bail!("convert_to_multisig: {err}")
+                anyhow::bail!(Error::Api(format!("convert_to_multisig: {err}")))
             }
             _ => anyhow::bail!("convert_to_multisig: unexpected response type: {resp:?}"),
         }
@@ -1380,12 +2119,57 @@ impl Client {
         match resp {
             Response::Ok(OkResponse::Default) => Ok(()),
             Response::Err(err) => {
-                anyhow::bail!("send_usdc: {err}")
+                anyhow::bail!(Error::Api(format!("send_usdc: {err}")))
             }
             _ => anyhow::bail!("send_usdc: unexpected response type: {resp:?}"),
         }
     }
 
+    /// Withdraw USDC from the perp balance to Arbitrum.
+    ///
+    /// The bridge sends `amount` minus [`Withdraw::FEE`] to `destination` on Arbitrum.
+    /// Use [`bridge::deposit`](crate::hypercore::bridge::deposit) for the opposite direction.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, PrivateKeySigner, types::Withdraw};
+    /// use rust_decimal::dec;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let signer: PrivateKeySigner = "your_key".parse()?;
+    ///
+    /// let nonce = chrono::Utc::now().timestamp_millis() as u64;
+    /// let withdraw = Withdraw {
+    ///     destination: "0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb".parse()?,
+    ///     amount: dec!(100),
+    ///     time: nonce,
+    /// };
+    /// client.withdraw(&signer, withdraw, nonce).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/exchange-endpoint#initiate-a-withdrawal-request>
+    pub async fn withdraw<S: SignerSync>(
+        &self,
+        signer: &S,
+        withdraw: Withdraw,
+        nonce: u64,
+    ) -> Result<()> {
+        let resp = self
+            .sign_and_send_sync(signer, withdraw.into_action(self.chain), nonce, None, None)
+            .await?;
+        match resp {
+            Response::Ok(OkResponse::Default) => Ok(()),
+            Response::Err(err) => {
+                anyhow::bail!(Error::Api(format!("withdraw: {err}")))
+            }
+            _ => anyhow::bail!("withdraw: unexpected response type: {resp:?}"),
+        }
+    }
+
     /// Send USDC to another address.
     ///
     /// Spot <> DEX or Subaccount.
@@ -1405,7 +2189,7 @@ impl Client {
             match resp {
                 Response::Ok(OkResponse::Default) => Ok(()),
                 Response::Err(err) => {
-                    anyhow::bail!("send_asset: {err}")
+                    anyhow::bail!(Error::Api(format!("send_asset: {err}")))
                 }
                 _ => anyhow::bail!("send_asset: unexpected response type: {resp:?}"),
             }
@@ -1431,7 +2215,7 @@ impl Client {
             match resp {
                 Response::Ok(OkResponse::Default) => Ok(()),
                 Response::Err(err) => {
-                    anyhow::bail!("spot send: {err}")
+                    anyhow::bail!(Error::Api(format!("spot send: {err}")))
                 }
                 _ => anyhow::bail!("spot_send: unexpected response type: {resp:?}"),
             }
@@ -1462,7 +2246,7 @@ impl Client {
         match resp {
             Response::Ok(OkResponse::Default) => Ok(()),
             Response::Err(err) => {
-                anyhow::bail!("evm_user_modify: {err}")
+                anyhow::bail!(Error::Api(format!("evm_user_modify: {err}")))
             }
             _ => anyhow::bail!("evm_user_modify: unexpected response type: {resp:?}"),
         }
@@ -1483,7 +2267,7 @@ impl Client {
         match resp {
             Response::Ok(OkResponse::Default) => Ok(()),
             Response::Err(err) => {
-                anyhow::bail!("noop: {err}")
+                anyhow::bail!(Error::Api(format!("noop: {err}")))
             }
             _ => anyhow::bail!("noop: unexpected response type: {resp:?}"),
         }
@@ -1552,6 +2336,96 @@ impl Client {
         }
     }
 
+    /// Waits for `weight` on the rate limiter, if any.
+    async fn acquire(&self, weight: u32) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire(weight).await;
+        }
+    }
+
+    /// Sends a request to the info endpoint and deserializes the response.
+    ///
+    /// This is the generic form of the typed info methods: it sends any
+    /// [`InfoRequest`], including [`InfoRequest::Other`] for request types the SDK does
+    /// not model, and deserializes the response into `T`. The rate limiter, if any, is
+    /// charged the request weight, plus the per-item weight of list responses.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, InfoRequest, UserFees};
+    /// use hypersdk::Address;
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let user: Address = "0x...".parse()?;
+    ///
+    /// let fees: UserFees = client.info(&InfoRequest::UserFees { user }).await?;
+    /// println!("taker rate: {}", fees.user_cross_rate);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn info<T: DeserializeOwned>(&self, req: &InfoRequest) -> Result<T> {
+        let Some(per_weight) = req.items_per_weight() else {
+            return self.send_info(req).await;
+        };
+
+        let data: serde_json::Value = self.send_info(req).await?;
+        if let (Some(rate_limiter), Some(items)) = (&self.rate_limiter, data.as_array()) {
+            rate_limiter.charge(rate_limit::items_weight(items.len(), per_weight));
+        }
+        Ok(serde_json::from_value(data).map_err(Error::from)?)
+    }
+
+    /// Sends an info request, charging its base weight.
+    async fn send_info<T: DeserializeOwned>(&self, req: &InfoRequest) -> Result<T> {
+        self.acquire(req.weight()).await;
+
+        let mut api_url = self.base_url.clone();
+        api_url.set_path("/info");
+
+        let started = Instant::now();
+        let res: Result<T, Error> = async {
+            Ok(self
+                .http_client
+                .post(api_url)
+                .json(req)
+                .send()
+                .await?
+                .json()
+                .await?)
+        }
+        .await;
+        record(
+            "info",
+            req.kind(),
+            started,
+            res.as_ref().err().map(Error::code),
+        );
+        Ok(res?)
+    }
+
+    /// Signs and sends `action`, expecting a default response.
+    async fn send_default<S: SignerSync>(
+        &self,
+        name: &str,
+        signer: &S,
+        action: impl Into<Action>,
+        nonce: u64,
+        vault_address: Option<Address>,
+        expires_after: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        let resp = self
+            .sign_and_send_sync(signer, action, nonce, vault_address, expires_after)
+            .await?;
+
+        match resp {
+            Response::Ok(OkResponse::Default) => Ok(()),
+            Response::Err(err) => anyhow::bail!(Error::Api(format!("{name}: {err}"))),
+            _ => anyhow::bail!("{name}: unexpected response type: {resp:?}"),
+        }
+    }
+
     /// Send a signed action hashing.
     fn sign_and_send_sync<S: SignerSync, A: Into<Action>>(
         &self,
@@ -1562,6 +2436,8 @@ impl Client {
         maybe_expires_after: Option<DateTime<Utc>>,
     ) -> impl Future<Output = Result<Response>> + Send + 'static {
         let action: Action = action.into();
+        let weight = action.weight();
+        let kind = action.kind();
         let res = action.sign_sync(
             signer,
             nonce,
@@ -1571,25 +2447,16 @@ impl Client {
         );
 
         let http_client = self.http_client.clone();
+        let rate_limiter = self.rate_limiter.clone();
         let mut url = self.base_url.clone();
         url.set_path("/exchange");
 
         async move {
             let req = res?;
-            let res = http_client
-                .post(url)
-                .timeout(Duration::from_secs(5))
-                // .header(header::CONTENT_TYPE, "application/json")
-                // .body(text)
-                .json(&req)
-                .send()
-                .await?
-                .json()
-                // .text()
-                .await?;
-            // println!("<< {res}");
-            // Ok(serde_json::from_str(&res).unwrap())
-            Ok(res)
+            if let Some(rate_limiter) = rate_limiter {
+                rate_limiter.acquire(weight).await;
+            }
+            post_action(&http_client, url, &req, kind).await
         }
     }
 
@@ -1618,24 +2485,77 @@ impl Client {
 
     #[doc(hidden)]
     pub async fn send(&self, req: ActionRequest) -> Result<Response> {
-        let http_client = self.http_client.clone();
+        self.acquire(req.action.weight()).await;
+
         let mut url = self.base_url.clone();
         url.set_path("/exchange");
 
-        let res = http_client
+        post_action(&self.http_client, url, &req, req.action.kind()).await
+    }
+
+    // TODO: https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint#retrieve-a-users-subaccounts
+}
+
+/// Posts a signed action to the exchange endpoint.
+async fn post_action(
+    http_client: &reqwest::Client,
+    url: Url,
+    req: &ActionRequest,
+    kind: &'static str,
+) -> Result<Response> {
+    let started = Instant::now();
+    let res: Result<Response, Error> = async {
+        Ok(http_client
             .post(url)
             .timeout(Duration::from_secs(5))
-            // .header(header::CONTENT_TYPE, "application/json")
-            // .body(text)
-            .json(&req)
+            .json(req)
             .send()
             .await?
             .json()
-            .await?;
-        Ok(res)
+            .await?)
     }
+    .await;
+    let code = match &res {
+        Ok(Response::Ok(_)) => None,
+        Ok(Response::Err(_)) => Some("API_ERROR"),
+        Err(err) => Some(err.code()),
+    };
+    record("exchange", kind, started, code);
+    Ok(res?)
+}
 
-    // TODO: https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint#retrieve-a-users-subaccounts
+/// Records the latency of an upstream request and, if it failed, its error code.
+#[cfg(feature = "metrics")]
+fn record(
+    endpoint: &'static str,
+    kind: &'static str,
+    started: Instant,
+    code: Option<&'static str>,
+) {
+    metrics::histogram!(
+        "hypersdk_upstream_request_duration_seconds",
+        "endpoint" => endpoint,
+        "type" => kind,
+    )
+    .record(started.elapsed());
+    if let Some(code) = code {
+        metrics::counter!(
+            "hypersdk_upstream_errors_total",
+            "endpoint" => endpoint,
+            "type" => kind,
+            "code" => code,
+        )
+        .increment(1);
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+fn record(
+    _endpoint: &'static str,
+    _kind: &'static str,
+    _started: Instant,
+    _code: Option<&'static str>,
+) {
 }
 
 /// Builder for constructing and executing multisig transactions on Hyperliquid.
@@ -1959,11 +2879,40 @@ where
 
         match resp {
             Response::Ok(OkResponse::Default) => Ok(()),
-            Response::Err(err) => anyhow::bail!("send_usdc: {err}"),
+            Response::Err(err) => anyhow::bail!(Error::Api(format!("send_usdc: {err}"))),
             _ => anyhow::bail!("send_usdc: unexpected response type: {resp:?}"),
         }
     }
 
+    /// Withdraw USDC from the multisig account to Arbitrum.
+    ///
+    /// Signatures are collected over the EIP-712 typed data of the withdrawal, as for
+    /// [`send_usdc`](Self::send_usdc). The nonce of the inner action is `withdraw.time`.
+    pub async fn withdraw(&self, withdraw: Withdraw) -> Result<()> {
+        let nonce = withdraw.time;
+        let action = multisig_collect_signatures(
+            self.lead.address(),
+            self.multi_sig_user,
+            self.signers.iter().copied(),
+            self.signatures.iter().copied(),
+            withdraw.into_action(self.client.chain()).into(),
+            nonce,
+            self.client.chain,
+        )
+        .await?;
+
+        let resp = self
+            .client
+            .sign_and_send(self.lead, action, self.nonce, None, None)
+            .await?;
+
+        match resp {
+            Response::Ok(OkResponse::Default) => Ok(()),
+            Response::Err(err) => anyhow::bail!(Error::Api(format!("withdraw: {err}"))),
+            _ => anyhow::bail!("withdraw: unexpected response type: {resp:?}"),
+        }
+    }
+
     /// Send assets from the multisig account.
     ///
     /// This method collects signatures from all signers for an asset transfer using EIP-712
@@ -2039,7 +2988,7 @@ where
 
         match resp {
             Response::Ok(OkResponse::Default) => Ok(()),
-            Response::Err(err) => anyhow::bail!("send_asset: {err}"),
+            Response::Err(err) => anyhow::bail!(Error::Api(format!("send_asset: {err}"))),
             _ => anyhow::bail!("send_asset: unexpected response type: {resp:?}"),
         }
     }
@@ -2099,7 +3048,7 @@ where
 
         match resp {
             Response::Ok(OkResponse::Default) => Ok(()),
-            Response::Err(err) => anyhow::bail!("approve_agent: {err}"),
+            Response::Err(err) => anyhow::bail!(Error::Api(format!("approve_agent: {err}"))),
             _ => anyhow::bail!("approve_agent: unexpected response type: {resp:?}"),
         }
     }
@@ -2151,8 +3100,85 @@ where
 
         match resp {
             Response::Ok(OkResponse::Default) => Ok(()),
-            Response::Err(err) => anyhow::bail!("convert_to_normal_user: {err}"),
+            Response::Err(err) => {
+                anyhow::bail!(Error::Api(format!("convert_to_normal_user: {err}")))
+            }
             _ => anyhow::bail!("convert_to_normal_user: unexpected response type: {resp:?}"),
         }
     }
+
+    /// Sets the margin mode and leverage of a perpetual market for the multisig account.
+    ///
+    /// See [`Client::update_leverage`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// client
+    ///     .multi_sig(&lead, multisig_addr, nonce)
+    ///     .signers(&signers)
+    ///     .update_leverage(&btc, true, 10)
+    ///     .await?;
+    /// ```
+    pub async fn update_leverage(
+        &self,
+        market: &PerpMarket,
+        is_cross: bool,
+        leverage: u32,
+    ) -> Result<()> {
+        let action = UpdateLeverage::new(market, is_cross, leverage)?;
+        self.send_default("update_leverage", action.into()).await
+    }
+
+    /// Adds or removes margin of an isolated position of the multisig account.
+    ///
+    /// See [`Client::update_isolated_margin`].
+    pub async fn update_isolated_margin(
+        &self,
+        market: &PerpMarket,
+        is_buy: bool,
+        amount: Decimal,
+    ) -> Result<()> {
+        let action = UpdateIsolatedMargin::new(market, is_buy, amount)?;
+        self.send_default("update_isolated_margin", action.into())
+            .await
+    }
+
+    /// Tops up the margin of an isolated-only position of the multisig account.
+    ///
+    /// See [`Client::top_up_isolated_only_margin`].
+    pub async fn top_up_isolated_only_margin(
+        &self,
+        market: &PerpMarket,
+        leverage: Decimal,
+    ) -> Result<()> {
+        let action = TopUpIsolatedOnlyMargin::new(market, leverage)?;
+        self.send_default("top_up_isolated_only_margin", action.into())
+            .await
+    }
+
+    /// Collects the signatures for `action` and sends it, expecting a default response.
+    async fn send_default(&self, name: &str, action: Action) -> Result<()> {
+        let action = multisig_collect_signatures(
+            self.lead.address(),
+            self.multi_sig_user,
+            self.signers.iter().copied(),
+            self.signatures.iter().copied(),
+            action,
+            self.nonce,
+            self.client.chain,
+        )
+        .await?;
+
+        let resp = self
+            .client
+            .sign_and_send(self.lead, action, self.nonce, None, None)
+            .await?;
+
+        match resp {
+            Response::Ok(OkResponse::Default) => Ok(()),
+            Response::Err(err) => anyhow::bail!(Error::Api(format!("{name}: {err}"))),
+            _ => anyhow::bail!("{name}: unexpected response type: {resp:?}"),
+        }
+    }
 }
@@ -9,6 +9,8 @@
 //! - [`WebSocket`]: Real-time WebSocket connection for market data and order updates
 //! - Market types: [`PerpMarket`], [`SpotMarket`], [`SpotToken`]
 //! - Order types and operations in the [`types`] module
+//! - [`RateLimiter`]: Client-side accounting of request weights, see [`rate_limit`]
+//! - [`bridge`]: USDC deposits from Arbitrum
 //!
 //! # Examples
 //!
@@ -80,14 +82,19 @@
 //! # }
 //! ```
 
+pub mod bridge;
 pub mod error;
+#[cfg(test)]
+pub(crate) mod fixtures;
 pub mod http;
+pub mod rate_limit;
 pub mod signing;
 pub mod types;
 mod utils;
 pub mod ws;
 
 use std::{
+    collections::HashMap,
     fmt,
     hash::Hash,
     sync::atomic::{self, AtomicU64},
@@ -100,7 +107,6 @@ use alloy::{
     primitives::{B128, U256, address},
 };
 use anyhow::Context;
-use async_graphql::{ComplexObject, Enum, SimpleObject};
 use chrono::Utc;
 use either::Either;
 /// Re-export error types.
@@ -112,6 +118,8 @@ use serde::{Deserialize, Serialize};
 pub use types::*;
 use url::Url;
 
+#[cfg(feature = "graphql")]
+use crate::hypergraphql::scalar;
 use crate::{
     Address,
     hyperevm::{from_wei, to_wei},
@@ -133,6 +141,8 @@ pub type OidOrCloid = Either<u64, Cloid>;
 ///
 /// Use this client for placing orders, querying balances, and managing positions.
 pub use http::Client as HttpClient;
+/// Re-export of the token bucket tracking request weights.
+pub use rate_limit::RateLimiter;
 /// Re-export of the WebSocket connection for real-time market data.
 ///
 /// Use this for subscribing to trades, order books, and order updates.
@@ -555,8 +565,7 @@ pub fn testnet_ws() -> WebSocket {
 ///
 /// See: <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/tick-and-lot-size>
 #[derive(Debug, Clone, Copy)]
-// FIXME: cfg_attr needed
-#[derive(SimpleObject)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct PriceTick {
     /// Maximum decimal places allowed for this market.
     /// - Spot: max_decimals = 8 - sz_decimals
@@ -691,7 +700,7 @@ fn build_price_ticks(sz_decimals: i64) -> PriceTick {
 /// This function uses: max_decimals = 6 - sz_decimals (as a construction parameter)
 ///
 /// See: <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/tick-and-lot-size>
-fn build_perp_price_ticks(sz_decimals: i64) -> PriceTick {
+pub(crate) fn build_perp_price_ticks(sz_decimals: i64) -> PriceTick {
     let max_decimals = 6 - sz_decimals;
     PriceTick { max_decimals }
 }
@@ -718,9 +727,8 @@ fn build_perp_price_ticks(sz_decimals: i64) -> PriceTick {
 /// # }
 /// ```
 #[derive(Debug, Clone)]
-// FIXME: THIS NEEDS TO BE SET WITH cfg_attr
-#[derive(SimpleObject)]
-#[graphql(complex)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct PerpMarket {
     /// Market name (e.g., "BTC", "ETH")
     pub name: String,
@@ -740,7 +748,6 @@ pub struct PerpMarket {
     pub table: PriceTick,
 }
 
-#[ComplexObject]
 impl PerpMarket {
     /// Returns the market symbol (same as name for perps).
     #[must_use]
@@ -754,6 +761,62 @@ impl PerpMarket {
         &self.table
     }
 
+    /// Returns true if positions in this market can use cross margin.
+    #[must_use]
+    pub fn allows_cross_margin(&self) -> bool {
+        !self.isolated_margin && self.margin_mode.is_none()
+    }
+
+    /// Checks a leverage update against the max leverage and margin mode of this market.
+    ///
+    /// Fails with [`Error::InvalidAction`] if `leverage` is zero or above
+    /// [`max_leverage`](Self::max_leverage), or if `is_cross` is set on an isolated-only
+    /// market.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use hypersdk::hypercore::PerpMarket;
+    /// # let market: PerpMarket = unimplemented!();
+    /// // BTC allows up to 40x cross leverage
+    /// assert!(market.check_leverage(true, 20).is_ok());
+    /// assert!(market.check_leverage(true, 50).is_err());
+    /// ```
+    pub fn check_leverage(&self, is_cross: bool, leverage: u32) -> Result<(), Error> {
+        self.check_max_leverage(Decimal::from(leverage))?;
+        if is_cross && !self.allows_cross_margin() {
+            return Err(Error::InvalidAction {
+                message: format!("{} does not allow cross margin", self.name),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks the target leverage of an isolated-only margin top-up.
+    ///
+    /// Fails with [`Error::InvalidAction`] if this market allows cross margin, or if
+    /// `leverage` is below 1 or above [`max_leverage`](Self::max_leverage).
+    pub fn check_top_up_leverage(&self, leverage: Decimal) -> Result<(), Error> {
+        if self.allows_cross_margin() {
+            return Err(Error::InvalidAction {
+                message: format!("{} is not isolated-only", self.name),
+            });
+        }
+        self.check_max_leverage(leverage)
+    }
+
+    fn check_max_leverage(&self, leverage: Decimal) -> Result<(), Error> {
+        if leverage < Decimal::ONE || leverage > Decimal::from(self.max_leverage) {
+            return Err(Error::InvalidAction {
+                message: format!(
+                    "leverage {leverage} out of range for {}: 1 to {}",
+                    self.name, self.max_leverage
+                ),
+            });
+        }
+        Ok(())
+    }
+
     /// Returns the tick size for a given price in this market.
     ///
     /// See [`PriceTick::tick_for`] for details on the calculation.
@@ -848,6 +911,8 @@ impl PerpMarket {
 /// # }
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct SpotMarket {
     /// Market name (e.g., "PURR/USDC", "BTC/USDC")
     pub name: String,
@@ -964,6 +1029,47 @@ impl PartialEq for SpotMarket {
 
 impl Eq for SpotMarket {}
 
+/// A perpetual market with its live statistics.
+///
+/// Returned by [`HttpClient::meta_and_asset_ctxs`], which fetches the metadata and
+/// the context of every market in one call.
+///
+/// # Example
+///
+/// ```no_run
+/// use hypersdk::hypercore;
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let client = hypercore::mainnet();
+/// let snapshots = client.meta_and_asset_ctxs().await?;
+///
+/// for snapshot in snapshots {
+///     println!("{}: mark {} funding {} OI {}",
+///         snapshot.market.name, snapshot.ctx.mark_px, snapshot.ctx.funding,
+///         snapshot.ctx.open_interest);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    /// Market metadata
+    pub market: PerpMarket,
+    /// Live market statistics
+    pub ctx: AssetCtx,
+}
+
+/// A spot market with its live statistics.
+///
+/// Returned by [`HttpClient::spot_meta_and_asset_ctxs`].
+#[derive(Debug, Clone)]
+pub struct SpotMarketSnapshot {
+    /// Market metadata
+    pub market: SpotMarket,
+    /// Live market statistics
+    pub ctx: SpotAssetCtx,
+}
+
 #[cfg(test)]
 mod tick_tests {
     use rust_decimal::dec;
@@ -1067,25 +1173,52 @@ mod tick_tests {
 /// # }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
-// FIXME: cfg_attr needed
-#[derive(SimpleObject)]
-#[graphql(complex)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct SpotToken {
     /// Token name (e.g., "USDC", "BTC", "PURR")
     pub name: String,
     /// Token index in the spot token array
     pub index: u32,
     /// Unique token identifier in HyperCore
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "token_id", into = "scalar::B128"))
+    )]
     pub token_id: B128,
     /// EVM contract address if the token is bridgeable
     ///
     /// `None` means the token only exists on HyperCore.
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(
+                owned,
+                name = "evm_contract",
+                into = "Option<scalar::Address>",
+                with = "scalar::option"
+            )
+        )
+    )]
     pub evm_contract: Option<Address>,
     /// Cross-chain transfer address for bridging between HyperCore and HyperEVM.
     ///
     /// Use this address as the destination when transferring from Core to EVM.
     ///
     /// **Special case:** HYPE token has no `evm_contract` but has this field set.
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(
+                owned,
+                name = "cross_chain_address",
+                into = "Option<scalar::Address>",
+                with = "scalar::option"
+            )
+        )
+    )]
     pub cross_chain_address: Option<Address>,
     /// Number of decimal places for sizes in HyperCore
     pub sz_decimals: i64,
@@ -1097,7 +1230,6 @@ pub struct SpotToken {
     pub evm_extra_decimals: i64,
 }
 
-#[ComplexObject]
 impl SpotToken {
     /// Converts a decimal amount to wei representation.
     ///
@@ -1235,9 +1367,12 @@ pub async fn spot_tokens(
     client: reqwest::Client,
 ) -> anyhow::Result<Vec<SpotToken>> {
     let data = raw_spot_markets(core_url, client).await?;
+    Ok(build_spot_tokens(data))
+}
 
-    let spot_tokens: Vec<_> = data.tokens.iter().cloned().map(SpotToken::from).collect();
-    Ok(spot_tokens)
+/// Builds the spot tokens of a `spotMeta` response.
+fn build_spot_tokens(data: SpotTokens) -> Vec<SpotToken> {
+    data.tokens.into_iter().map(SpotToken::from).collect()
 }
 
 /// Fetches all available spot trading markets from HyperCore.
@@ -1265,6 +1400,11 @@ pub async fn spot_markets(
     client: reqwest::Client,
 ) -> anyhow::Result<Vec<SpotMarket>> {
     let data = raw_spot_markets(core_url, client).await?;
+    Ok(build_spot_markets(data))
+}
+
+/// Builds the spot markets of a `spotMeta` response.
+fn build_spot_markets(data: SpotTokens) -> Vec<SpotMarket> {
     let mut markets = Vec::with_capacity(data.universe.len());
 
     let spot_tokens: Vec<_> = data.tokens.iter().cloned().map(SpotToken::from).collect();
@@ -1283,13 +1423,13 @@ pub async fn spot_markets(
 
         markets.push(SpotMarket {
             name: item.name,
-            index: 10_000 + item.index,
+            index: AssetIndex::Spot(item.index).asset(),
             tokens: [base.clone(), quote.clone()],
             table: build_price_ticks(base.sz_decimals),
         });
     }
 
-    Ok(markets)
+    markets
 }
 
 /// Fetches all available perpetual futures DEXes from HyperCore.
@@ -1332,8 +1472,12 @@ pub async fn perp_dexs(
         .await
         .context("info")?;
 
-    let dexes: Vec<Option<PerpDex>> = resp.json().await?;
-    let dex_list = dexes
+    Ok(build_perp_dexs(resp.json().await?))
+}
+
+/// Builds the DEXes of a `perpDexs` response, in which the default DEX is `null`.
+fn build_perp_dexs(dexes: Vec<Option<PerpDex>>) -> Vec<Dex> {
+    dexes
         .into_iter()
         .enumerate()
         .filter_map(|(index, dex)| {
@@ -1342,9 +1486,7 @@ pub async fn perp_dexs(
                 index,
             })
         })
-        .collect();
-
-    Ok(dex_list)
+        .collect()
 }
 
 #[derive(Deserialize)]
@@ -1376,31 +1518,62 @@ pub async fn perp_markets(
         .await
         .context("meta")?;
     let data: PerpTokens = resp.json().await?;
+
+    Ok(build_perp_markets(data, &spot, dex.as_ref()))
+}
+
+/// Builds the perpetual markets of a `meta` response, taking the collateral token from
+/// the `spotMeta` response.
+fn build_perp_markets(data: PerpTokens, spot: &SpotTokens, dex: Option<&Dex>) -> Vec<PerpMarket> {
     let collateral = &spot.tokens[data.collateral_token];
     let collateral = SpotToken::from(collateral.clone());
-    let dex_index = dex.as_ref().map(|dex| dex.index).unwrap_or_default();
 
-    let perps = data
-        .universe
+    data.universe
         .into_iter()
         .enumerate()
-        .map(|(index, perp)| {
-            // https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/asset-ids
-            let index = 100_000 * usize::from(dex.is_some()) + dex_index * 10_000 + index;
-            PerpMarket {
-                name: perp.name,
-                index,
-                max_leverage: perp.max_leverage,
-                sz_decimals: perp.sz_decimals,
-                collateral: collateral.clone(),
-                isolated_margin: perp.only_isolated,
-                margin_mode: perp.margin_mode,
-                table: build_perp_price_ticks(perp.sz_decimals),
-            }
+        .map(|(index, perp)| PerpMarket {
+            name: perp.name,
+            index: AssetIndex::perp(dex, index).asset(),
+            max_leverage: perp.max_leverage,
+            sz_decimals: perp.sz_decimals,
+            collateral: collateral.clone(),
+            isolated_margin: perp.only_isolated,
+            margin_mode: perp.margin_mode,
+            table: build_perp_price_ticks(perp.sz_decimals),
         })
-        .collect();
+        .collect()
+}
+
+/// Joins the markets of a `metaAndAssetCtxs` response with their contexts.
+fn build_market_snapshots(
+    (data, ctxs): (PerpTokens, Vec<AssetCtx>),
+    spot: &SpotTokens,
+    dex: Option<&Dex>,
+) -> Vec<MarketSnapshot> {
+    build_perp_markets(data, spot, dex)
+        .into_iter()
+        .zip(ctxs)
+        .map(|(market, ctx)| MarketSnapshot { market, ctx })
+        .collect()
+}
 
-    Ok(perps)
+/// Joins the markets of a `spotMetaAndAssetCtxs` response with their contexts.
+///
+/// Contexts are listed in `universe` order; markets are matched by name.
+fn build_spot_market_snapshots(
+    (data, ctxs): (SpotTokens, Vec<SpotAssetCtx>),
+) -> Vec<SpotMarketSnapshot> {
+    let mut ctxs: HashMap<String, SpotAssetCtx> = ctxs
+        .into_iter()
+        .map(|ctx| (ctx.coin.clone(), ctx))
+        .collect();
+    build_spot_markets(data)
+        .into_iter()
+        .filter_map(|market| {
+            let ctx = ctxs.remove(&market.name)?;
+            Some(SpotMarketSnapshot { market, ctx })
+        })
+        .collect()
 }
 
 // TODO: perpDexs
@@ -1437,12 +1610,14 @@ struct PerpUniverseItem {
     // margin_table_id: u64,
 }
 
-#[derive(Debug, Copy, Clone, Deserialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "camelCase")]
-// FIXME: cfg_attr needed
-#[derive(Enum, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum MarginMode {
+    /// Isolated margin only; margin cannot be removed from open positions
     StrictIsolated,
+    /// Isolated margin only
+    NoCross,
 }
 
 #[derive(Deserialize)]
@@ -1526,6 +1701,7 @@ mod tests {
     use std::{collections::HashMap, sync::Arc, thread};
 
     use alloy::primitives::address;
+    use rust_decimal::dec;
 
     use super::*;
     use crate::hypercore;
@@ -1742,4 +1918,80 @@ mod tests {
             &duplicates[..duplicates.len().min(10)]
         );
     }
+
+    const SPOT_META: &str = r#"{
+        "universe": [
+            {"tokens": [1, 0], "name": "PURR/USDC", "index": 0, "isCanonical": true},
+            {"tokens": [2, 0], "name": "@1", "index": 1, "isCanonical": false}
+        ],
+        "tokens": [
+            {"name": "USDC", "szDecimals": 8, "weiDecimals": 8, "index": 0, "tokenId": "0x6d1e7cde53ba9467b783cb7c530ce054", "isCanonical": true, "evmContract": null, "fullName": null},
+            {"name": "PURR", "szDecimals": 0, "weiDecimals": 5, "index": 1, "tokenId": "0xc1fb593aeffbeb02f85e0308e9956a90", "isCanonical": true, "evmContract": null, "fullName": null},
+            {"name": "HFUN", "szDecimals": 2, "weiDecimals": 8, "index": 2, "tokenId": "0xbaf265ef389da684513d98d68edf4eae", "isCanonical": false, "evmContract": null, "fullName": null}
+        ]
+    }"#;
+
+    #[test]
+    fn test_build_market_snapshots() {
+        let spot: SpotTokens = serde_json::from_str(SPOT_META).unwrap();
+        let text = r#"[
+            {
+                "universe": [
+                    {"szDecimals": 5, "name": "BTC", "maxLeverage": 40, "marginTableId": 56},
+                    {"szDecimals": 4, "name": "ETH", "maxLeverage": 25, "marginTableId": 55}
+                ],
+                "marginTables": [],
+                "collateralToken": 0
+            },
+            [
+                {"funding": "0.0000125", "openInterest": "30000.5", "prevDayPx": "100000.0", "dayNtlVlm": "1500000000.0", "premium": "0.0001", "oraclePx": "101000.0", "markPx": "101010.0", "midPx": "101005.5", "impactPxs": ["101005.0", "101006.0"], "dayBaseVlm": "15000.0"},
+                {"funding": "-0.00001", "openInterest": "500000.0", "prevDayPx": "4000.0", "dayNtlVlm": "800000000.0", "premium": null, "oraclePx": "3900.0", "markPx": "3900.5", "midPx": null, "impactPxs": null, "dayBaseVlm": "200000.0"}
+            ]
+        ]"#;
+        let data = serde_json::from_str(text).unwrap();
+        let dex = Dex {
+            name: "xyz".into(),
+            index: 1,
+        };
+
+        let snapshots = build_market_snapshots(data, &spot, Some(&dex));
+        assert_eq!(snapshots.len(), 2);
+        let btc = &snapshots[0];
+        assert_eq!(btc.market.name, "BTC");
+        assert_eq!(btc.market.index, 110_000);
+        assert_eq!(btc.market.collateral.name, "USDC");
+        assert_eq!(btc.ctx.mark_px, dec!(101010.0));
+        assert_eq!(btc.ctx.day_change(), Some(dec!(0.0101)));
+        let eth = &snapshots[1];
+        assert_eq!(eth.market.index, 110_001);
+        assert!(eth.ctx.premium.is_none());
+        assert_eq!(eth.ctx.open_interest_ntl(), dec!(1950250000));
+    }
+
+    #[test]
+    fn test_build_perp_dexs() {
+        let text = r#"[null, {"name": "xyz", "fullName": "XYZ", "deployer": "0x0000000000000000000000000000000000000001"}]"#;
+        let dexes = build_perp_dexs(serde_json::from_str(text).unwrap());
+        assert_eq!(dexes.len(), 1);
+        assert_eq!(dexes[0].name(), "xyz");
+        assert_eq!(dexes[0].index(), 1);
+    }
+
+    #[test]
+    fn test_build_spot_market_snapshots() {
+        let text = format!(
+            r#"[{SPOT_META}, [
+                {{"prevDayPx": "0.2", "dayNtlVlm": "1000.0", "markPx": "0.25", "midPx": "0.25", "circulatingSupply": "596000000.0", "coin": "PURR/USDC", "totalSupply": "600000000.0", "dayBaseVlm": "4000.0"}},
+                {{"prevDayPx": "0.0", "dayNtlVlm": "0.0", "markPx": "30.0", "midPx": null, "circulatingSupply": "1000.0", "coin": "@1", "totalSupply": "1000.0", "dayBaseVlm": "0.0"}}
+            ]]"#
+        );
+        let data = serde_json::from_str(&text).unwrap();
+
+        let snapshots = build_spot_market_snapshots(data);
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].market.symbol(), "PURR/USDC");
+        assert_eq!(snapshots[0].ctx.day_change(), Some(dec!(0.25)));
+        assert_eq!(snapshots[1].market.index, 10_001);
+        assert_eq!(snapshots[1].ctx.day_change(), None);
+    }
 }
@@ -0,0 +1,221 @@
+//! Client-side accounting of Hyperliquid request weights.
+//!
+//! Hyperliquid limits REST traffic by weight rather than by request count: every IP
+//! may spend 1200 weight per minute across `/info`, `/exchange` and `/explorer`. Each
+//! request costs a base weight, and some info endpoints cost extra for the number of
+//! items they return. See the
+//! [rate limits](https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/rate-limits-and-user-limits)
+//! documentation.
+//!
+//! A [`RateLimiter`] attached with [`HttpClient::with_rate_limiter`] charges the weight
+//! of every request the client sends. It is shared by cloning the `Arc`, so several
+//! clients behind the same IP can draw from a single budget.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//!
+//! use hypersdk::hypercore::{self, RateLimiter};
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let limiter = Arc::new(RateLimiter::default());
+//! let client = hypercore::mainnet().with_rate_limiter(limiter.clone());
+//!
+//! client.all_mids().await?;
+//! assert_eq!(limiter.consumed(), 2);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`HttpClient::with_rate_limiter`]: super::HttpClient::with_rate_limiter
+
+use std::{sync::Mutex, time::Duration};
+
+use tokio::time::Instant;
+
+/// Weight that can be spent per [`WEIGHT_PERIOD`] from a single IP.
+pub const WEIGHT_LIMIT: u32 = 1200;
+
+/// Period over which [`WEIGHT_LIMIT`] is replenished.
+pub const WEIGHT_PERIOD: Duration = Duration::from_secs(60);
+
+/// Weight of the lightest info requests: `l2Book`, `allMids`, `clearinghouseState`,
+/// `orderStatus`, `spotClearinghouseState` and `exchangeStatus`.
+pub const INFO_LIGHT_WEIGHT: u32 = 2;
+
+/// Weight of the info requests not listed elsewhere.
+pub const INFO_WEIGHT: u32 = 20;
+
+/// Weight of the `userRole` info request.
+pub const USER_ROLE_WEIGHT: u32 = 60;
+
+/// Weight of explorer requests.
+pub const EXPLORER_WEIGHT: u32 = 40;
+
+/// Number of returned items costing one extra weight, for list endpoints such as
+/// `userFills`, `historicalOrders` or `fundingHistory`.
+pub const ITEMS_PER_WEIGHT: u32 = 20;
+
+/// Number of returned candles costing one extra weight for `candleSnapshot`.
+pub const CANDLES_PER_WEIGHT: u32 = 60;
+
+/// Base weight of exchange actions.
+pub const EXCHANGE_WEIGHT: u32 = 1;
+
+/// Number of batched orders or cancels costing one extra weight on exchange actions.
+pub const ACTIONS_PER_WEIGHT: u32 = 40;
+
+/// Returns the weight of an exchange action of `batch_len` orders or cancels.
+///
+/// ```
+/// use hypersdk::hypercore::rate_limit::exchange_weight;
+///
+/// assert_eq!(exchange_weight(1), 1);
+/// assert_eq!(exchange_weight(79), 2);
+/// ```
+#[must_use]
+pub const fn exchange_weight(batch_len: usize) -> u32 {
+    EXCHANGE_WEIGHT + items_weight(batch_len, ACTIONS_PER_WEIGHT)
+}
+
+/// Returns the extra weight of a response of `items` items, one per `per_weight`.
+#[must_use]
+pub const fn items_weight(items: usize, per_weight: u32) -> u32 {
+    (items / per_weight as usize) as u32
+}
+
+/// Token bucket tracking the weight spent against Hyperliquid's limit.
+///
+/// The bucket holds up to `capacity` weight and refills continuously at
+/// `capacity / period`. [`acquire`](Self::acquire) waits until the weight of the next
+/// request is available; [`charge`](Self::charge) records weight that is only known
+/// once a response arrives and may leave the bucket in debt, which delays the
+/// following requests.
+#[derive(Debug)]
+pub struct RateLimiter {
+    capacity: u32,
+    period: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    tokens: f64,
+    updated: Instant,
+    consumed: u64,
+}
+
+impl RateLimiter {
+    /// Creates a full bucket of `capacity` weight replenished every `period`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` or `period` is zero.
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        assert!(capacity > 0, "rate limiter capacity must be positive");
+        assert!(!period.is_zero(), "rate limiter period must be positive");
+        Self {
+            capacity,
+            period,
+            state: Mutex::new(State {
+                tokens: f64::from(capacity),
+                updated: Instant::now(),
+                consumed: 0,
+            }),
+        }
+    }
+
+    /// Returns the bucket capacity.
+    #[must_use]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Returns the weight that can be spent right now without waiting.
+    #[must_use]
+    pub fn available(&self) -> u32 {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        state.tokens.max(0.0) as u32
+    }
+
+    /// Returns the total weight charged since creation.
+    #[must_use]
+    pub fn consumed(&self) -> u64 {
+        self.state.lock().unwrap().consumed
+    }
+
+    /// Waits until `weight` is available, then spends it.
+    ///
+    /// Weights above the capacity wait for a full bucket.
+    pub async fn acquire(&self, weight: u32) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                self.refill(&mut state);
+                let needed = f64::from(weight.min(self.capacity));
+                if state.tokens >= needed {
+                    state.tokens -= f64::from(weight);
+                    state.consumed += u64::from(weight);
+                    return;
+                }
+                self.period
+                    .mul_f64((needed - state.tokens) / f64::from(self.capacity))
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Spends `weight` without waiting.
+    pub fn charge(&self, weight: u32) {
+        if weight == 0 {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        state.tokens -= f64::from(weight);
+        state.consumed += u64::from(weight);
+    }
+
+    fn refill(&self, state: &mut State) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.updated);
+        state.updated = now;
+        let refill = elapsed.as_secs_f64() / self.period.as_secs_f64() * f64::from(self.capacity);
+        state.tokens = (state.tokens + refill).min(f64::from(self.capacity));
+    }
+}
+
+impl Default for RateLimiter {
+    /// A bucket matching the per-IP limit of [`WEIGHT_LIMIT`] per [`WEIGHT_PERIOD`].
+    fn default() -> Self {
+        Self::new(WEIGHT_LIMIT, WEIGHT_PERIOD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_waits_for_refill() {
+        let limiter = RateLimiter::new(100, Duration::from_secs(10));
+        limiter.acquire(60).await;
+        limiter.charge(30);
+        assert_eq!(limiter.available(), 10);
+        assert_eq!(limiter.consumed(), 90);
+
+        // 40 more weight refills in 4 seconds
+        let start = Instant::now();
+        limiter.acquire(50).await;
+        assert!(start.elapsed() >= Duration::from_secs(4));
+        assert_eq!(limiter.consumed(), 140);
+
+        // debt delays the next request
+        limiter.charge(100);
+        let start = Instant::now();
+        limiter.acquire(10).await;
+        assert!(start.elapsed() >= Duration::from_secs(11));
+    }
+}
@@ -285,6 +285,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_recover_withdraw() {
+        let signer = get_signer();
+
+        let withdraw = types::Withdraw {
+            destination: "0x0D1d9635D0640821d15e323ac8AdADfA9c111414"
+                .parse()
+                .unwrap(),
+            amount: rust_decimal::Decimal::TEN,
+            time: 1690393044548,
+        };
+        let action = Action::from(withdraw.into_action(Chain::Mainnet));
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "type": "withdraw3",
+                "signatureChainId": "0xa4b1",
+                "hyperliquidChain": "Mainnet",
+                "destination": "0x0d1d9635d0640821d15e323ac8adadfa9c111414",
+                "amount": "10",
+                "time": 1690393044548u64,
+            })
+        );
+
+        let multi_sig = action
+            .typed_data_multisig(signer.address(), signer.address(), Chain::Mainnet)
+            .unwrap();
+        assert_eq!(multi_sig.primary_type, "HyperliquidTransaction:Withdraw");
+
+        let nonce = 1690393044548u64;
+        let action_request = action
+            .clone()
+            .sign_sync(&signer, nonce, None, None, Chain::Mainnet)
+            .unwrap();
+        let recovered_address = action
+            .recover(&action_request.signature, nonce, None, None, Chain::Mainnet)
+            .unwrap();
+        assert_eq!(recovered_address, signer.address());
+    }
+
     #[test]
     fn test_recover_batch_order() {
         use rust_decimal::dec;
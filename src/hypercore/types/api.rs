@@ -5,19 +5,20 @@
 //! signing, serialization, and API communication.
 
 use alloy::{
-    dyn_abi::TypedData,
+    dyn_abi::{Resolver, TypedData},
     primitives::{Address, B256},
     signers::{Signer, SignerSync, k256::ecdsa::RecoveryId},
 };
 use chrono::{DateTime, Utc};
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, prelude::ToPrimitive};
 use serde::{Deserialize, Serialize};
 
 use crate::hypercore::{
-    Chain,
+    Chain, Error, PerpMarket, rate_limit,
     types::{
         BatchCancel, BatchCancelCloid, BatchModify, BatchOrder, CORE_MAINNET_EIP712_DOMAIN,
-        OrderResponseStatus, ScheduleCancel, Signature,
+        OrderResponseStatus, ScheduleCancel, SendToken, Signature, TwapCancel, TwapCancelStatus,
+        TwapOrderStatus, TwapPlacement,
     },
     utils::{self, get_typed_data},
 };
@@ -75,8 +76,15 @@ pub enum Action {
     CancelByCloid(BatchCancelCloid),
     /// Schedule cancellation of all orders.
     ScheduleCancel(ScheduleCancel),
+    /// TWAP order placement.
+    TwapOrder(TwapPlacement),
+    /// TWAP order cancellation.
+    TwapCancel(TwapCancel),
     /// Core USDC transfer.
     UsdSend(UsdSendAction),
+    /// USDC withdrawal to Arbitrum.
+    #[serde(rename = "withdraw3")]
+    Withdraw(WithdrawAction),
     /// Send asset.
     SendAsset(SendAssetAction),
     /// Spot send.
@@ -88,8 +96,28 @@ pub enum Action {
     ApproveAgent(ApproveAgent),
     /// Convert to multi-signature user.
     ConvertToMultiSigUser(ConvertToMultiSigUser),
+    /// Update leverage.
+    UpdateLeverage(UpdateLeverage),
     /// Update isolated margin.
     UpdateIsolatedMargin(UpdateIsolatedMargin),
+    /// Top up isolated-only margin to a target leverage.
+    TopUpIsolatedOnlyMargin(TopUpIsolatedOnlyMargin),
+    /// Vault deposit or withdrawal.
+    VaultTransfer(VaultTransfer),
+    /// Vault creation.
+    CreateVault(CreateVault),
+    /// Vault settings update.
+    VaultModify(VaultModify),
+    /// Vault distribution to followers.
+    VaultDistribute(VaultDistribute),
+    /// Subaccount creation.
+    CreateSubAccount(CreateSubAccount),
+    /// Subaccount rename.
+    SubAccountModify(SubAccountModify),
+    /// USDC transfer between the master account and a subaccount.
+    SubAccountTransfer(SubAccountTransfer),
+    /// Spot token transfer between the master account and a subaccount.
+    SubAccountSpotTransfer(SubAccountSpotTransfer),
     /// Multi-sig action.
     MultiSig(MultiSigAction),
     /// Invalidate a request.
@@ -97,6 +125,57 @@ pub enum Action {
 }
 
 impl Action {
+    /// Returns the `type` of the action, e.g. `order` or `usdSend`.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Order(_) => "order",
+            Self::BatchModify(_) => "batchModify",
+            Self::Cancel(_) => "cancel",
+            Self::CancelByCloid(_) => "cancelByCloid",
+            Self::ScheduleCancel(_) => "scheduleCancel",
+            Self::TwapOrder(_) => "twapOrder",
+            Self::TwapCancel(_) => "twapCancel",
+            Self::UsdSend(_) => "usdSend",
+            Self::Withdraw(_) => "withdraw3",
+            Self::SendAsset(_) => "sendAsset",
+            Self::SpotSend(_) => "spotSend",
+            Self::EvmUserModify { .. } => "evmUserModify",
+            Self::ApproveAgent(_) => "approveAgent",
+            Self::ConvertToMultiSigUser(_) => "convertToMultiSigUser",
+            Self::UpdateLeverage(_) => "updateLeverage",
+            Self::UpdateIsolatedMargin(_) => "updateIsolatedMargin",
+            Self::TopUpIsolatedOnlyMargin(_) => "topUpIsolatedOnlyMargin",
+            Self::VaultTransfer(_) => "vaultTransfer",
+            Self::CreateVault(_) => "createVault",
+            Self::VaultModify(_) => "vaultModify",
+            Self::VaultDistribute(_) => "vaultDistribute",
+            Self::CreateSubAccount(_) => "createSubAccount",
+            Self::SubAccountModify(_) => "subAccountModify",
+            Self::SubAccountTransfer(_) => "subAccountTransfer",
+            Self::SubAccountSpotTransfer(_) => "subAccountSpotTransfer",
+            Self::MultiSig(_) => "multiSig",
+            Self::Noop => "noop",
+        }
+    }
+
+    /// Rate limit weight of the action, see [`exchange_weight`](rate_limit::exchange_weight).
+    ///
+    /// Batched orders, modifications and cancels weigh more as the batch grows; a
+    /// multi-sig action weighs as much as the action it wraps.
+    #[must_use]
+    pub fn weight(&self) -> u32 {
+        let batch_len = match self {
+            Self::Order(batch) => batch.orders.len(),
+            Self::BatchModify(batch) => batch.modifies.len(),
+            Self::Cancel(batch) => batch.cancels.len(),
+            Self::CancelByCloid(batch) => batch.cancels.len(),
+            Self::MultiSig(multi_sig) => return multi_sig.payload.action.weight(),
+            _ => 1,
+        };
+        rate_limit::exchange_weight(batch_len)
+    }
+
     /// Hash the action for signing.
     ///
     /// The hash is generated by serializing the action to MessagePack, appending the nonce,
@@ -115,7 +194,7 @@ impl Action {
 impl Action {
     /// Returns the typed data for multisig signing, if applicable.
     ///
-    /// Only EIP-712 typed data actions (UsdSend, SpotSend, SendAsset) support multisig typed data.
+    /// Only EIP-712 typed data actions (UsdSend, Withdraw, SpotSend, SendAsset) support multisig typed data.
     /// All other actions (orders, cancels, modifications) return None and use RMP hash signing.
     pub fn typed_data_multisig(
         &self,
@@ -129,6 +208,9 @@ impl Action {
             Action::UsdSend(inner) => Some(utils::get_typed_data::<solidity::multisig::UsdSend>(
                 inner, chain, multi_sig,
             )),
+            Action::Withdraw(inner) => Some(utils::get_typed_data::<solidity::multisig::Withdraw>(
+                inner, chain, multi_sig,
+            )),
             Action::SpotSend(inner) => Some(utils::get_typed_data::<solidity::multisig::SpotSend>(
                 inner, chain, multi_sig,
             )),
@@ -162,8 +244,22 @@ pub enum Response {
 #[serde(tag = "type", content = "data")]
 #[serde(rename_all = "camelCase")]
 pub enum OkResponse {
-    Order { statuses: Vec<OrderResponseStatus> },
-    Cancel { statuses: Vec<OrderResponseStatus> },
+    Order {
+        statuses: Vec<OrderResponseStatus>,
+    },
+    Cancel {
+        statuses: Vec<OrderResponseStatus>,
+    },
+    TwapOrder {
+        status: TwapOrderStatus,
+    },
+    TwapCancel {
+        status: TwapCancelStatus,
+    },
+    /// Address of the created vault.
+    CreateVault(Address),
+    /// Address of the created subaccount.
+    CreateSubAccount(Address),
     // should be ok?
     Default,
 }
@@ -191,8 +287,20 @@ impl Action {
             | Action::Cancel(_)
             | Action::CancelByCloid(_)
             | Action::ScheduleCancel(_)
+            | Action::TwapOrder(_)
+            | Action::TwapCancel(_)
             | Action::EvmUserModify { .. }
+            | Action::UpdateLeverage(_)
             | Action::UpdateIsolatedMargin(_)
+            | Action::TopUpIsolatedOnlyMargin(_)
+            | Action::VaultTransfer(_)
+            | Action::CreateVault(_)
+            | Action::VaultModify(_)
+            | Action::VaultDistribute(_)
+            | Action::CreateSubAccount(_)
+            | Action::SubAccountModify(_)
+            | Action::SubAccountTransfer(_)
+            | Action::SubAccountSpotTransfer(_)
             | Action::Noop => {
                 let connection_id = self.hash(nonce, maybe_vault_address, expires_after)?;
                 let agent = solidity::Agent {
@@ -206,6 +314,10 @@ impl Action {
                 let typed_data = get_typed_data::<solidity::UsdSend>(&inner, chain, None);
                 signer.sign_dynamic_typed_data_sync(&typed_data)?
             }
+            Action::Withdraw(inner) => {
+                let typed_data = get_typed_data::<solidity::Withdraw>(&inner, chain, None);
+                signer.sign_dynamic_typed_data_sync(&typed_data)?
+            }
             Action::SendAsset(inner) => {
                 let typed_data = get_typed_data::<solidity::SendAsset>(&inner, chain, None);
                 signer.sign_dynamic_typed_data_sync(&typed_data)?
@@ -281,8 +393,20 @@ impl Action {
             | Action::Cancel(_)
             | Action::CancelByCloid(_)
             | Action::ScheduleCancel(_)
+            | Action::TwapOrder(_)
+            | Action::TwapCancel(_)
             | Action::EvmUserModify { .. }
+            | Action::UpdateLeverage(_)
             | Action::UpdateIsolatedMargin(_)
+            | Action::TopUpIsolatedOnlyMargin(_)
+            | Action::VaultTransfer(_)
+            | Action::CreateVault(_)
+            | Action::VaultModify(_)
+            | Action::VaultDistribute(_)
+            | Action::CreateSubAccount(_)
+            | Action::SubAccountModify(_)
+            | Action::SubAccountTransfer(_)
+            | Action::SubAccountSpotTransfer(_)
             | Action::Noop => {
                 let connection_id = self.hash(nonce, maybe_vault_address, expires_after)?;
                 let agent = solidity::Agent {
@@ -298,6 +422,10 @@ impl Action {
                 let typed_data = get_typed_data::<solidity::UsdSend>(&inner, chain, None);
                 signer.sign_dynamic_typed_data(&typed_data).await?
             }
+            Action::Withdraw(inner) => {
+                let typed_data = get_typed_data::<solidity::Withdraw>(&inner, chain, None);
+                signer.sign_dynamic_typed_data(&typed_data).await?
+            }
             Action::SendAsset(inner) => {
                 let typed_data = get_typed_data::<solidity::SendAsset>(&inner, chain, None);
                 signer.sign_dynamic_typed_data(&typed_data).await?
@@ -369,8 +497,20 @@ impl Action {
             | Action::Cancel(_)
             | Action::CancelByCloid(_)
             | Action::ScheduleCancel(_)
+            | Action::TwapOrder(_)
+            | Action::TwapCancel(_)
             | Action::EvmUserModify { .. }
+            | Action::UpdateLeverage(_)
             | Action::UpdateIsolatedMargin(_)
+            | Action::TopUpIsolatedOnlyMargin(_)
+            | Action::VaultTransfer(_)
+            | Action::CreateVault(_)
+            | Action::VaultModify(_)
+            | Action::VaultDistribute(_)
+            | Action::CreateSubAccount(_)
+            | Action::SubAccountModify(_)
+            | Action::SubAccountTransfer(_)
+            | Action::SubAccountSpotTransfer(_)
             | Action::Noop => {
                 let expires_after =
                     maybe_expires_after.map(|after| after.timestamp_millis() as u64);
@@ -387,6 +527,10 @@ impl Action {
                 let typed_data = get_typed_data::<solidity::UsdSend>(&inner, chain, None);
                 Ok(typed_data.eip712_signing_hash()?)
             }
+            Action::Withdraw(inner) => {
+                let typed_data = get_typed_data::<solidity::Withdraw>(&inner, chain, None);
+                Ok(typed_data.eip712_signing_hash()?)
+            }
             Action::SendAsset(inner) => {
                 let typed_data = get_typed_data::<solidity::SendAsset>(&inner, chain, None);
                 Ok(typed_data.eip712_signing_hash()?)
@@ -502,6 +646,42 @@ pub struct UsdSendAction {
     pub time: u64,
 }
 
+/// Withdraw USDC from the perpetual balance to Arbitrum.
+///
+/// The bridge sends `amount` minus a 1 USDC fee to `destination` on Arbitrum, usually
+/// within a few minutes. Requires an EIP-712 signature.
+///
+/// # Fields
+///
+/// - `signature_chain_id`: The chain ID for signature verification (use [`crate::hypercore::ARBITRUM_MAINNET_CHAIN_ID`] or [`crate::hypercore::ARBITRUM_TESTNET_CHAIN_ID`])
+/// - `hyperliquid_chain`: Whether this is mainnet or testnet
+/// - `destination`: The Arbitrum address receiving the USDC
+/// - `amount`: Amount of USDC to withdraw, fee included
+/// - `time`: Timestamp in milliseconds (should match the nonce)
+///
+/// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/exchange-endpoint#initiate-a-withdrawal-request>
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawAction {
+    /// Signature chain ID.
+    ///
+    /// For arbitrum use [`crate::hypercore::ARBITRUM_MAINNET_CHAIN_ID`] or [`crate::hypercore::ARBITRUM_TESTNET_CHAIN_ID`].
+    pub signature_chain_id: String,
+    /// The chain this action is being executed on.
+    pub hyperliquid_chain: Chain,
+    /// The destination address on Arbitrum.
+    #[serde(
+        serialize_with = "crate::hypercore::utils::serialize_address_as_hex",
+        deserialize_with = "crate::hypercore::utils::deserialize_address_from_hex"
+    )]
+    pub destination: Address,
+    /// The amount.
+    #[serde(with = "rust_decimal::serde::str")]
+    pub amount: Decimal,
+    /// Current time, should match the nonce
+    pub time: u64,
+}
+
 /// Send spot tokens to another address.
 ///
 /// This action transfers spot tokens (like PURR, HYPE, etc.) from your spot balance
@@ -648,6 +828,34 @@ pub struct ConvertToMultiSigUser {
     pub nonce: u64,
 }
 
+/// Request to update the leverage of an asset.
+///
+/// Sets the margin mode (cross or isolated) and the leverage used by the position in
+/// `asset`, and by the orders placed afterwards.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLeverage {
+    /// Asset index.
+    pub asset: usize,
+    /// `true` for cross margin, `false` for isolated margin.
+    pub is_cross: bool,
+    /// Leverage, from 1 to the max leverage of the market.
+    pub leverage: u32,
+}
+
+impl UpdateLeverage {
+    /// Creates a leverage update for `market`, checked with
+    /// [`PerpMarket::check_leverage`].
+    pub fn new(market: &PerpMarket, is_cross: bool, leverage: u32) -> Result<Self, Error> {
+        market.check_leverage(is_cross, leverage)?;
+        Ok(Self {
+            asset: market.index,
+            is_cross,
+            leverage,
+        })
+    }
+}
+
 /// Request to update isolated margin for a position.
 ///
 /// Allows adding or removing margin from an isolated-margin position.
@@ -658,8 +866,397 @@ pub struct UpdateIsolatedMargin {
     pub asset: usize,
     /// `true` for a long position, `false` for a short position.
     pub is_buy: bool,
-    /// Margin delta in USD (scaled integer representation).
-    pub ntli: u64,
+    /// Margin delta in USD with 6 decimals, e.g. `1_000_000` adds 1 USD and
+    /// `-1_000_000` removes it.
+    pub ntli: i64,
+}
+
+impl UpdateIsolatedMargin {
+    /// Creates an update adding `amount` USD of margin to the isolated position in
+    /// `market`, or removing it if negative.
+    ///
+    /// Fails with [`Error::InvalidAction`] if `amount` is zero or has more than 6
+    /// decimals, or if margin cannot be removed from the positions of `market`
+    /// ([`MarginMode::StrictIsolated`](crate::hypercore::MarginMode::StrictIsolated)).
+    pub fn new(market: &PerpMarket, is_buy: bool, amount: Decimal) -> Result<Self, Error> {
+        let invalid = |message: String| Error::InvalidAction { message };
+        if amount.is_zero() {
+            return Err(invalid("margin amount is zero".into()));
+        }
+        if amount.is_sign_negative()
+            && market.margin_mode == Some(crate::hypercore::MarginMode::StrictIsolated)
+        {
+            return Err(invalid(format!(
+                "margin cannot be removed from {} positions",
+                market.name
+            )));
+        }
+        if amount.normalize().scale() > 6 {
+            return Err(invalid(format!(
+                "margin amount {amount} has more than 6 decimals"
+            )));
+        }
+        let ntli = (amount * Decimal::from(1_000_000))
+            .to_i64()
+            .ok_or_else(|| invalid(format!("margin amount {amount} out of range")))?;
+        Ok(Self {
+            asset: market.index,
+            is_buy,
+            ntli,
+        })
+    }
+}
+
+/// Request to top up the margin of an isolated-only position to a target leverage.
+///
+/// Adds the margin needed to bring the position in `asset`, which must be in an
+/// isolated-only market, down to `leverage`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TopUpIsolatedOnlyMargin {
+    /// Asset index of the position.
+    pub asset: usize,
+    /// Target leverage.
+    #[serde(with = "rust_decimal::serde::str")]
+    pub leverage: Decimal,
+}
+
+impl TopUpIsolatedOnlyMargin {
+    /// Creates a top-up of the position in `market` to `leverage`, checked with
+    /// [`PerpMarket::check_top_up_leverage`].
+    pub fn new(market: &PerpMarket, leverage: Decimal) -> Result<Self, Error> {
+        market.check_top_up_leverage(leverage)?;
+        Ok(Self {
+            asset: market.index,
+            leverage,
+        })
+    }
+}
+
+/// Converts a positive USD amount to the 6 decimals integer used by vault actions.
+fn usd_units(amount: Decimal, what: &str) -> Result<u64, Error> {
+    let invalid = |message: String| Error::InvalidAction { message };
+    if amount <= Decimal::ZERO {
+        return Err(invalid(format!("{what} amount {amount} is not positive")));
+    }
+    if amount.normalize().scale() > 6 {
+        return Err(invalid(format!(
+            "{what} amount {amount} has more than 6 decimals"
+        )));
+    }
+    (amount * Decimal::from(1_000_000))
+        .to_u64()
+        .ok_or_else(|| invalid(format!("{what} amount {amount} out of range")))
+}
+
+/// Deposit USDC into a vault or withdraw it.
+///
+/// Withdrawals from a vault are subject to its lockup period, 1 day for user vaults
+/// and 4 days for HLP.
+///
+/// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/exchange-endpoint#deposit-or-withdraw-from-a-vault>
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultTransfer {
+    /// The vault address.
+    #[serde(
+        serialize_with = "crate::hypercore::utils::serialize_address_as_hex",
+        deserialize_with = "crate::hypercore::utils::deserialize_address_from_hex"
+    )]
+    pub vault_address: Address,
+    /// `true` to deposit, `false` to withdraw.
+    pub is_deposit: bool,
+    /// Amount in USD with 6 decimals, e.g. `1_000_000` for 1 USD.
+    pub usd: u64,
+}
+
+impl VaultTransfer {
+    /// Creates a deposit of `amount` USD into `vault`.
+    ///
+    /// Fails with [`Error::InvalidAction`] if `amount` is not positive or has more
+    /// than 6 decimals.
+    pub fn deposit(vault: Address, amount: Decimal) -> Result<Self, Error> {
+        Ok(Self {
+            vault_address: vault,
+            is_deposit: true,
+            usd: usd_units(amount, "deposit")?,
+        })
+    }
+
+    /// Creates a withdrawal of `amount` USD from `vault`, checked as in
+    /// [`deposit`](Self::deposit).
+    pub fn withdraw(vault: Address, amount: Decimal) -> Result<Self, Error> {
+        Ok(Self {
+            vault_address: vault,
+            is_deposit: false,
+            usd: usd_units(amount, "withdrawal")?,
+        })
+    }
+}
+
+/// Create a vault led by the signer.
+///
+/// The leader seeds the vault with `initial_usd` and must keep at least 5% of its
+/// equity afterwards.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVault {
+    /// Vault name.
+    pub name: String,
+    /// Vault description.
+    pub description: String,
+    /// Initial deposit in USD with 6 decimals.
+    pub initial_usd: u64,
+    /// Request nonce, must match the nonce of the action.
+    pub nonce: u64,
+}
+
+impl CreateVault {
+    /// Smallest initial deposit, in USD.
+    pub const MIN_INITIAL_USD: u64 = 100;
+
+    /// Creates a vault creation seeded with `initial` USD.
+    ///
+    /// Fails with [`Error::InvalidAction`] if `name` is empty, or `initial` is below
+    /// [`MIN_INITIAL_USD`](Self::MIN_INITIAL_USD) or has more than 6 decimals.
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        initial: Decimal,
+        nonce: u64,
+    ) -> Result<Self, Error> {
+        let name = name.into();
+        if name.trim().is_empty() {
+            return Err(Error::InvalidAction {
+                message: "vault name is empty".into(),
+            });
+        }
+        if initial < Decimal::from(Self::MIN_INITIAL_USD) {
+            return Err(Error::InvalidAction {
+                message: format!(
+                    "initial vault deposit {initial} is below {} USD",
+                    Self::MIN_INITIAL_USD
+                ),
+            });
+        }
+        Ok(Self {
+            name,
+            description: description.into(),
+            initial_usd: usd_units(initial, "initial deposit")?,
+            nonce,
+        })
+    }
+}
+
+/// Change the settings of a vault.
+///
+/// Settings left to `None` are unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultModify {
+    /// The vault address.
+    #[serde(
+        serialize_with = "crate::hypercore::utils::serialize_address_as_hex",
+        deserialize_with = "crate::hypercore::utils::deserialize_address_from_hex"
+    )]
+    pub vault_address: Address,
+    /// Whether followers can deposit.
+    pub allow_deposits: Option<bool>,
+    /// Whether positions are closed proportionally when followers withdraw.
+    pub always_close_on_withdraw: Option<bool>,
+}
+
+/// Distribute USDC from a vault to its followers, or close the vault.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultDistribute {
+    /// The vault address.
+    #[serde(
+        serialize_with = "crate::hypercore::utils::serialize_address_as_hex",
+        deserialize_with = "crate::hypercore::utils::deserialize_address_from_hex"
+    )]
+    pub vault_address: Address,
+    /// Amount in USD with 6 decimals; `0` closes the vault.
+    pub usd: u64,
+}
+
+impl VaultDistribute {
+    /// Creates a distribution of `amount` USD, checked as in [`VaultTransfer::deposit`].
+    pub fn new(vault: Address, amount: Decimal) -> Result<Self, Error> {
+        Ok(Self {
+            vault_address: vault,
+            usd: usd_units(amount, "distribution")?,
+        })
+    }
+
+    /// Creates a distribution closing `vault`, returning all funds to its followers.
+    #[must_use]
+    pub fn close(vault: Address) -> Self {
+        Self {
+            vault_address: vault,
+            usd: 0,
+        }
+    }
+}
+
+/// Create a subaccount of the signer.
+///
+/// The master account needs $100k of trading volume to create subaccounts.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSubAccount {
+    /// Subaccount name.
+    pub name: String,
+}
+
+impl CreateSubAccount {
+    /// Creates a subaccount creation named `name`.
+    ///
+    /// Fails with [`Error::InvalidAction`] if `name` is empty.
+    pub fn new(name: impl Into<String>) -> Result<Self, Error> {
+        Ok(Self {
+            name: subaccount_name(name.into())?,
+        })
+    }
+}
+
+/// Rename a subaccount.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountModify {
+    /// The subaccount address.
+    #[serde(
+        serialize_with = "crate::hypercore::utils::serialize_address_as_hex",
+        deserialize_with = "crate::hypercore::utils::deserialize_address_from_hex"
+    )]
+    pub sub_account_user: Address,
+    /// New name.
+    pub name: String,
+}
+
+impl SubAccountModify {
+    /// Creates a rename of `sub_account` to `name`, checked as in [`CreateSubAccount::new`].
+    pub fn new(sub_account: Address, name: impl Into<String>) -> Result<Self, Error> {
+        Ok(Self {
+            sub_account_user: sub_account,
+            name: subaccount_name(name.into())?,
+        })
+    }
+}
+
+fn subaccount_name(name: String) -> Result<String, Error> {
+    if name.trim().is_empty() {
+        return Err(Error::InvalidAction {
+            message: "subaccount name is empty".into(),
+        });
+    }
+    Ok(name)
+}
+
+/// Transfer USDC between the perp balances of the master account and a subaccount.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountTransfer {
+    /// The subaccount address.
+    #[serde(
+        serialize_with = "crate::hypercore::utils::serialize_address_as_hex",
+        deserialize_with = "crate::hypercore::utils::deserialize_address_from_hex"
+    )]
+    pub sub_account_user: Address,
+    /// `true` from the master account to the subaccount, `false` the other way.
+    pub is_deposit: bool,
+    /// Amount in USD with 6 decimals, e.g. `1_000_000` for 1 USD.
+    pub usd: u64,
+}
+
+impl SubAccountTransfer {
+    /// Creates a transfer of `amount` USD from the master account to `sub_account`.
+    ///
+    /// Fails with [`Error::InvalidAction`] if `amount` is not positive or has more
+    /// than 6 decimals.
+    pub fn deposit(sub_account: Address, amount: Decimal) -> Result<Self, Error> {
+        Ok(Self {
+            sub_account_user: sub_account,
+            is_deposit: true,
+            usd: usd_units(amount, "deposit")?,
+        })
+    }
+
+    /// Creates a transfer of `amount` USD from `sub_account` to the master account,
+    /// checked as in [`deposit`](Self::deposit).
+    pub fn withdraw(sub_account: Address, amount: Decimal) -> Result<Self, Error> {
+        Ok(Self {
+            sub_account_user: sub_account,
+            is_deposit: false,
+            usd: usd_units(amount, "withdrawal")?,
+        })
+    }
+}
+
+/// Transfer a spot token between the master account and a subaccount.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubAccountSpotTransfer {
+    /// The subaccount address.
+    #[serde(
+        serialize_with = "crate::hypercore::utils::serialize_address_as_hex",
+        deserialize_with = "crate::hypercore::utils::deserialize_address_from_hex"
+    )]
+    pub sub_account_user: Address,
+    /// `true` from the master account to the subaccount, `false` the other way.
+    pub is_deposit: bool,
+    /// Token
+    pub token: String,
+    /// The amount.
+    #[serde(with = "rust_decimal::serde::str")]
+    pub amount: Decimal,
+}
+
+impl SubAccountSpotTransfer {
+    /// Creates a transfer of `amount` of `token` from the master account to `sub_account`.
+    ///
+    /// Fails with [`Error::InvalidAction`] if `amount` is not positive or has more
+    /// decimals than the token allows.
+    pub fn deposit(sub_account: Address, token: SendToken, amount: Decimal) -> Result<Self, Error> {
+        Self::new(sub_account, true, token, amount)
+    }
+
+    /// Creates a transfer of `amount` of `token` from `sub_account` to the master
+    /// account, checked as in [`deposit`](Self::deposit).
+    pub fn withdraw(
+        sub_account: Address,
+        token: SendToken,
+        amount: Decimal,
+    ) -> Result<Self, Error> {
+        Self::new(sub_account, false, token, amount)
+    }
+
+    fn new(
+        sub_account: Address,
+        is_deposit: bool,
+        token: SendToken,
+        amount: Decimal,
+    ) -> Result<Self, Error> {
+        if amount <= Decimal::ZERO {
+            return Err(Error::InvalidAction {
+                message: format!("transfer amount {amount} is not positive"),
+            });
+        }
+        if i64::from(amount.normalize().scale()) > token.0.wei_decimals {
+            return Err(Error::InvalidAction {
+                message: format!(
+                    "transfer amount {amount} has more than {} decimals",
+                    token.0.wei_decimals
+                ),
+            });
+        }
+        Ok(Self {
+            sub_account_user: sub_account,
+            is_deposit,
+            token: token.to_string(),
+            amount,
+        })
+    }
 }
 
 /// Multi-signature action payload.
@@ -703,6 +1300,37 @@ impl MultiSigPayload {
         }
     }
 
+    /// Returns the EIP-712 typed data each authorized user signs for this payload.
+    ///
+    /// Lets signers outside this process, such as browser wallets using
+    /// `eth_signTypedData_v4`, produce the signatures of a [`MultiSigAction`]. Its
+    /// signing hash is the [`prehash`](Self::prehash).
+    pub fn typed_data(&self, nonce: u64, chain: Chain) -> anyhow::Result<TypedData> {
+        let multi_sig_user: Address = self.multi_sig_user.parse()?;
+        let lead: Address = self.outer_signer.parse()?;
+
+        if let Some(typed_data) = self.action.typed_data_multisig(multi_sig_user, lead, chain) {
+            return Ok(typed_data);
+        }
+
+        // RMP-based actions sign an Agent wrapping the hash of the payload
+        let connection_id = utils::rmp_hash(
+            &(&self.multi_sig_user, &self.outer_signer, &self.action),
+            nonce,
+            None,
+            None,
+        )?;
+        Ok(TypedData {
+            domain: CORE_MAINNET_EIP712_DOMAIN,
+            resolver: Resolver::from_struct::<solidity::Agent>(),
+            primary_type: "Agent".into(),
+            message: serde_json::json!({
+                "source": if chain.is_mainnet() { "a" } else { "b" },
+                "connectionId": connection_id,
+            }),
+        })
+    }
+
     /// Signs this multisig payload synchronously and returns a signature.
     ///
     /// Uses EIP-712 typed data for transfers or RMP+Agent for orders/cancels.
@@ -825,7 +1453,7 @@ pub struct MultiSigAction {
 
 #[cfg(test)]
 mod tests {
-    use alloy::primitives::address;
+    use alloy::primitives::{address, b256};
 
     use super::*;
 
@@ -836,6 +1464,21 @@ mod tests {
         let _data: Response = serde_json::from_str(text).unwrap();
     }
 
+    #[test]
+    fn test_multisig_typed_data_matches_prehash() {
+        let payload = MultiSigPayload {
+            multi_sig_user: "0x5ecb62791b22a3108367c2a2024019ee7ea88431".into(),
+            outer_signer: "0x0d6ecb912b6ee160e95bc198b618acc1bcb92525".into(),
+            action: Box::new(Action::ScheduleCancel(ScheduleCancel { time: None })),
+        };
+        let nonce = 1_700_000_000_000;
+        let typed_data = payload.typed_data(nonce, Chain::Testnet).unwrap();
+        assert_eq!(
+            typed_data.eip712_signing_hash().unwrap(),
+            payload.prehash(nonce, Chain::Testnet).unwrap()
+        );
+    }
+
     #[test]
     fn update_isolated_margin() {
         let text = r#"{"action":{"type":"updateIsolatedMargin","asset":173,"isBuy":true,"ntli":2000000},"nonce":1768223623573,"signature":{"r":"0xf85df30c97a4f2cd6b463b5f385d1f93e029791ffc9bb49fdcad2616608350e2","s":"0x3763da7c7ef7a4d7a528815bddff75b854d540487dfb1f1c75e7201f57c2ea6e","v":28}}"#;
@@ -846,4 +1489,237 @@ mod tests {
             address!("0x5eCb62791B22A3108367c2A2024019Ee7eA88431")
         );
     }
+
+    fn perp(max_leverage: u64, margin_mode: Option<crate::hypercore::MarginMode>) -> PerpMarket {
+        PerpMarket {
+            max_leverage,
+            isolated_margin: margin_mode.is_some(),
+            margin_mode,
+            ..crate::hypercore::fixtures::btc()
+        }
+    }
+
+    #[test]
+    fn test_margin_actions() {
+        use crate::hypercore::MarginMode;
+        use rust_decimal::dec;
+
+        let cross = perp(40, None);
+        let action = Action::from(UpdateLeverage::new(&cross, true, 20).unwrap());
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({ "type": "updateLeverage", "asset": 0, "isCross": true, "leverage": 20 })
+        );
+        assert!(UpdateLeverage::new(&cross, true, 0).is_err());
+        assert!(UpdateLeverage::new(&cross, false, 41).is_err());
+        assert!(TopUpIsolatedOnlyMargin::new(&cross, dec!(5)).is_err());
+
+        let isolated = perp(10, Some(MarginMode::NoCross));
+        assert!(UpdateLeverage::new(&isolated, true, 5).is_err());
+        assert!(UpdateLeverage::new(&isolated, false, 5).is_ok());
+        let action = Action::from(TopUpIsolatedOnlyMargin::new(&isolated, dec!(2.5)).unwrap());
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({ "type": "topUpIsolatedOnlyMargin", "asset": 0, "leverage": "2.5" })
+        );
+
+        let margin = UpdateIsolatedMargin::new(&isolated, true, dec!(-12.5)).unwrap();
+        assert_eq!(margin.ntli, -12_500_000);
+        assert!(UpdateIsolatedMargin::new(&isolated, true, dec!(0.0000001)).is_err());
+        assert!(UpdateIsolatedMargin::new(&isolated, true, Decimal::ZERO).is_err());
+
+        let strict = perp(10, Some(MarginMode::StrictIsolated));
+        assert!(UpdateIsolatedMargin::new(&strict, false, dec!(-1)).is_err());
+        assert!(UpdateIsolatedMargin::new(&strict, false, dec!(1)).is_ok());
+    }
+
+    #[test]
+    fn test_twap_actions() {
+        use crate::hypercore::types::TwapOrder;
+        use rust_decimal::dec;
+
+        let action = Action::from(TwapPlacement {
+            twap: TwapOrder {
+                asset: 1,
+                is_buy: false,
+                sz: dec!(2.5),
+                reduce_only: true,
+                minutes: 30,
+                randomize: false,
+            },
+        });
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "type": "twapOrder",
+                "twap": { "a": 1, "b": false, "s": "2.5", "r": true, "m": 30, "t": false }
+            })
+        );
+        let action = Action::from(TwapCancel {
+            asset: 1,
+            twap_id: 42,
+        });
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({ "type": "twapCancel", "a": 1, "t": 42 })
+        );
+
+        let text = r#"{"status":"ok","response":{"type":"twapOrder","data":{"status":{"running":{"twapId":77738308}}}}}"#;
+        let resp: Response = serde_json::from_str(text).unwrap();
+        assert!(matches!(
+            resp,
+            Response::Ok(OkResponse::TwapOrder {
+                status: TwapOrderStatus::Running { twap_id: 77738308 }
+            })
+        ));
+        let text =
+            r#"{"status":"ok","response":{"type":"twapCancel","data":{"status":"success"}}}"#;
+        let resp: Response = serde_json::from_str(text).unwrap();
+        assert!(matches!(
+            resp,
+            Response::Ok(OkResponse::TwapCancel {
+                status: TwapCancelStatus::Success
+            })
+        ));
+        let text = r#"{"status":"ok","response":{"type":"twapCancel","data":{"status":{"error":"TWAP was never placed, already canceled, or filled."}}}}"#;
+        let resp: Response = serde_json::from_str(text).unwrap();
+        assert!(matches!(
+            resp,
+            Response::Ok(OkResponse::TwapCancel {
+                status: TwapCancelStatus::Error(_)
+            })
+        ));
+    }
+
+    #[test]
+    fn test_vault_actions() {
+        use rust_decimal::dec;
+
+        let vault = address!("0xdfc24b077bc1425ad1dea75bcb6f8158e10df303");
+        let action = Action::from(VaultTransfer::deposit(vault, dec!(12.5)).unwrap());
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "type": "vaultTransfer",
+                "vaultAddress": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303",
+                "isDeposit": true,
+                "usd": 12_500_000
+            })
+        );
+        assert_eq!(
+            action.hash(1_700_000_000_000, None, None).unwrap(),
+            b256!("0x0e67f79c7309547af7eaed58d2540ee0182d64328f7e4bd28f4f513c8be0037d")
+        );
+        assert!(matches!(
+            VaultTransfer::withdraw(vault, dec!(-1)),
+            Err(Error::InvalidAction { .. })
+        ));
+        assert!(VaultTransfer::withdraw(vault, dec!(0.0000001)).is_err());
+
+        let nonce = 1_700_000_000_000;
+        let action =
+            Action::from(CreateVault::new("Alpha", "Basis trades", dec!(100), nonce).unwrap());
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "type": "createVault",
+                "name": "Alpha",
+                "description": "Basis trades",
+                "initialUsd": 100_000_000,
+                "nonce": nonce
+            })
+        );
+        assert_eq!(
+            action.hash(nonce, None, None).unwrap(),
+            b256!("0x7b54843dd53bd54d6f441017965d94cbedf05b750178509357bce1b2ba827f1f")
+        );
+        assert!(CreateVault::new("Alpha", "", dec!(99), 7).is_err());
+        assert!(CreateVault::new(" ", "", dec!(100), 7).is_err());
+
+        let action = Action::from(VaultModify {
+            vault_address: vault,
+            allow_deposits: Some(false),
+            always_close_on_withdraw: None,
+        });
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "type": "vaultModify",
+                "vaultAddress": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303",
+                "allowDeposits": false,
+                "alwaysCloseOnWithdraw": null
+            })
+        );
+        assert_eq!(VaultDistribute::close(vault).usd, 0);
+        assert!(VaultDistribute::new(vault, Decimal::ZERO).is_err());
+
+        let text = r#"{"status":"ok","response":{"type":"createVault","data":"0xdfc24b077bc1425ad1dea75bcb6f8158e10df303"}}"#;
+        let resp: Response = serde_json::from_str(text).unwrap();
+        assert!(matches!(resp, Response::Ok(OkResponse::CreateVault(addr)) if addr == vault));
+    }
+
+    #[test]
+    fn test_subaccount_actions() {
+        use rust_decimal::dec;
+
+        let sub = address!("0x035605fc2f24d65300227189025e90a0d947f16c");
+        let action = Action::from(CreateSubAccount::new("basis").unwrap());
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({ "type": "createSubAccount", "name": "basis" })
+        );
+        assert!(matches!(
+            CreateSubAccount::new(""),
+            Err(Error::InvalidAction { .. })
+        ));
+
+        let action = Action::from(SubAccountModify::new(sub, "carry").unwrap());
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "type": "subAccountModify",
+                "subAccountUser": "0x035605fc2f24d65300227189025e90a0d947f16c",
+                "name": "carry"
+            })
+        );
+
+        let action = Action::from(SubAccountTransfer::withdraw(sub, dec!(250)).unwrap());
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "type": "subAccountTransfer",
+                "subAccountUser": "0x035605fc2f24d65300227189025e90a0d947f16c",
+                "isDeposit": false,
+                "usd": 250_000_000
+            })
+        );
+        assert_eq!(
+            action.hash(1_700_000_000_000, None, None).unwrap(),
+            b256!("0x2734042f1a5623d8f643341f00cf94d204795a95efe8c905f865fee372c939f2")
+        );
+
+        let purr = SendToken(crate::hypercore::fixtures::purr());
+        let action =
+            Action::from(SubAccountSpotTransfer::deposit(sub, purr.clone(), dec!(12.5)).unwrap());
+        assert_eq!(
+            serde_json::to_value(&action).unwrap(),
+            serde_json::json!({
+                "type": "subAccountSpotTransfer",
+                "subAccountUser": "0x035605fc2f24d65300227189025e90a0d947f16c",
+                "isDeposit": true,
+                "token": "PURR",
+                "amount": "12.5"
+            })
+        );
+        assert_eq!(
+            action.hash(1_700_000_000_000, None, None).unwrap(),
+            b256!("0xca15c2b57e02ac5c1317515486ebddc21d306b3b5c8d33f946a7733eb0a009bd")
+        );
+        assert!(SubAccountSpotTransfer::withdraw(sub, purr.clone(), dec!(0.000001)).is_err());
+        assert!(SubAccountSpotTransfer::withdraw(sub, purr, Decimal::ZERO).is_err());
+
+        let text = r#"{"status":"ok","response":{"type":"createSubAccount","data":"0x035605fc2f24d65300227189025e90a0d947f16c"}}"#;
+        let resp: Response = serde_json::from_str(text).unwrap();
+        assert!(matches!(resp, Response::Ok(OkResponse::CreateSubAccount(addr)) if addr == sub));
+    }
 }
@@ -25,6 +25,7 @@
 //!
 //! ## Transfer Types
 //! - [`UsdSend`]: Send USDC from perp balance
+//! - [`Withdraw`]: Withdraw USDC from perp balance to Arbitrum
 //! - [`SpotSend`]: Send spot tokens
 //! - [`SendAsset`]: Send assets between accounts/DEXes
 //!
@@ -77,7 +78,9 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use crate::hypercore::{Chain, Cloid, OidOrCloid, SpotToken};
+use crate::hypercore::{Chain, Cloid, OidOrCloid, SpotToken, rate_limit};
+#[cfg(feature = "graphql")]
+use crate::hypergraphql::scalar;
 
 pub mod api;
 pub(super) mod solidity;
@@ -86,7 +89,10 @@ pub(super) mod solidity;
 pub use api::{Action, ActionRequest, MultiSigAction, MultiSigPayload};
 
 // Import from raw module (which is now a submodule)
-use api::{SendAssetAction, SpotSendAction, UsdSendAction};
+use api::{
+    SendAssetAction, SpotSendAction, SubAccountModify, SubAccountSpotTransfer, SubAccountTransfer,
+    UsdSendAction, WithdrawAction,
+};
 
 /// Domain for Core mainnet EIP‑712 signing.
 /// This domain is used when creating signatures for transactions on the mainnet.
@@ -129,6 +135,12 @@ impl Dex {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Returns the DEX index.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
 }
 
 impl PartialEq for Dex {
@@ -145,12 +157,86 @@ impl Hash for Dex {
     }
 }
 
+/// Market designated by an asset index.
+///
+/// Orders and other actions name markets by asset index: perpetuals of the default
+/// DEX use their index, spot markets `10_000` plus their index, and perpetuals of
+/// HIP-3 DEXes `100_000 + dex * 10_000` plus their index.
+///
+/// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/asset-ids>
+///
+/// # Example
+///
+/// ```
+/// use hypersdk::hypercore::AssetIndex;
+///
+/// assert_eq!(AssetIndex::Spot(107).asset(), 10_107);
+/// assert_eq!(AssetIndex::from_asset(110_003), AssetIndex::DexPerp { dex: 1, index: 3 });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetIndex {
+    /// Perpetual of the default DEX.
+    Perp(usize),
+    /// Spot market.
+    Spot(usize),
+    /// Perpetual of a HIP-3 DEX, `dex` being the [`Dex::index`].
+    DexPerp { dex: usize, index: usize },
+}
+
+impl AssetIndex {
+    /// First asset index of spot markets.
+    pub const SPOT_OFFSET: usize = 10_000;
+    /// First asset index of HIP-3 DEX perpetuals.
+    pub const DEX_OFFSET: usize = 100_000;
+    /// Number of asset indices reserved for each HIP-3 DEX.
+    pub const DEX_SPAN: usize = 10_000;
+
+    /// Returns the index of a perpetual of `dex`, or of the default DEX if `None`.
+    #[must_use]
+    pub fn perp(dex: Option<&Dex>, index: usize) -> Self {
+        match dex {
+            Some(dex) => Self::DexPerp {
+                dex: dex.index,
+                index,
+            },
+            None => Self::Perp(index),
+        }
+    }
+
+    /// Parses an asset index.
+    #[must_use]
+    pub fn from_asset(asset: usize) -> Self {
+        if asset < Self::SPOT_OFFSET {
+            Self::Perp(asset)
+        } else if asset < Self::DEX_OFFSET {
+            Self::Spot(asset - Self::SPOT_OFFSET)
+        } else {
+            let offset = asset - Self::DEX_OFFSET;
+            Self::DexPerp {
+                dex: offset / Self::DEX_SPAN,
+                index: offset % Self::DEX_SPAN,
+            }
+        }
+    }
+
+    /// Returns the asset index used in API calls.
+    #[must_use]
+    pub fn asset(self) -> usize {
+        match self {
+            Self::Perp(index) => index,
+            Self::Spot(index) => Self::SPOT_OFFSET + index,
+            Self::DexPerp { dex, index } => Self::DEX_OFFSET + dex * Self::DEX_SPAN + index,
+        }
+    }
+}
+
 /// Side for a trade or an order.
 ///
 /// `Bid` represents a buy order, `Ask` represents a sell order.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, derive_more::Display,
 )]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum Side {
     #[serde(rename = "B")]
     Bid,
@@ -192,6 +278,8 @@ pub enum Outgoing {
 /// |--------------|------------------|-------------|
 /// | [`OrderUpdates`](Self::OrderUpdates) | [`Incoming::OrderUpdates`] | Order status changes |
 /// | [`UserFills`](Self::UserFills) | [`Incoming::UserFills`] | Trade fills |
+/// | [`UserTwapSliceFills`](Self::UserTwapSliceFills) | [`Incoming::UserTwapSliceFills`] | TWAP slice fills |
+/// | [`UserTwapHistory`](Self::UserTwapHistory) | [`Incoming::UserTwapHistory`] | TWAP lifecycle events |
 ///
 /// # Related Types
 ///
@@ -249,6 +337,12 @@ pub enum Subscription {
     /// Fill events for user
     #[display("userFills({user})")]
     UserFills { user: Address },
+    /// TWAP slice fills for user
+    #[display("userTwapSliceFills({user})")]
+    UserTwapSliceFills { user: Address },
+    /// TWAP lifecycle events for user
+    #[display("userTwapHistory({user})")]
+    UserTwapHistory { user: Address },
 }
 
 /// Hyperliquid websocket message.
@@ -266,6 +360,8 @@ pub enum Subscription {
 /// - **Trades**: Trade events for a market
 /// - **OrderUpdates**: Order status changes for a user
 /// - **UserFills**: Fill events for a user
+/// - **UserTwapSliceFills**: TWAP slice fills for a user
+/// - **UserTwapHistory**: TWAP lifecycle events for a user
 /// - **Ping/Pong**: Heartbeat messages
 ///
 /// # Example
@@ -320,6 +416,21 @@ pub enum Incoming {
     OrderUpdates(Vec<OrderUpdate>),
     /// Fill events for a user
     UserFills { user: Address, fills: Vec<Fill> },
+    /// TWAP slice fills for a user; the first message is a snapshot of recent fills
+    UserTwapSliceFills {
+        user: Address,
+        #[serde(rename = "twapSliceFills")]
+        fills: Vec<TwapSliceFill>,
+        #[serde(rename = "isSnapshot", default)]
+        is_snapshot: Option<bool>,
+    },
+    /// TWAP lifecycle events for a user; the first message is a snapshot of the history
+    UserTwapHistory {
+        user: Address,
+        history: Vec<TwapHistory>,
+        #[serde(rename = "isSnapshot", default)]
+        is_snapshot: Option<bool>,
+    },
     /// Server heartbeat ping
     Ping,
     /// Server heartbeat pong
@@ -331,6 +442,7 @@ pub enum Incoming {
 /// Contains status, timestamp, and the original order details.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct OrderUpdate {
     pub status: OrderStatus,
     pub status_timestamp: u64,
@@ -369,12 +481,15 @@ pub struct OrderUpdate {
 /// ```
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct Bbo {
     /// Market symbol
     pub coin: String,
     /// Timestamp in milliseconds
     pub time: u64,
     /// (best_bid, best_ask)
+    #[cfg_attr(feature = "graphql", graphql(skip))]
     pub bbo: (Option<BookLevel>, Option<BookLevel>),
 }
 
@@ -432,10 +547,19 @@ impl Bbo {
 /// ```
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct BookLevel {
     /// Price level
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "px", into = "scalar::Decimal"))
+    )]
     pub px: Decimal,
     /// Total size at this level
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "sz", into = "scalar::Decimal"))
+    )]
     pub sz: Decimal,
     /// Number of orders at this level
     pub n: usize,
@@ -481,14 +605,23 @@ pub struct BookLevel {
 /// ```
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct Trade {
     /// Market symbol
     pub coin: String,
     /// Taker's side (Bid = buy, Ask = sell)
     pub side: Side,
     /// Execution price
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "px", into = "scalar::Decimal"))
+    )]
     pub px: Decimal,
     /// Trade size
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "sz", into = "scalar::Decimal"))
+    )]
     pub sz: Decimal,
     /// Timestamp in milliseconds
     pub time: u64,
@@ -549,6 +682,7 @@ impl Trade {
 /// assert_eq!(parsed, CandleInterval::FifteenMinutes);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, derive_more::Display)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum CandleInterval {
     #[serde(rename = "1m")]
     #[display("1m")]
@@ -636,6 +770,7 @@ impl std::str::FromStr for CandleInterval {
 /// - `num_trades`: Number of trades in this candle
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct Candle {
     /// Candle open time (milliseconds)
     #[serde(rename = "t")]
@@ -651,18 +786,38 @@ pub struct Candle {
     pub interval: String,
     /// Open price
     #[serde(rename = "o")]
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "open", into = "scalar::Decimal"))
+    )]
     pub open: Decimal,
     /// High price
     #[serde(rename = "h")]
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "high", into = "scalar::Decimal"))
+    )]
     pub high: Decimal,
     /// Low price
     #[serde(rename = "l")]
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "low", into = "scalar::Decimal"))
+    )]
     pub low: Decimal,
     /// Close price
     #[serde(rename = "c")]
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "close", into = "scalar::Decimal"))
+    )]
     pub close: Decimal,
     /// Volume
     #[serde(rename = "v")]
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "volume", into = "scalar::Decimal"))
+    )]
     pub volume: Decimal,
     /// Number of trades
     #[serde(rename = "n")]
@@ -712,6 +867,8 @@ pub struct Candle {
 /// ```
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct L2Book {
     /// Market symbol
     pub coin: String,
@@ -721,6 +878,7 @@ pub struct L2Book {
     #[serde(default)]
     pub snapshot: Option<bool>,
     /// [bids, asks]
+    #[cfg_attr(feature = "graphql", graphql(skip))]
     pub levels: [Vec<BookLevel>; 2],
 }
 
@@ -823,22 +981,42 @@ impl L2Book {
 /// ```
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct Fill {
     /// Market symbol
     pub coin: String,
     /// Fill price
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "px", into = "scalar::Decimal"))
+    )]
     pub px: Decimal,
     /// Fill size
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "sz", into = "scalar::Decimal"))
+    )]
     pub sz: Decimal,
     /// Order side
     pub side: Side,
     /// Timestamp in milliseconds
     pub time: u64,
     /// Position before fill
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "start_position", into = "scalar::Decimal")
+        )
+    )]
     pub start_position: Decimal,
     /// Direction (Open/Close Long/Short)
     pub dir: String,
     /// Realized PnL from closing
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "closed_pnl", into = "scalar::Decimal"))
+    )]
     pub closed_pnl: Decimal,
     /// Transaction hash
     pub hash: String,
@@ -847,10 +1025,26 @@ pub struct Fill {
     /// True if taker (crossed spread)
     pub crossed: bool,
     /// Fee amount
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "fee", into = "scalar::Decimal"))
+    )]
     pub fee: Decimal,
     /// Trade ID
     pub tid: u64,
     /// Client order ID
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(
+                owned,
+                name = "cloid",
+                into = "Option<scalar::Cloid>",
+                with = "scalar::option"
+            )
+        )
+    )]
     pub cloid: Option<B128>,
     /// Fee token
     pub fee_token: String,
@@ -909,14 +1103,39 @@ impl Fill {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde_as]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct BasicOrder {
     pub timestamp: u64,
     pub coin: String,
     pub side: Side,
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "limit_px", into = "scalar::Decimal"))
+    )]
     pub limit_px: Decimal,
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "sz", into = "scalar::Decimal"))
+    )]
     pub sz: Decimal,
     pub oid: u64,
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "orig_sz", into = "scalar::Decimal"))
+    )]
     pub orig_sz: Decimal,
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(
+                owned,
+                name = "cloid",
+                into = "Option<scalar::Cloid>",
+                with = "scalar::option"
+            )
+        )
+    )]
     pub cloid: Option<B128>,
     pub order_type: OrderType,
     pub tif: Option<TimeInForce>,
@@ -934,10 +1153,15 @@ pub struct BasicOrder {
 /// - `method`: Liquidation method used
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct Liquidation {
     /// Address of liquidated user
     pub liquidated_user: String,
     /// Mark price at liquidation
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "mark_px", into = "scalar::Decimal"))
+    )]
     pub mark_px: Decimal,
     /// Liquidation method
     pub method: String,
@@ -946,8 +1170,9 @@ pub struct Liquidation {
 /// Order type.
 ///
 /// Determines the behaviour of the order (limit, market, or trigger).
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "PascalCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum OrderType {
     Limit,
     Market,
@@ -985,8 +1210,9 @@ pub enum OrderType {
 /// // Standard order: remains active until filled or cancelled
 /// let standard_tif = TimeInForce::Gtc;
 /// ```
-#[derive(Debug, Deserialize, Clone, Copy, Serialize)]
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Serialize)]
 #[serde(rename = "PascalCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum TimeInForce {
     /// Add Liquidity Only - maker-only order
     Alo,
@@ -1059,8 +1285,9 @@ pub enum TimeInForce {
 /// let status = OrderStatus::Open;
 /// assert!(!status.is_finished());
 /// ```
-#[derive(Debug, Copy, Clone, Hash, Deserialize, Serialize, derive_more::Display)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, derive_more::Display)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum OrderStatus {
     /// Order is active on the book
     Open,
@@ -1261,6 +1488,39 @@ impl UsdSend {
     }
 }
 
+/// Withdraw USDC to Arbitrum (inner data).
+///
+/// This is the core data structure for a withdrawal. To create a signable action,
+/// use the `into_action()` method to convert it to a `WithdrawAction`.
+///
+/// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/exchange-endpoint#initiate-a-withdrawal-request>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Withdraw {
+    /// The destination address on Arbitrum.
+    pub destination: Address,
+    /// The amount, including the withdrawal fee.
+    pub amount: Decimal,
+    /// Current time, should match the nonce
+    pub time: u64,
+}
+
+impl Withdraw {
+    /// Flat fee in USDC deducted from every withdrawal.
+    pub const FEE: Decimal = Decimal::ONE;
+
+    /// Converts this into a signable `WithdrawAction`.
+    #[must_use]
+    pub fn into_action(self, chain: Chain) -> WithdrawAction {
+        WithdrawAction {
+            signature_chain_id: chain.arbitrum_id().to_owned(),
+            hyperliquid_chain: chain,
+            destination: self.destination,
+            amount: self.amount,
+            time: self.time,
+        }
+    }
+}
+
 /// Send spot tokens (inner data).
 ///
 /// This is the core data structure for a spot token transfer. To create a signable action,
@@ -1527,8 +1787,9 @@ pub struct BatchOrder {
 /// - `Na` – No special grouping; orders are independent.
 /// - `NormalTpsl` – Link a main order with its take-profit/stop-loss orders.
 /// - `PositionTpsl` – Attach TP/SL orders to an existing position.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum OrderGrouping {
     Na,
     NormalTpsl,
@@ -1593,6 +1854,7 @@ pub enum OrderTypePlacement {
 /// Indicates whether the trigger is a take‑profit (`Tp`) or stop‑loss (`Sl`).
 #[derive(PartialEq, Eq, Deserialize, Serialize, Copy, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum TpSl {
     Tp,
     Sl,
@@ -1674,6 +1936,94 @@ pub struct ScheduleCancel {
     pub time: Option<u64>,
 }
 
+/// Native TWAP order.
+///
+/// The exchange splits the order into slices executed every 30 seconds over
+/// `minutes`. Each slice is an IOC order limited to 3% slippage from the mark price.
+///
+/// # Example
+///
+/// ```rust
+/// use hypersdk::hypercore::types::TwapOrder;
+/// use rust_decimal::dec;
+///
+/// // Buy 10 ETH over 2 hours
+/// let twap = TwapOrder {
+///     asset: 1,
+///     is_buy: true,
+///     sz: dec!(10),
+///     reduce_only: false,
+///     minutes: 120,
+///     randomize: true,
+/// };
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TwapOrder {
+    /// Asset index.
+    #[serde(rename = "a")]
+    pub asset: usize,
+    /// `true` for a buy, `false` for a sell.
+    #[serde(rename = "b")]
+    pub is_buy: bool,
+    /// Total size in base asset units.
+    #[serde(rename = "s", with = "rust_decimal::serde::str")]
+    pub sz: Decimal,
+    /// When `true`, slices can only reduce an existing position.
+    #[serde(rename = "r")]
+    pub reduce_only: bool,
+    /// Duration in minutes, from 5 to 1440.
+    #[serde(rename = "m")]
+    pub minutes: u32,
+    /// Whether to randomize the slice sizes.
+    #[serde(rename = "t")]
+    pub randomize: bool,
+}
+
+impl TwapOrder {
+    /// Shortest TWAP duration, in minutes.
+    pub const MIN_MINUTES: u32 = 5;
+    /// Longest TWAP duration, in minutes.
+    pub const MAX_MINUTES: u32 = 24 * 60;
+}
+
+/// TWAP order placement action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapPlacement {
+    pub twap: TwapOrder,
+}
+
+/// TWAP order cancellation action.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TwapCancel {
+    /// Asset index.
+    #[serde(rename = "a")]
+    pub asset: usize,
+    /// TWAP ID, as returned when placing the TWAP.
+    #[serde(rename = "t")]
+    pub twap_id: u64,
+}
+
+/// Status of a placed TWAP order.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TwapOrderStatus {
+    /// The TWAP is running
+    Running { twap_id: u64 },
+    /// The TWAP was rejected
+    Error(String),
+}
+
+/// Status of a TWAP cancellation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TwapCancelStatus {
+    /// The TWAP was cancelled
+    Success,
+    /// The cancellation was rejected
+    Error(String),
+}
+
 /// Clearinghouse state for a user's perpetual positions.
 ///
 /// # Example
@@ -1702,14 +2052,30 @@ pub struct ScheduleCancel {
 /// ```
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct ClearinghouseState {
     /// Margin summary for isolated positions
     pub margin_summary: MarginSummary,
     /// Margin summary for cross-margin account
     pub cross_margin_summary: MarginSummary,
     /// Cross maintenance margin used
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(
+                owned,
+                name = "cross_maintenance_margin_used",
+                into = "scalar::Decimal"
+            )
+        )
+    )]
     pub cross_maintenance_margin_used: Decimal,
     /// Amount available for withdrawal
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "withdrawable", into = "scalar::Decimal"))
+    )]
     pub withdrawable: Decimal,
     /// List of asset positions
     pub asset_positions: Vec<AssetPosition>,
@@ -1722,14 +2088,34 @@ pub struct ClearinghouseState {
 /// Contains aggregate margin information for either isolated or cross-margin positions.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct MarginSummary {
     /// Total account value (equity)
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "account_value", into = "scalar::Decimal"))
+    )]
     pub account_value: Decimal,
     /// Total notional position value
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "total_ntl_pos", into = "scalar::Decimal"))
+    )]
     pub total_ntl_pos: Decimal,
     /// Total raw USD value
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "total_raw_usd", into = "scalar::Decimal"))
+    )]
     pub total_raw_usd: Decimal,
     /// Total margin used
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "total_margin_used", into = "scalar::Decimal")
+        )
+    )]
     pub total_margin_used: Decimal,
 }
 
@@ -1756,6 +2142,7 @@ impl MarginSummary {
 /// Position type for perpetual positions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, derive_more::Display)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum PositionType {
     /// One-way position mode (single position per market)
     #[display("oneWay")]
@@ -1767,6 +2154,7 @@ pub enum PositionType {
 /// Wraps the position details along with cumulative funding information.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct AssetPosition {
     /// Type of position
     #[serde(rename = "type")]
@@ -1780,24 +2168,78 @@ pub struct AssetPosition {
 /// Contains all information about a single perpetual position.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct PositionData {
     /// Asset/coin symbol (e.g., "BTC", "ETH")
     pub coin: String,
     /// Position size (positive for long, negative for short)
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "szi", into = "scalar::Decimal"))
+    )]
     pub szi: Decimal,
     /// Leverage configuration
     pub leverage: Leverage,
     /// Entry price
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(
+                owned,
+                name = "entry_px",
+                into = "Option<scalar::Decimal>",
+                with = "scalar::option"
+            )
+        )
+    )]
     pub entry_px: Option<Decimal>,
     /// Current position value
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "position_value", into = "scalar::Decimal")
+        )
+    )]
     pub position_value: Decimal,
     /// Unrealized profit and loss
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "unrealized_pnl", into = "scalar::Decimal")
+        )
+    )]
     pub unrealized_pnl: Decimal,
     /// Return on equity (as a decimal, e.g., 0.05 for 5%)
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "return_on_equity", into = "scalar::Decimal")
+        )
+    )]
     pub return_on_equity: Decimal,
     /// Liquidation price (None if no position)
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(
+                owned,
+                name = "liquidation_px",
+                into = "Option<scalar::Decimal>",
+                with = "scalar::option"
+            )
+        )
+    )]
     pub liquidation_px: Option<Decimal>,
     /// Margin used for this position
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "margin_used", into = "scalar::Decimal"))
+    )]
     pub margin_used: Decimal,
     /// Maximum leverage allowed for this asset
     pub max_leverage: u32,
@@ -1834,6 +2276,7 @@ impl PositionData {
 /// Leverage type for positions.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, derive_more::Display)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum LeverageType {
     /// Cross-margin mode (shared margin across positions)
     #[display("cross")]
@@ -1846,6 +2289,7 @@ pub enum LeverageType {
 /// Leverage configuration for a position.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct Leverage {
     /// Leverage type
     #[serde(rename = "type")]
@@ -1855,6 +2299,18 @@ pub struct Leverage {
     /// Raw USD value used for isolated leverage (if applicable)
     #[serde(default)]
     #[serde(with = "rust_decimal::serde::str_option")]
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(
+                owned,
+                name = "raw_usd",
+                into = "Option<scalar::Decimal>",
+                with = "scalar::option"
+            )
+        )
+    )]
     pub raw_usd: Option<Decimal>,
 }
 
@@ -1877,12 +2333,25 @@ impl Leverage {
 /// Tracks funding payments over different time periods.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct CumulativeFunding {
     /// Total funding payments since position opened
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "all_time", into = "scalar::Decimal"))
+    )]
     pub all_time: Decimal,
     /// Funding payments since position was opened
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "since_open", into = "scalar::Decimal"))
+    )]
     pub since_open: Decimal,
     /// Funding payments since last position change
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "since_change", into = "scalar::Decimal"))
+    )]
     pub since_change: Decimal,
 }
 
@@ -1917,14 +2386,23 @@ pub struct CumulativeFunding {
 /// ```
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct FundingRate {
     /// Market symbol (e.g., "BTC", "ETH")
     pub coin: String,
     /// Funding rate applied to positions
     #[serde(with = "rust_decimal::serde::str")]
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "funding_rate", into = "scalar::Decimal"))
+    )]
     pub funding_rate: Decimal,
     /// Market premium component
     #[serde(with = "rust_decimal::serde::str")]
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "premium", into = "scalar::Decimal"))
+    )]
     pub premium: Decimal,
     /// Timestamp in milliseconds
     pub time: u64,
@@ -1952,6 +2430,141 @@ impl FundingRate {
     }
 }
 
+/// Venue name of Hyperliquid in [`PredictedFundings`].
+pub const HYPERLIQUID_VENUE: &str = "HlPerp";
+
+/// Predicted next funding of a coin on Hyperliquid and the other venues it reports.
+///
+/// Returned by [`HttpClient::predicted_fundings`](crate::hypercore::HttpClient::predicted_fundings).
+/// Venues are named as in the API, e.g. `HlPerp`, `BinPerp` or `BybitPerp`; venues
+/// without a prediction are left out.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(from = "(String, Vec<(String, Option<PredictedFunding>)>)")]
+pub struct PredictedFundings {
+    /// Coin (e.g., "BTC", "ETH")
+    pub coin: String,
+    /// Predicted funding per venue
+    pub venues: Vec<(String, PredictedFunding)>,
+}
+
+impl From<(String, Vec<(String, Option<PredictedFunding>)>)> for PredictedFundings {
+    fn from((coin, venues): (String, Vec<(String, Option<PredictedFunding>)>)) -> Self {
+        let venues = venues
+            .into_iter()
+            .filter_map(|(venue, funding)| Some((venue, funding?)))
+            .collect();
+        Self { coin, venues }
+    }
+}
+
+impl PredictedFundings {
+    /// Returns the predicted funding on `venue`.
+    #[must_use]
+    pub fn venue(&self, venue: &str) -> Option<&PredictedFunding> {
+        self.venues
+            .iter()
+            .find(|(name, _)| name == venue)
+            .map(|(_, funding)| funding)
+    }
+
+    /// Returns the predicted funding on Hyperliquid.
+    #[must_use]
+    pub fn hyperliquid(&self) -> Option<&PredictedFunding> {
+        self.venue(HYPERLIQUID_VENUE)
+    }
+
+    /// Returns the widest annualized funding spread between two venues.
+    ///
+    /// Returns `None` if fewer than two venues have a prediction.
+    #[must_use]
+    pub fn spread(&self) -> Option<FundingSpread> {
+        let annualized = || {
+            self.venues
+                .iter()
+                .map(|(venue, funding)| (venue, funding.annualized_rate()))
+        };
+        let (long_venue, low) = annualized().min_by_key(|(_, rate)| *rate)?;
+        let (short_venue, high) = annualized().max_by_key(|(_, rate)| *rate)?;
+        (long_venue != short_venue).then(|| FundingSpread {
+            coin: self.coin.clone(),
+            long_venue: long_venue.clone(),
+            short_venue: short_venue.clone(),
+            spread: high - low,
+        })
+    }
+}
+
+/// Predicted next funding of a coin on one venue.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PredictedFunding {
+    /// Funding rate per interval
+    pub funding_rate: Decimal,
+    /// Next funding time in milliseconds
+    pub next_funding_time: u64,
+    /// Hours between fundings: 1 on Hyperliquid, usually 8 on other venues
+    pub funding_interval_hours: u32,
+}
+
+impl PredictedFunding {
+    /// Returns the annualized funding rate, taking the venue's funding interval into
+    /// account.
+    #[must_use]
+    pub fn annualized_rate(&self) -> Decimal {
+        let periods_per_year = 24 * 365 / self.funding_interval_hours.max(1);
+        self.funding_rate * Decimal::from(periods_per_year)
+    }
+}
+
+/// Annualized funding spread of a coin between two venues.
+///
+/// Holding a long position on `long_venue`, which has the lowest funding, and a short
+/// position on `short_venue`, which has the highest, earns `spread` per year before
+/// fees, if the predicted rates held.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FundingSpread {
+    /// Coin (e.g., "BTC", "ETH")
+    pub coin: String,
+    /// Venue with the lowest annualized funding
+    pub long_venue: String,
+    /// Venue with the highest annualized funding
+    pub short_venue: String,
+    /// Annualized funding difference between the two venues
+    pub spread: Decimal,
+}
+
+impl FundingSpread {
+    /// Ranks coins by their widest annualized funding spread, widest first.
+    ///
+    /// Coins with fewer than two venues are left out.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use hypersdk::hypercore::{self, FundingSpread};
+    ///
+    /// # async fn example() -> anyhow::Result<()> {
+    /// let client = hypercore::mainnet();
+    /// let fundings = client.predicted_fundings().await?;
+    ///
+    /// for spread in FundingSpread::rank(&fundings).iter().take(10) {
+    ///     println!("{}: long {} / short {}: {:.2}%",
+    ///         spread.coin, spread.long_venue, spread.short_venue, spread.spread * rust_decimal::dec!(100));
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn rank(fundings: &[PredictedFundings]) -> Vec<FundingSpread> {
+        let mut spreads: Vec<_> = fundings
+            .iter()
+            .filter_map(PredictedFundings::spread)
+            .collect();
+        spreads.sort_by_key(|spread| std::cmp::Reverse(spread.spread));
+        spreads
+    }
+}
+
 /// User balance.
 ///
 /// Represents the balance of a specific token in a user's account.
@@ -1986,16 +2599,29 @@ impl FundingRate {
 /// ```
 #[derive(Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct UserBalance {
     /// Token symbol
     pub coin: String,
     /// Token index
     pub token: usize,
     /// Amount held (locked)
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "hold", into = "scalar::Decimal"))
+    )]
     pub hold: Decimal,
     /// Total balance
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "total", into = "scalar::Decimal"))
+    )]
     pub total: Decimal,
     /// Entry notional
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "entry_ntl", into = "scalar::Decimal"))
+    )]
     pub entry_ntl: Decimal,
 }
 
@@ -2074,8 +2700,21 @@ impl fmt::Display for SendToken {
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct MultiSigConfig {
     /// Addresses authorized to sign for this multisig account
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(
+                owned,
+                name = "authorized_users",
+                into = "Vec<scalar::Address>",
+                with = "scalar::list"
+            )
+        )
+    )]
     pub authorized_users: Vec<Address>,
     /// Minimum number of signatures required (e.g., 2 for 2-of-3)
     pub threshold: usize,
@@ -2086,10 +2725,15 @@ pub struct MultiSigConfig {
 /// Represents an additional agent authorized to act on behalf of a user account.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct ApiAgent {
     /// Name or identifier of the agent
     pub name: String,
     /// Address of the agent
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "address", into = "scalar::Address"))
+    )]
     pub address: Address,
     /// Timestamp in milliseconds until which this agent is valid
     pub valid_until: Option<u64>,
@@ -2122,6 +2766,7 @@ pub struct ApiAgent {
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct UserRole {
     /// The role type
     pub role: UserRoleType,
@@ -2130,6 +2775,7 @@ pub struct UserRole {
 /// Type of role for a user in the Hyperliquid system.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, derive_more::Display)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum UserRoleType {
     /// Regular user account
     User,
@@ -2166,10 +2812,19 @@ pub enum UserRoleType {
 /// ```
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct UserVaultEquity {
     /// The vault address
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "vault_address", into = "scalar::Address"))
+    )]
     pub vault_address: Address,
     /// User's equity in the vault
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "equity", into = "scalar::Decimal"))
+    )]
     pub equity: Decimal,
     /// Timestamp until which funds are locked
     pub locked_until_timestamp: Option<u64>,
@@ -2183,30 +2838,73 @@ pub struct UserVaultEquity {
 /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint#retrieve-details-for-a-vault>
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct VaultDetails {
     /// Name of the vault
     pub name: String,
     /// Address of the vault
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "vault_address", into = "scalar::Address"))
+    )]
     pub vault_address: Address,
     /// Leader (manager) of the vault
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "leader", into = "scalar::Address"))
+    )]
     pub leader: Address,
     /// Description of the vault
     pub description: String,
     /// Portfolio performance data for different time periods
+    #[cfg_attr(feature = "graphql", graphql(skip))]
     pub portfolio: Vec<(String, VaultPortfolio)>,
     /// Annual percentage return
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "apr", into = "scalar::Decimal"))
+    )]
     pub apr: Decimal,
     /// State of the current user as a follower (if queried with user parameter)
     pub follower_state: Option<VaultFollowerState>,
     /// Leader's fraction of the vault
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "leader_fraction", into = "scalar::Decimal")
+        )
+    )]
     pub leader_fraction: Decimal,
     /// Leader's commission rate
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "leader_commission", into = "scalar::Decimal")
+        )
+    )]
     pub leader_commission: Decimal,
     /// List of vault followers
     pub followers: Vec<VaultFollower>,
     /// Maximum amount that can be distributed
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "max_distributable", into = "scalar::Decimal")
+        )
+    )]
     pub max_distributable: Decimal,
     /// Maximum amount that can be withdrawn
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "max_withdrawable", into = "scalar::Decimal")
+        )
+    )]
     pub max_withdrawable: Decimal,
     /// Whether the vault is closed
     #[serde(default)]
@@ -2225,6 +2923,7 @@ pub struct VaultDetails {
 /// Vault relationship type.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct VaultRelationship {
     /// Type of relationship
     #[serde(rename = "type")]
@@ -2234,20 +2933,25 @@ pub struct VaultRelationship {
 /// Type of vault relationship.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, derive_more::Display)]
 #[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 pub enum VaultRelationshipType {
     /// Normal vault relationship
     Normal,
 }
 
-/// Vault portfolio data for a specific time period.
+/// Portfolio data of a vault or a user for a specific time period.
 ///
 /// Contains historical account value and PnL data.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct VaultPortfolio {
     /// Historical account values as (timestamp_ms, value) pairs
+    #[cfg_attr(feature = "graphql", graphql(skip))]
     pub account_value_history: Vec<(u64, String)>,
     /// Historical PnL values as (timestamp_ms, value) pairs
+    #[cfg_attr(feature = "graphql", graphql(skip))]
     pub pnl_history: Vec<(u64, String)>,
     /// Volume for the period
     pub vlm: String,
@@ -2256,12 +2960,25 @@ pub struct VaultPortfolio {
 /// State of a user as a vault follower.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct VaultFollowerState {
     /// User's equity in the vault
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "vault_equity", into = "scalar::Decimal"))
+    )]
     pub vault_equity: Decimal,
     /// User's PnL
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "pnl", into = "scalar::Decimal"))
+    )]
     pub pnl: Decimal,
     /// User's all-time PnL
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "all_time_pnl", into = "scalar::Decimal"))
+    )]
     pub all_time_pnl: Decimal,
     /// Number of days following
     pub days_following: u64,
@@ -2274,14 +2991,29 @@ pub struct VaultFollowerState {
 /// Information about a vault follower.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct VaultFollower {
     /// Follower's identity (address or special role like Leader)
+    #[cfg_attr(feature = "graphql", graphql(skip))]
     pub user: VaultFollowerUser,
     /// Follower's equity in the vault
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "vault_equity", into = "scalar::Decimal"))
+    )]
     pub vault_equity: Decimal,
     /// Follower's PnL
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "pnl", into = "scalar::Decimal"))
+    )]
     pub pnl: Decimal,
     /// Follower's all-time PnL
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "all_time_pnl", into = "scalar::Decimal"))
+    )]
     pub all_time_pnl: Decimal,
     /// Number of days following
     pub days_following: u64,
@@ -2356,12 +3088,25 @@ impl<'de> serde::Deserialize<'de> for VaultFollowerUser {
 /// <https://hyperliquid.gitbook.io/hyperliquid-docs/for-developers/api/info-endpoint#retrieve-a-users-subaccounts>
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+#[cfg_attr(feature = "graphql", graphql(complex))]
 pub struct SubAccount {
     /// Human-readable name of the subaccount
     pub name: String,
     /// Address of the subaccount
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "sub_account_user", into = "scalar::Address")
+        )
+    )]
     pub sub_account_user: Address,
     /// Address of the master account
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "master", into = "scalar::Address"))
+    )]
     pub master: Address,
     /// Clearinghouse state for perpetuals trading
     pub clearinghouse_state: ClearinghouseState,
@@ -2369,11 +3114,54 @@ pub struct SubAccount {
     pub spot_state: SpotState,
 }
 
+impl SubAccount {
+    /// Builds a transfer of `amount` USD from the master account to this subaccount.
+    ///
+    /// See [`SubAccountTransfer::deposit`] for the checks.
+    pub fn deposit(&self, amount: Decimal) -> Result<SubAccountTransfer, crate::hypercore::Error> {
+        SubAccountTransfer::deposit(self.sub_account_user, amount)
+    }
+
+    /// Builds a transfer of `amount` USD from this subaccount to the master account.
+    pub fn withdraw(&self, amount: Decimal) -> Result<SubAccountTransfer, crate::hypercore::Error> {
+        SubAccountTransfer::withdraw(self.sub_account_user, amount)
+    }
+
+    /// Builds a transfer of `amount` of `token` from the master account to this subaccount.
+    ///
+    /// See [`SubAccountSpotTransfer::deposit`] for the checks.
+    pub fn spot_deposit(
+        &self,
+        token: SendToken,
+        amount: Decimal,
+    ) -> Result<SubAccountSpotTransfer, crate::hypercore::Error> {
+        SubAccountSpotTransfer::deposit(self.sub_account_user, token, amount)
+    }
+
+    /// Builds a transfer of `amount` of `token` from this subaccount to the master account.
+    pub fn spot_withdraw(
+        &self,
+        token: SendToken,
+        amount: Decimal,
+    ) -> Result<SubAccountSpotTransfer, crate::hypercore::Error> {
+        SubAccountSpotTransfer::withdraw(self.sub_account_user, token, amount)
+    }
+
+    /// Builds a rename of this subaccount to `name`.
+    pub fn rename(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<SubAccountModify, crate::hypercore::Error> {
+        SubAccountModify::new(self.sub_account_user, name)
+    }
+}
+
 /// Spot trading state for an account.
 ///
 /// Contains the spot balances for an account.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct SpotState {
     /// List of spot balances
     pub balances: Vec<UserBalance>,
@@ -2499,52 +3287,565 @@ pub struct CandleSnapshotRequest {
 }
 
 // ========================================================
-// PRIVATE TYPES
+// TWAP TYPES
 // ========================================================
 
-/// Info endpoint request types.
+/// State of a TWAP order.
 ///
-/// Used for querying various types of information from the API.
-#[derive(Serialize)]
+/// Part of the [`TwapHistory`] entries received with
+/// [`Subscription::UserTwapHistory`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
-#[serde(tag = "type")]
-pub(super) enum InfoRequest {
-    Meta {
-        #[serde(skip_serializing_if = "Option::is_none")]
-        dex: Option<String>,
-    },
-    SpotMeta,
-    PerpDexs,
-    FrontendOpenOrders {
-        user: Address,
-    },
-    HistoricalOrders {
-        user: Address,
-    },
-    UserFills {
-        user: Address,
+pub struct TwapState {
+    /// Market symbol
+    pub coin: String,
+    /// User running the TWAP
+    pub user: Address,
+    /// Order side
+    pub side: Side,
+    /// Total size
+    pub sz: Decimal,
+    /// Size executed so far
+    pub executed_sz: Decimal,
+    /// Notional executed so far
+    pub executed_ntl: Decimal,
+    /// Duration in minutes
+    pub minutes: u32,
+    /// Whether slices only reduce the position
+    pub reduce_only: bool,
+    /// Whether slice sizes are randomized
+    pub randomize: bool,
+    /// Start time in milliseconds
+    pub timestamp: u64,
+}
+
+impl TwapState {
+    /// Returns the size left to execute.
+    #[must_use]
+    pub fn remaining_sz(&self) -> Decimal {
+        (self.sz - self.executed_sz).max(Decimal::ZERO)
+    }
+
+    /// Returns the executed fraction of the size, from 0 to 1.
+    #[must_use]
+    pub fn progress(&self) -> Decimal {
+        if self.sz.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.executed_sz / self.sz
+    }
+
+    /// Returns the average execution price, or `None` if nothing was executed.
+    #[must_use]
+    pub fn avg_px(&self) -> Option<Decimal> {
+        (!self.executed_sz.is_zero()).then(|| self.executed_ntl / self.executed_sz)
+    }
+
+    /// Returns the time the TWAP is scheduled to end, in milliseconds.
+    #[must_use]
+    pub fn end_time(&self) -> u64 {
+        self.timestamp + u64::from(self.minutes) * 60_000
+    }
+}
+
+/// Lifecycle event of a TWAP order.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapHistory {
+    /// Event time in seconds
+    pub time: u64,
+    /// State of the TWAP at the event
+    pub state: TwapState,
+    /// Status of the TWAP
+    pub status: TwapHistoryStatus,
+}
+
+/// Status of a [`TwapHistory`] event.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapHistoryStatus {
+    /// Status
+    pub status: TwapStatus,
+    /// Details, e.g. the error message
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Lifecycle status of a TWAP order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TwapStatus {
+    /// The TWAP started
+    Activated,
+    /// The TWAP was cancelled
+    Terminated,
+    /// The TWAP executed its full duration
+    Finished,
+    /// The TWAP stopped on an error
+    Error,
+}
+
+/// Fill of a TWAP slice.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapSliceFill {
+    /// Fill details
+    pub fill: Fill,
+    /// TWAP the slice belongs to
+    pub twap_id: u64,
+}
+
+// ========================================================
+// ASSET CONTEXT TYPES
+// ========================================================
+
+/// Live statistics of a perpetual market.
+///
+/// Part of a [`MarketSnapshot`](crate::hypercore::MarketSnapshot).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetCtx {
+    /// Current hourly funding rate
+    pub funding: Decimal,
+    /// Open interest, in contracts
+    pub open_interest: Decimal,
+    /// Oracle price
+    pub oracle_px: Decimal,
+    /// Mark price
+    pub mark_px: Decimal,
+    /// Mid price, if the book has both sides
+    #[serde(default)]
+    pub mid_px: Option<Decimal>,
+    /// Premium of the mark price over the oracle price
+    #[serde(default)]
+    pub premium: Option<Decimal>,
+    /// Impact bid and ask prices
+    #[serde(default)]
+    pub impact_pxs: Option<[Decimal; 2]>,
+    /// Mark price 24 hours ago
+    pub prev_day_px: Decimal,
+    /// 24h notional volume
+    pub day_ntl_vlm: Decimal,
+    /// 24h volume, in contracts
+    pub day_base_vlm: Decimal,
+}
+
+impl AssetCtx {
+    /// Returns the open interest in quote currency, at the mark price.
+    #[must_use]
+    pub fn open_interest_ntl(&self) -> Decimal {
+        self.open_interest * self.mark_px
+    }
+
+    /// Returns the 24h price change as a fraction, e.g. `0.05` for +5%.
+    ///
+    /// Returns `None` if the previous day price is zero.
+    #[must_use]
+    pub fn day_change(&self) -> Option<Decimal> {
+        day_change(self.prev_day_px, self.mark_px)
+    }
+}
+
+/// Live statistics of a spot market.
+///
+/// Part of a [`SpotMarketSnapshot`](crate::hypercore::SpotMarketSnapshot).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpotAssetCtx {
+    /// Market name (e.g., "PURR/USDC", "@107")
+    pub coin: String,
+    /// Mark price
+    pub mark_px: Decimal,
+    /// Mid price, if the book has both sides
+    #[serde(default)]
+    pub mid_px: Option<Decimal>,
+    /// Mark price 24 hours ago
+    pub prev_day_px: Decimal,
+    /// 24h notional volume
+    pub day_ntl_vlm: Decimal,
+    /// 24h volume, in base tokens
+    pub day_base_vlm: Decimal,
+    /// Circulating supply of the base token
+    pub circulating_supply: Decimal,
+    /// Total supply of the base token
+    pub total_supply: Decimal,
+}
+
+impl SpotAssetCtx {
+    /// Returns the 24h price change as a fraction, e.g. `0.05` for +5%.
+    ///
+    /// Returns `None` if the previous day price is zero.
+    #[must_use]
+    pub fn day_change(&self) -> Option<Decimal> {
+        day_change(self.prev_day_px, self.mark_px)
+    }
+}
+
+fn day_change(prev_day_px: Decimal, px: Decimal) -> Option<Decimal> {
+    (!prev_day_px.is_zero()).then(|| (px - prev_day_px) / prev_day_px)
+}
+
+// ========================================================
+// ACCOUNT INFO TYPES
+// ========================================================
+
+/// An open order as shown in the frontend, with its trigger and TP/SL details.
+///
+/// Returned by [`HttpClient::frontend_open_orders`](crate::hypercore::HttpClient::frontend_open_orders).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontendOrder {
+    /// Timestamp in milliseconds
+    pub timestamp: u64,
+    /// Market symbol
+    pub coin: String,
+    /// Order side
+    pub side: Side,
+    /// Limit price
+    pub limit_px: Decimal,
+    /// Remaining size
+    pub sz: Decimal,
+    /// Order ID
+    pub oid: u64,
+    /// Original size
+    pub orig_sz: Decimal,
+    /// Client order ID
+    #[serde(default)]
+    pub cloid: Option<B128>,
+    /// Order type as displayed, e.g. "Limit", "Stop Market" or "Take Profit Limit"
+    pub order_type: String,
+    /// Time in force, for limit orders
+    #[serde(default)]
+    pub tif: Option<TimeInForce>,
+    /// Whether the order only reduces a position
+    pub reduce_only: bool,
+    /// Whether the order is a trigger order
+    pub is_trigger: bool,
+    /// Trigger price, zero for non-trigger orders
+    pub trigger_px: Decimal,
+    /// Trigger condition as displayed, e.g. "Price above 100" or "N/A"
+    pub trigger_condition: String,
+    /// Whether the order is a TP/SL of the whole position
+    pub is_position_tpsl: bool,
+    /// TP/SL orders attached to this order
+    #[serde(default)]
+    pub children: Vec<FrontendOrder>,
+}
+
+/// A funding payment of a user.
+///
+/// Returned by [`HttpClient::user_funding`](crate::hypercore::HttpClient::user_funding).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingPayment {
+    /// Timestamp in milliseconds
+    pub time: u64,
+    /// Transaction hash
+    pub hash: String,
+    /// Payment details
+    pub delta: FundingDelta,
+}
+
+/// Details of a [`FundingPayment`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingDelta {
+    /// Market symbol
+    pub coin: String,
+    /// USDC paid (negative) or received (positive)
+    pub usdc: Decimal,
+    /// Signed position size at the time of payment
+    pub szi: Decimal,
+    /// Funding rate applied
+    pub funding_rate: Decimal,
+    /// Number of samples the rate was computed from
+    #[serde(default)]
+    pub n_samples: Option<u64>,
+}
+
+/// A non-funding ledger update of a user: deposits, withdrawals, transfers and vault
+/// operations.
+///
+/// Returned by [`HttpClient::user_non_funding_ledger_updates`](crate::hypercore::HttpClient::user_non_funding_ledger_updates).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerUpdate {
+    /// Timestamp in milliseconds
+    pub time: u64,
+    /// Transaction hash
+    pub hash: String,
+    /// Update details
+    pub delta: LedgerDelta,
+}
+
+/// Details of a [`LedgerUpdate`].
+///
+/// Update types without a variant deserialize as [`LedgerDelta::Other`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum LedgerDelta {
+    /// USDC deposit from Arbitrum
+    Deposit { usdc: Decimal },
+    /// USDC withdrawal to Arbitrum
+    Withdraw {
+        usdc: Decimal,
+        nonce: u64,
+        fee: Decimal,
     },
-    OrderStatus {
+    /// USDC transfer between users
+    InternalTransfer {
+        usdc: Decimal,
         user: Address,
-        #[serde(with = "either::serde_untagged")]
-        oid: OidOrCloid,
+        destination: Address,
+        fee: Decimal,
     },
-    SpotClearinghouseState {
+    /// USDC transfer between a master account and a subaccount
+    SubAccountTransfer {
+        usdc: Decimal,
         user: Address,
+        destination: Address,
     },
-    ClearinghouseState {
+    /// Spot token transfer between users
+    SpotTransfer {
+        token: String,
+        amount: Decimal,
+        usdc_value: Decimal,
         user: Address,
+        destination: Address,
+        fee: Decimal,
     },
-    AllMids,
-    CandleSnapshot {
-        req: CandleSnapshotRequest,
+    /// USDC transfer between the perp and spot balances
+    AccountClassTransfer { usdc: Decimal, to_perp: bool },
+    /// Vault creation
+    VaultCreate { vault: Address, usdc: Decimal },
+    /// Deposit into a vault
+    VaultDeposit { vault: Address, usdc: Decimal },
+    /// Withdrawal from a vault
+    VaultWithdraw {
+        vault: Address,
+        user: Address,
+        requested_usd: Decimal,
+        net_withdrawn_usd: Decimal,
     },
-    UserToMultiSigSigners {
+    /// Distribution from a vault to its followers
+    VaultDistribution { vault: Address, usdc: Decimal },
+    /// Any other update type
+    #[serde(other)]
+    Other,
+}
+
+/// Fee rates and recent volume of a user.
+///
+/// Returned by [`HttpClient::user_fees`](crate::hypercore::HttpClient::user_fees). Rates
+/// are fractions, e.g. `0.00045` for 4.5 bps, and already include the user's discounts.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserFees {
+    /// Daily volume of the user and the exchange, most recent last
+    pub daily_user_vlm: Vec<DailyVolume>,
+    /// Perp taker rate
+    pub user_cross_rate: Decimal,
+    /// Perp maker rate
+    pub user_add_rate: Decimal,
+    /// Spot taker rate
+    pub user_spot_cross_rate: Decimal,
+    /// Spot maker rate
+    pub user_spot_add_rate: Decimal,
+    /// Active referral discount
+    pub active_referral_discount: Decimal,
+    /// Active staking discount, if any
+    #[serde(default)]
+    pub active_staking_discount: Option<StakingDiscount>,
+}
+
+/// Daily volume entry of [`UserFees`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyVolume {
+    /// Date, e.g. "2025-05-23"
+    pub date: String,
+    /// Taker volume of the user
+    pub user_cross: Decimal,
+    /// Maker volume of the user
+    pub user_add: Decimal,
+    /// Volume of the exchange
+    pub exchange: Decimal,
+}
+
+/// Staking discount of [`UserFees`].
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingDiscount {
+    /// Staked amount in basis points of the HYPE max supply
+    pub bps_of_max_supply: Decimal,
+    /// Discount applied to fees
+    pub discount: Decimal,
+}
+
+/// Address-based request rate limit of a user.
+///
+/// Returned by [`HttpClient::user_rate_limit`](crate::hypercore::HttpClient::user_rate_limit).
+/// Each USDC traded grants one request, on top of an initial buffer.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserRateLimit {
+    /// Cumulative traded volume
+    pub cum_vlm: Decimal,
+    /// Requests used
+    pub n_requests_used: u64,
+    /// Requests allowed
+    pub n_requests_cap: u64,
+    /// Requests bought beyond the cap
+    #[serde(default)]
+    pub n_requests_surplus: u64,
+}
+
+impl UserRateLimit {
+    /// Returns the number of requests left before hitting the cap.
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        (self.n_requests_cap + self.n_requests_surplus).saturating_sub(self.n_requests_used)
+    }
+}
+
+/// Details of a spot token: supply, decimals, prices and deployment.
+///
+/// Returned by [`HttpClient::token_details`](crate::hypercore::HttpClient::token_details).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDetails {
+    /// Token name
+    pub name: String,
+    /// Maximum supply
+    pub max_supply: Decimal,
+    /// Total supply
+    pub total_supply: Decimal,
+    /// Circulating supply
+    pub circulating_supply: Decimal,
+    /// Decimals used for sizes
+    pub sz_decimals: u32,
+    /// Decimals used on-chain
+    pub wei_decimals: u32,
+    /// Mid price, if the token trades
+    #[serde(default)]
+    pub mid_px: Option<Decimal>,
+    /// Mark price
+    pub mark_px: Decimal,
+    /// Price 24 hours ago
+    pub prev_day_px: Decimal,
+    /// Deployer, if the token was not created at genesis
+    #[serde(default)]
+    pub deployer: Option<Address>,
+    /// Gas paid in the deployment auction
+    #[serde(default)]
+    pub deploy_gas: Option<Decimal>,
+    /// Deployment time
+    #[serde(default)]
+    pub deploy_time: Option<String>,
+    /// USDC seeded in the hyperliquidity market
+    pub seeded_usdc: Decimal,
+    /// Future emissions
+    pub future_emissions: Decimal,
+}
+
+// ========================================================
+// INFO REQUESTS
+// ========================================================
+
+/// Info endpoint request types.
+///
+/// Sent with [`HttpClient::info`](crate::hypercore::HttpClient::info), which
+/// deserializes the response into any type. Requests without a variant can be sent as
+/// [`InfoRequest::Other`].
+///
+/// # Example
+///
+/// ```no_run
+/// use hypersdk::hypercore::{self, InfoRequest, L2Book};
+///
+/// # async fn example() -> anyhow::Result<()> {
+/// let client = hypercore::mainnet();
+/// let book: L2Book = client
+///     .info(&InfoRequest::L2Book {
+///         coin: "BTC".into(),
+///         n_sig_figs: Some(3),
+///         mantissa: None,
+///     })
+///     .await?;
+///
+/// let stats: serde_json::Value = client
+///     .info(&InfoRequest::Other(serde_json::json!({ "type": "exchangeStatus" })))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "type")]
+pub enum InfoRequest {
+    /// Perpetual markets metadata of a DEX, the default one if `None`.
+    Meta {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
+    },
+    /// Spot markets and tokens metadata.
+    SpotMeta,
+    /// Perpetual markets metadata of a DEX, the default one if `None`, with their live
+    /// statistics.
+    MetaAndAssetCtxs {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
+    },
+    /// Spot markets and tokens metadata with their live statistics.
+    SpotMetaAndAssetCtxs,
+    /// HIP-3 perpetual DEXes.
+    PerpDexs,
+    /// Open orders of a user, with trigger and TP/SL details.
+    FrontendOpenOrders { user: Address },
+    /// Historical orders of a user.
+    HistoricalOrders { user: Address },
+    /// Fills of a user.
+    UserFills { user: Address },
+    /// Fills of a user within a time range.
+    UserFillsByTime {
         user: Address,
+        #[serde(rename = "startTime")]
+        start_time: u64,
+        #[serde(rename = "endTime", skip_serializing_if = "Option::is_none")]
+        end_time: Option<u64>,
     },
-    ExtraAgents {
+    /// Status of an order, by order ID or client order ID.
+    OrderStatus {
         user: Address,
+        #[serde(with = "either::serde_untagged")]
+        oid: OidOrCloid,
     },
+    /// Spot balances of a user.
+    SpotClearinghouseState { user: Address },
+    /// Perpetual account state of a user.
+    ClearinghouseState { user: Address },
+    /// Mid prices of all markets.
+    AllMids,
+    /// Order book snapshot, aggregated to `n_sig_figs` significant figures (2 to 5).
+    /// `mantissa` (1, 2 or 5) is only allowed with 5 significant figures.
+    L2Book {
+        coin: String,
+        #[serde(rename = "nSigFigs", skip_serializing_if = "Option::is_none")]
+        n_sig_figs: Option<u32>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mantissa: Option<u32>,
+    },
+    /// Candles of a market.
+    CandleSnapshot { req: CandleSnapshotRequest },
+    /// Signers of a multisig user.
+    UserToMultiSigSigners { user: Address },
+    /// API agents of a user.
+    ExtraAgents { user: Address },
+    /// Funding rate history of a market.
     FundingHistory {
         coin: String,
         #[serde(rename = "startTime")]
@@ -2552,6 +3853,40 @@ pub(super) enum InfoRequest {
         #[serde(rename = "endTime", skip_serializing_if = "Option::is_none")]
         end_time: Option<u64>,
     },
+    /// Predicted next funding of every coin on Hyperliquid and other venues.
+    PredictedFundings,
+    /// Perpetual markets of a DEX, the default one if `None`, at their open interest cap.
+    PerpsAtOpenInterestCap {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        dex: Option<String>,
+    },
+    /// Funding payments of a user within a time range.
+    UserFunding {
+        user: Address,
+        #[serde(rename = "startTime")]
+        start_time: u64,
+        #[serde(rename = "endTime", skip_serializing_if = "Option::is_none")]
+        end_time: Option<u64>,
+    },
+    /// Deposits, withdrawals, transfers and vault operations of a user within a time range.
+    UserNonFundingLedgerUpdates {
+        user: Address,
+        #[serde(rename = "startTime")]
+        start_time: u64,
+        #[serde(rename = "endTime", skip_serializing_if = "Option::is_none")]
+        end_time: Option<u64>,
+    },
+    /// Fee rates and volume of a user.
+    UserFees { user: Address },
+    /// Request rate limit of a user.
+    UserRateLimit { user: Address },
+    /// Account value and PnL history of a user.
+    Portfolio { user: Address },
+    /// Details of a spot token.
+    TokenDetails {
+        #[serde(rename = "tokenId")]
+        token_id: B128,
+    },
     /// Retrieve details for a vault.
     VaultDetails {
         #[serde(rename = "vaultAddress")]
@@ -2560,17 +3895,85 @@ pub(super) enum InfoRequest {
         user: Option<Address>,
     },
     /// Retrieve a user's vault deposits.
-    UserVaultEquities {
-        user: Address,
-    },
+    UserVaultEquities { user: Address },
     /// Query a user's role.
-    UserRole {
-        user: Address,
-    },
+    UserRole { user: Address },
     /// Retrieve a user's subaccounts.
-    SubAccounts {
-        user: Address,
-    },
+    SubAccounts { user: Address },
+    /// Any other request, sent as is. It must contain the `type` field.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+impl InfoRequest {
+    /// The `type` of the request, e.g. `clearinghouseState`, or `other` for
+    /// [`InfoRequest::Other`].
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Meta { .. } => "meta",
+            Self::SpotMeta => "spotMeta",
+            Self::MetaAndAssetCtxs { .. } => "metaAndAssetCtxs",
+            Self::SpotMetaAndAssetCtxs => "spotMetaAndAssetCtxs",
+            Self::PerpDexs => "perpDexs",
+            Self::FrontendOpenOrders { .. } => "frontendOpenOrders",
+            Self::HistoricalOrders { .. } => "historicalOrders",
+            Self::UserFills { .. } => "userFills",
+            Self::UserFillsByTime { .. } => "userFillsByTime",
+            Self::OrderStatus { .. } => "orderStatus",
+            Self::SpotClearinghouseState { .. } => "spotClearinghouseState",
+            Self::ClearinghouseState { .. } => "clearinghouseState",
+            Self::AllMids => "allMids",
+            Self::L2Book { .. } => "l2Book",
+            Self::CandleSnapshot { .. } => "candleSnapshot",
+            Self::UserToMultiSigSigners { .. } => "userToMultiSigSigners",
+            Self::ExtraAgents { .. } => "extraAgents",
+            Self::FundingHistory { .. } => "fundingHistory",
+            Self::PredictedFundings => "predictedFundings",
+            Self::PerpsAtOpenInterestCap { .. } => "perpsAtOpenInterestCap",
+            Self::UserFunding { .. } => "userFunding",
+            Self::UserNonFundingLedgerUpdates { .. } => "userNonFundingLedgerUpdates",
+            Self::UserFees { .. } => "userFees",
+            Self::UserRateLimit { .. } => "userRateLimit",
+            Self::Portfolio { .. } => "portfolio",
+            Self::TokenDetails { .. } => "tokenDetails",
+            Self::VaultDetails { .. } => "vaultDetails",
+            Self::UserVaultEquities { .. } => "userVaultEquities",
+            Self::UserRole { .. } => "userRole",
+            Self::SubAccounts { .. } => "subAccounts",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// Base rate limit weight of the request.
+    #[must_use]
+    pub fn weight(&self) -> u32 {
+        match self {
+            Self::AllMids
+            | Self::L2Book { .. }
+            | Self::OrderStatus { .. }
+            | Self::ClearinghouseState { .. }
+            | Self::SpotClearinghouseState { .. } => rate_limit::INFO_LIGHT_WEIGHT,
+            Self::UserRole { .. } => rate_limit::USER_ROLE_WEIGHT,
+            _ => rate_limit::INFO_WEIGHT,
+        }
+    }
+
+    /// Number of returned items costing one extra weight, for requests charged by
+    /// response size.
+    #[must_use]
+    pub fn items_per_weight(&self) -> Option<u32> {
+        match self {
+            Self::HistoricalOrders { .. }
+            | Self::UserFills { .. }
+            | Self::UserFillsByTime { .. }
+            | Self::FundingHistory { .. }
+            | Self::UserFunding { .. }
+            | Self::UserNonFundingLedgerUpdates { .. } => Some(rate_limit::ITEMS_PER_WEIGHT),
+            Self::CandleSnapshot { .. } => Some(rate_limit::CANDLES_PER_WEIGHT),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -2907,4 +4310,234 @@ mod tests {
         // Check timestamp
         assert_eq!(state.time, 1768397010203);
     }
+
+    #[test]
+    fn test_info_request_serialization() {
+        let user = Address::repeat_byte(1);
+        let req = InfoRequest::L2Book {
+            coin: "BTC".into(),
+            n_sig_figs: Some(5),
+            mantissa: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({ "type": "l2Book", "coin": "BTC", "nSigFigs": 5 })
+        );
+        assert_eq!(req.weight(), rate_limit::INFO_LIGHT_WEIGHT);
+
+        let cloid = B128::repeat_byte(0xab);
+        let req = InfoRequest::OrderStatus {
+            user,
+            oid: either::Either::Right(cloid),
+        };
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({ "type": "orderStatus", "user": user, "oid": cloid })
+        );
+
+        let req = InfoRequest::UserNonFundingLedgerUpdates {
+            user,
+            start_time: 1,
+            end_time: None,
+        };
+        assert_eq!(
+            serde_json::to_value(&req).unwrap(),
+            serde_json::json!({ "type": "userNonFundingLedgerUpdates", "user": user, "startTime": 1 })
+        );
+        assert_eq!(req.items_per_weight(), Some(rate_limit::ITEMS_PER_WEIGHT));
+
+        let raw = serde_json::json!({ "type": "exchangeStatus" });
+        let req = InfoRequest::Other(raw.clone());
+        assert_eq!(serde_json::to_value(&req).unwrap(), raw);
+        assert_eq!(req.kind(), "other");
+    }
+
+    #[test]
+    fn test_frontend_order_deserialization() {
+        let text = r#"[{
+            "coin": "BTC",
+            "side": "A",
+            "limitPx": "110000.0",
+            "sz": "0.0",
+            "oid": 101,
+            "timestamp": 1750000000000,
+            "triggerCondition": "N/A",
+            "isTrigger": false,
+            "triggerPx": "0.0",
+            "children": [{
+                "coin": "BTC",
+                "side": "A",
+                "limitPx": "120000.0",
+                "sz": "0.0",
+                "oid": 102,
+                "timestamp": 1750000000000,
+                "triggerCondition": "Price above 118000",
+                "isTrigger": true,
+                "triggerPx": "118000.0",
+                "children": [],
+                "isPositionTpsl": false,
+                "reduceOnly": true,
+                "orderType": "Take Profit Limit",
+                "origSz": "0.0",
+                "tif": null,
+                "cloid": null
+            }],
+            "isPositionTpsl": false,
+            "reduceOnly": false,
+            "orderType": "Limit",
+            "origSz": "0.01",
+            "tif": "Gtc",
+            "cloid": "0x00000000000000000000000000000001"
+        }]"#;
+        let orders: Vec<FrontendOrder> = serde_json::from_str(text).unwrap();
+        assert_eq!(orders[0].tif, Some(TimeInForce::Gtc));
+        assert!(orders[0].cloid.is_some());
+        let tp = &orders[0].children[0];
+        assert!(tp.is_trigger);
+        assert_eq!(tp.order_type, "Take Profit Limit");
+        assert_eq!(tp.trigger_px.to_string(), "118000.0");
+    }
+
+    #[test]
+    fn test_ledger_update_deserialization() {
+        let text = r#"[
+            {"time": 1, "hash": "0x01", "delta": {"type": "deposit", "usdc": "100.0"}},
+            {"time": 2, "hash": "0x02", "delta": {"type": "accountClassTransfer", "usdc": "5.0", "toPerp": false}},
+            {"time": 3, "hash": "0x03", "delta": {"type": "vaultDeposit", "vault": "0xdfc24b077bc1425ad1dea75bcb6f8158e10df303", "usdc": "10.0"}},
+            {"time": 4, "hash": "0x04", "delta": {"type": "cStakingTransfer", "token": "HYPE", "amount": "1.0", "isDeposit": true}}
+        ]"#;
+        let updates: Vec<LedgerUpdate> = serde_json::from_str(text).unwrap();
+        assert!(matches!(updates[0].delta, LedgerDelta::Deposit { .. }));
+        assert!(matches!(
+            updates[1].delta,
+            LedgerDelta::AccountClassTransfer { to_perp: false, .. }
+        ));
+        assert!(matches!(updates[2].delta, LedgerDelta::VaultDeposit { .. }));
+        assert!(matches!(updates[3].delta, LedgerDelta::Other));
+    }
+
+    #[test]
+    fn test_user_fees_and_rate_limit_deserialization() {
+        let text = r#"{
+            "dailyUserVlm": [{"date": "2025-05-23", "userCross": "0.0", "userAdd": "12.5", "exchange": "2852367.0"}],
+            "feeSchedule": {"cross": "0.00045", "add": "0.00015"},
+            "userCrossRate": "0.000315",
+            "userAddRate": "0.000105",
+            "userSpotCrossRate": "0.0007",
+            "userSpotAddRate": "0.0004",
+            "activeReferralDiscount": "0.0",
+            "trial": null,
+            "activeStakingDiscount": {"bpsOfMaxSupply": "0.0", "discount": "0.0"}
+        }"#;
+        let fees: UserFees = serde_json::from_str(text).unwrap();
+        assert_eq!(fees.user_cross_rate.to_string(), "0.000315");
+        assert_eq!(fees.daily_user_vlm.len(), 1);
+
+        let text =
+            r#"{"cumVlm": "2854574.593578", "nRequestsUsed": 2890, "nRequestsCap": 2864574}"#;
+        let limit: UserRateLimit = serde_json::from_str(text).unwrap();
+        assert_eq!(limit.remaining(), 2864574 - 2890);
+    }
+
+    #[test]
+    fn test_predicted_fundings() {
+        let text = r#"[
+            ["AVAX", [
+                ["BinPerp", {"fundingRate": "0.0001", "nextFundingTime": 1733961600000, "fundingIntervalHours": 8}],
+                ["HlPerp", {"fundingRate": "0.0000125", "nextFundingTime": 1733958000000, "fundingIntervalHours": 1}],
+                ["BybitPerp", {"fundingRate": "0.0001", "nextFundingTime": 1733961600000, "fundingIntervalHours": 8}]
+            ]],
+            ["BTC", [
+                ["BinPerp", {"fundingRate": "0.0001", "nextFundingTime": 1733961600000, "fundingIntervalHours": 8}],
+                ["HlPerp", {"fundingRate": "0.00005", "nextFundingTime": 1733958000000, "fundingIntervalHours": 1}],
+                ["BybitPerp", null]
+            ]],
+            ["PURR", [
+                ["BinPerp", null],
+                ["HlPerp", {"fundingRate": "0.0000125", "nextFundingTime": 1733958000000, "fundingIntervalHours": 1}]
+            ]]
+        ]"#;
+        let fundings: Vec<PredictedFundings> = serde_json::from_str(text).unwrap();
+        assert_eq!(fundings[1].venues.len(), 2);
+        assert!(fundings[1].venue("BybitPerp").is_none());
+        assert_eq!(
+            fundings[0].hyperliquid().unwrap().annualized_rate(),
+            rust_decimal::dec!(0.1095)
+        );
+
+        let spreads = FundingSpread::rank(&fundings);
+        assert_eq!(spreads.len(), 2);
+        assert_eq!(spreads[0].coin, "BTC");
+        assert_eq!(spreads[0].long_venue, "BinPerp");
+        assert_eq!(spreads[0].short_venue, HYPERLIQUID_VENUE);
+        assert_eq!(spreads[0].spread, rust_decimal::dec!(0.3285));
+        assert_eq!(spreads[1].coin, "AVAX");
+        assert_eq!(spreads[1].spread, rust_decimal::dec!(0.0));
+    }
+
+    #[test]
+    fn test_incoming_twap() {
+        let text = r#"{
+            "channel": "userTwapHistory",
+            "data": {
+                "user": "0x0000000000000000000000000000000000000001",
+                "isSnapshot": true,
+                "history": [{
+                    "time": 1700000000,
+                    "state": {
+                        "coin": "ETH",
+                        "user": "0x0000000000000000000000000000000000000001",
+                        "side": "B",
+                        "sz": "10.0",
+                        "executedSz": "4.0",
+                        "executedNtl": "8000.0",
+                        "minutes": 60,
+                        "reduceOnly": false,
+                        "randomize": true,
+                        "timestamp": 1700000000000
+                    },
+                    "status": {"status": "activated"}
+                }]
+            }
+        }"#;
+        let Incoming::UserTwapHistory {
+            history,
+            is_snapshot,
+            ..
+        } = serde_json::from_str(text).unwrap()
+        else {
+            panic!("expected userTwapHistory");
+        };
+        assert_eq!(is_snapshot, Some(true));
+        let state = &history[0].state;
+        assert_eq!(history[0].status.status, TwapStatus::Activated);
+        assert_eq!(state.remaining_sz(), rust_decimal::dec!(6));
+        assert_eq!(state.progress(), rust_decimal::dec!(0.4));
+        assert_eq!(state.avg_px(), Some(rust_decimal::dec!(2000)));
+        assert_eq!(state.end_time(), 1700003600000);
+
+        let text = r#"{
+            "channel": "userTwapSliceFills",
+            "data": {
+                "user": "0x0000000000000000000000000000000000000001",
+                "twapSliceFills": [{
+                    "fill": {
+                        "coin": "ETH", "px": "2000.0", "sz": "0.5", "side": "B", "time": 1700000030000,
+                        "startPosition": "0.0", "dir": "Open Long", "closedPnl": "0.0", "hash": "0x00",
+                        "oid": 1, "crossed": true, "fee": "0.35", "tid": 2, "feeToken": "USDC"
+                    },
+                    "twapId": 3
+                }]
+            }
+        }"#;
+        let Incoming::UserTwapSliceFills {
+            fills, is_snapshot, ..
+        } = serde_json::from_str(text).unwrap()
+        else {
+            panic!("expected userTwapSliceFills");
+        };
+        assert_eq!(is_snapshot, None);
+        assert_eq!(fills[0].twap_id, 3);
+        assert_eq!(fills[0].fill.sz, rust_decimal::dec!(0.5));
+    }
 }
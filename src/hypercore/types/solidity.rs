@@ -18,6 +18,13 @@ sol! {
         uint64 time;
     }
 
+    struct Withdraw {
+        string hyperliquidChain;
+        string destination;
+        string amount;
+        uint64 time;
+    }
+
     struct SpotSend {
         string hyperliquidChain;
         string destination;
@@ -74,6 +81,15 @@ pub mod multisig {
             uint64 time;
         }
 
+        struct Withdraw {
+            string hyperliquidChain;
+            address payloadMultiSigUser;
+            address outerSigner;
+            string destination;
+            string amount;
+            uint64 time;
+        }
+
         struct SpotSend {
             string hyperliquidChain;
             address payloadMultiSigUser;
@@ -5,6 +5,9 @@
 //! [`Id`], [`Market`], and [`MarketParams`] types are created to avoid
 //! the redundant definitions of such types.
 
+// Generated contract bindings mirror the ABI signatures.
+#![allow(clippy::too_many_arguments)]
+
 use alloy::sol;
 
 macro_rules! transmute_this {
@@ -57,8 +57,9 @@
 //! ```
 
 use alloy::{
-    primitives::{Address, FixedBytes, U256},
+    primitives::{Address, FixedBytes, U256, address, keccak256},
     providers::Provider,
+    sol_types::SolValue,
     transports::TransportError,
 };
 use num_traits::{FromPrimitive, NumOps, One, ToPrimitive};
@@ -80,6 +81,19 @@ pub mod contracts;
 /// A 32-byte unique identifier for a Morpho Blue market.
 pub type MarketId = FixedBytes<32>;
 
+/// Morpho Blue contract on HyperEVM mainnet.
+pub const MORPHO_ADDRESS: Address = address!("0x68e37dE8d93d3496ae143F2E900490f6280C57cD");
+
+impl MarketParams {
+    /// Returns the ID of the market with these parameters.
+    ///
+    /// Morpho identifies a market by the hash of its ABI-encoded parameters.
+    #[must_use]
+    pub fn id(&self) -> MarketId {
+        keccak256(self.abi_encode())
+    }
+}
+
 /// Annual Percentage Yield (APY) for a Morpho market.
 ///
 /// Contains both borrow and supply APY rates for a lending market.
@@ -92,6 +92,8 @@ use crate::hyperevm::{
         IUniswapV3Pool::{self, IUniswapV3PoolInstance},
     },
 };
+#[cfg(feature = "graphql")]
+use crate::hypergraphql::scalar;
 
 /// Standard Uniswap V3 fee tiers (in basis points).
 ///
@@ -239,20 +241,55 @@ pub struct Contracts {
 /// Query user positions: `client.positions(user_address).await?`
 /// Check if position is active with `pos.in_range` field.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
 pub struct Position {
     /// NFT token ID for this position
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "token_id", into = "scalar::U256"))
+    )]
     pub token_id: U256,
     /// First token in the pair
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "token0", into = "scalar::Address"))
+    )]
     pub token0: Address,
     /// Second token in the pair
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "token1", into = "scalar::Address"))
+    )]
     pub token1: Address,
     /// Amount of token0 provided as liquidity
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "token0_provided", into = "scalar::Decimal")
+        )
+    )]
     pub token0_provided: Decimal,
     /// Amount of token1 provided as liquidity
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(
+            skip,
+            derived(owned, name = "token1_provided", into = "scalar::Decimal")
+        )
+    )]
     pub token1_provided: Decimal,
     /// Accumulated fees in token0
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "token0_fees", into = "scalar::Decimal"))
+    )]
     pub token0_fees: Decimal,
     /// Accumulated fees in token1
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "token1_fees", into = "scalar::Decimal"))
+    )]
     pub token1_fees: Decimal,
     /// Whether the position is in range (actively earning fees)
     pub in_range: bool,
@@ -292,6 +329,58 @@ impl Position {
     }
 }
 
+/// Current state of a Uniswap V3 pool.
+///
+/// Returned by [`Client::pool_state`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::SimpleObject))]
+pub struct PoolState {
+    /// Pool contract address
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "address", into = "scalar::Address"))
+    )]
+    pub address: Address,
+    /// First token in the pair
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "token0", into = "scalar::Address"))
+    )]
+    pub token0: Address,
+    /// Second token in the pair
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "token1", into = "scalar::Address"))
+    )]
+    pub token1: Address,
+    /// Decimals of token0
+    pub decimals0: u8,
+    /// Decimals of token1
+    pub decimals1: u8,
+    /// Fee tier in hundredths of a basis point (e.g. `3000` = 0.3%)
+    pub fee: u32,
+    /// Current tick
+    pub tick: i32,
+    /// In-range liquidity
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "liquidity", into = "scalar::U256"))
+    )]
+    pub liquidity: U256,
+    /// Current sqrt price in Q64.96 format
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "sqrt_price_x96", into = "scalar::U256"))
+    )]
+    pub sqrt_price_x96: U256,
+    /// Price of token0 in terms of token1, see [`sqrt_x96_to_price`]
+    #[cfg_attr(
+        feature = "graphql",
+        graphql(skip, derived(owned, name = "price", into = "scalar::Decimal"))
+    )]
+    pub price: Decimal,
+}
+
 /// Client for Uniswap V3 DEX.
 ///
 /// Provides methods for querying pools, positions, and executing swaps.
@@ -580,6 +669,46 @@ where
         ))
     }
 
+    /// Returns the tokens, fee tier, liquidity and price of a pool.
+    pub async fn pool_state(&self, pool_address: Address) -> Result<PoolState> {
+        let pool = self.pool(pool_address);
+
+        let (token0, token1, fee, liquidity, slot0) = self
+            .provider
+            .multicall()
+            .add(pool.token0())
+            .add(pool.token1())
+            .add(pool.fee())
+            .add(pool.liquidity())
+            .add(pool.slot0())
+            .aggregate()
+            .await?;
+
+        let token0_client = ERC20::new(token0, self.provider.clone());
+        let token1_client = ERC20::new(token1, self.provider.clone());
+
+        let (decimals0, decimals1) = self
+            .provider
+            .multicall()
+            .add(token0_client.decimals())
+            .add(token1_client.decimals())
+            .aggregate()
+            .await?;
+
+        Ok(PoolState {
+            address: pool_address,
+            token0,
+            token1,
+            decimals0,
+            decimals1,
+            fee: fee.to(),
+            tick: slot0.tick.as_i32(),
+            liquidity: U256::from(liquidity),
+            sqrt_price_x96: U256::from(slot0.sqrtPriceX96),
+            price: sqrt_x96_to_price(slot0.sqrtPriceX96, decimals0 as u32, decimals1 as u32),
+        })
+    }
+
     /// Get the pool's price in a Decimal approximation.
     pub async fn pool_price_from(&self, pool_address: Address) -> Result<Decimal> {
         let pool = self.pool(pool_address);
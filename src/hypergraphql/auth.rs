@@ -0,0 +1,525 @@
+//! Authentication, roles and per-caller request budgets.
+//!
+//! Without an [`Auth`] the router trusts every caller, which is only suitable for local
+//! use. With one, every GraphQL request must carry a credential:
+//!
+//! - an API key, as `Authorization: Bearer <key>` or `X-Api-Key: <key>`
+//! - an HS256 JWT, as `Authorization: Bearer <jwt>`, whose `sub` claim names the caller
+//!   and `role` claim grants its [`Role`]
+//!
+//! WebSocket clients that cannot set headers send the same values as `Authorization` or
+//! `apiKey` in the `connection_init` payload.
+//!
+//! Each credential resolves to a [`Principal`] added to the request data. Fields that
+//! need more than read access are marked with a [`RoleGuard`]: trading mutations require
+//! [`Role::Trader`], and fund movements and agent approvals require [`Role::Admin`].
+//! Fields accepting an arbitrary exchange action check it with [`Role::for_action`].
+//!
+//! Callers can be given a [`Budget`]: a token bucket refilled at `requests` per
+//! `per-seconds`, charged once per HTTP request or WebSocket connection.
+//!
+//! ```
+//! use std::sync::Arc;
+//! use axum::Extension;
+//! use hypersdk::{hypercore, hypergraphql::{self, auth::{Auth, AuthConfig}}};
+//!
+//! let config: AuthConfig = serde_json::from_str(r#"{
+//!     "keys": [{ "name": "desk-a", "key": "secret", "role": "trader" }]
+//! }"#).unwrap();
+//!
+//! let schema = hypergraphql::build_schema(hypercore::mainnet());
+//! let app = hypergraphql::router(schema).layer(Extension(Arc::new(Auth::new(config))));
+//! ```
+
+use std::{collections::HashMap, fmt, sync::Mutex, time::Duration};
+
+use async_graphql::{Context, Guard, Result};
+use axum::http::{HeaderMap, StatusCode, header::AUTHORIZATION};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use tokio::time::Instant;
+
+use crate::hypercore::types::Action;
+
+/// Access level of a caller. Each role includes the ones before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Queries and subscriptions
+    Reader,
+    /// Placing, modifying and cancelling orders
+    Trader,
+    /// Moving funds, approving agents and server administration
+    Admin,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Reader => "reader",
+            Self::Trader => "trader",
+            Self::Admin => "admin",
+        })
+    }
+}
+
+impl Role {
+    /// Returns the role needed to sign `action` on behalf of a caller.
+    ///
+    /// Order, TWAP and margin actions need [`Role::Trader`]. Transfers, withdrawals,
+    /// vault and subaccount management, agent approvals and account changes need
+    /// [`Role::Admin`].
+    #[must_use]
+    pub fn for_action(action: &Action) -> Self {
+        match action {
+            Action::Order(_)
+            | Action::BatchModify(_)
+            | Action::Cancel(_)
+            | Action::CancelByCloid(_)
+            | Action::ScheduleCancel(_)
+            | Action::TwapOrder(_)
+            | Action::TwapCancel(_)
+            | Action::UpdateLeverage(_)
+            | Action::UpdateIsolatedMargin(_)
+            | Action::TopUpIsolatedOnlyMargin(_)
+            | Action::Noop => Self::Trader,
+            Action::UsdSend(_)
+            | Action::Withdraw(_)
+            | Action::SendAsset(_)
+            | Action::SpotSend(_)
+            | Action::EvmUserModify { .. }
+            | Action::ApproveAgent(_)
+            | Action::ConvertToMultiSigUser(_)
+            | Action::VaultTransfer(_)
+            | Action::CreateVault(_)
+            | Action::VaultModify(_)
+            | Action::VaultDistribute(_)
+            | Action::CreateSubAccount(_)
+            | Action::SubAccountModify(_)
+            | Action::SubAccountTransfer(_)
+            | Action::SubAccountSpotTransfer(_)
+            | Action::MultiSig(_) => Self::Admin,
+        }
+    }
+}
+
+/// An authenticated caller, available as request data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    /// Key name or JWT subject
+    pub name: String,
+    /// Granted role
+    pub role: Role,
+}
+
+/// Request budget of a caller: at most `requests` per `per_seconds`, with bursts up to
+/// `requests`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Budget {
+    /// Number of requests
+    pub requests: u32,
+    /// Period in seconds
+    pub per_seconds: u64,
+}
+
+/// A static API key.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ApiKey {
+    /// Name of the key holder, used in logs and as the budget identity
+    pub name: String,
+    /// Secret sent by the client
+    pub key: String,
+    /// Granted role
+    pub role: Role,
+    /// Request budget, unlimited if omitted
+    #[serde(default)]
+    pub budget: Option<Budget>,
+}
+
+/// HS256 JWT verification settings.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct JwtConfig {
+    /// Shared HMAC secret
+    pub secret: String,
+    /// Required `iss` claim
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// Required `aud` claim
+    #[serde(default)]
+    pub audience: Option<String>,
+    /// Request budget of each subject, unlimited if omitted
+    #[serde(default)]
+    pub budget: Option<Budget>,
+}
+
+/// Accepted credentials.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct AuthConfig {
+    /// Static API keys
+    pub keys: Vec<ApiKey>,
+    /// JWT verification, JWTs are rejected if omitted
+    pub jwt: Option<JwtConfig>,
+}
+
+/// Authentication failure.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum AuthError {
+    /// No credential was sent
+    #[display("missing credentials")]
+    Missing,
+    /// The credential is unknown, expired or malformed
+    #[display("invalid credentials")]
+    Invalid,
+    /// The caller has used up its budget
+    #[display("request budget of {_0} exceeded")]
+    BudgetExceeded(String),
+}
+
+impl AuthError {
+    /// Returns the matching HTTP status.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::Missing | Self::Invalid => StatusCode::UNAUTHORIZED,
+            Self::BudgetExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+/// Claims read from a JWT.
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+    role: Role,
+}
+
+struct Jwt {
+    key: DecodingKey,
+    validation: Validation,
+    budget: Option<Budget>,
+}
+
+/// Token bucket of a caller.
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn full(budget: Budget) -> Self {
+        Self {
+            tokens: f64::from(budget.requests),
+            updated_at: Instant::now(),
+        }
+    }
+
+    /// Refills the bucket and takes a token if one is available.
+    fn try_take(&mut self, budget: Budget) -> bool {
+        let capacity = f64::from(budget.requests);
+        let period = Duration::from_secs(budget.per_seconds.max(1)).as_secs_f64();
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * capacity / period).min(capacity);
+        self.updated_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Verifies credentials and enforces budgets.
+///
+/// Add it to the router as an [`axum::Extension`] wrapped in an `Arc`.
+pub struct Auth {
+    keys: HashMap<String, ApiKey>,
+    jwt: Option<Jwt>,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl Auth {
+    /// Creates an authenticator accepting the configured credentials.
+    pub fn new(config: AuthConfig) -> Self {
+        let keys = config
+            .keys
+            .into_iter()
+            .map(|key| (key.key.clone(), key))
+            .collect();
+        let jwt = config.jwt.map(|jwt| {
+            let mut validation = Validation::new(Algorithm::HS256);
+            validation.set_required_spec_claims(&["exp", "sub"]);
+            if let Some(issuer) = &jwt.issuer {
+                validation.set_issuer(&[issuer]);
+            }
+            match &jwt.audience {
+                Some(audience) => validation.set_audience(&[audience]),
+                None => validation.validate_aud = false,
+            }
+            Jwt {
+                key: DecodingKey::from_secret(jwt.secret.as_bytes()),
+                validation,
+                budget: jwt.budget,
+            }
+        });
+        Self {
+            keys,
+            jwt,
+            buckets: Mutex::default(),
+        }
+    }
+
+    /// Authenticates a request from its `Authorization` or `X-Api-Key` header.
+    pub fn authenticate_headers(&self, headers: &HeaderMap) -> Result<Principal, AuthError> {
+        let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+        self.authenticate(credential(
+            header(AUTHORIZATION.as_str()),
+            header("x-api-key"),
+        ))
+    }
+
+    /// Authenticates a WebSocket connection from its `connection_init` payload.
+    pub fn authenticate_payload(
+        &self,
+        payload: &serde_json::Value,
+    ) -> Result<Principal, AuthError> {
+        let field = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|name| payload.get(*name).and_then(|value| value.as_str()))
+        };
+        self.authenticate(credential(
+            field(&["Authorization", "authorization"]),
+            field(&["apiKey", "X-Api-Key", "x-api-key"]),
+        ))
+    }
+
+    /// Resolves a credential to a principal and charges its budget.
+    pub fn authenticate(&self, credential: Option<&str>) -> Result<Principal, AuthError> {
+        let credential = credential.ok_or(AuthError::Missing)?;
+
+        // API keys and JWT subjects have separate budgets even when their names collide
+        let (principal, budget, bucket) = if let Some(key) = self.keys.get(credential) {
+            let principal = Principal {
+                name: key.name.clone(),
+                role: key.role,
+            };
+            (principal, key.budget, format!("key:{}", key.name))
+        } else if let Some(jwt) = &self.jwt {
+            let claims = jsonwebtoken::decode::<Claims>(credential, &jwt.key, &jwt.validation)
+                .map_err(|_| AuthError::Invalid)?
+                .claims;
+            let bucket = format!("jwt:{}", claims.sub);
+            let principal = Principal {
+                name: claims.sub,
+                role: claims.role,
+            };
+            (principal, jwt.budget, bucket)
+        } else {
+            return Err(AuthError::Invalid);
+        };
+
+        if let Some(budget) = budget {
+            let mut buckets = self.buckets.lock().unwrap();
+            let bucket = buckets
+                .entry(bucket)
+                .or_insert_with(|| Bucket::full(budget));
+            if !bucket.try_take(budget) {
+                return Err(AuthError::BudgetExceeded(principal.name));
+            }
+        }
+
+        Ok(principal)
+    }
+}
+
+/// Extracts the credential from an `Authorization` value or an API key.
+fn credential<'a>(authorization: Option<&'a str>, api_key: Option<&'a str>) -> Option<&'a str> {
+    authorization
+        .map(|value| value.strip_prefix("Bearer ").unwrap_or(value).trim())
+        .or(api_key)
+        .filter(|value| !value.is_empty())
+}
+
+/// Guard requiring a minimum [`Role`].
+///
+/// Passes when the request has no [`Principal`], i.e. the server runs without [`Auth`].
+///
+/// ```ignore
+/// #[graphql(guard = "RoleGuard::new(Role::Trader)")]
+/// async fn place(&self, ctx: &Context<'_>) -> Result<bool> { ... }
+/// ```
+pub struct RoleGuard(Role);
+
+impl RoleGuard {
+    /// Creates a guard requiring `role` or higher.
+    pub fn new(role: Role) -> Self {
+        Self(role)
+    }
+}
+
+impl Guard for RoleGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        match ctx.data_opt::<Principal>() {
+            Some(principal) if principal.role < self.0 => Err(format!(
+                "{} role required, {} has {}",
+                self.0, principal.name, principal.role
+            )
+            .into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::json;
+
+    use super::*;
+
+    fn auth() -> Auth {
+        Auth::new(AuthConfig {
+            keys: vec![ApiKey {
+                name: "desk".into(),
+                key: "k1".into(),
+                role: Role::Trader,
+                budget: Some(Budget {
+                    requests: 2,
+                    per_seconds: 10,
+                }),
+            }],
+            jwt: Some(JwtConfig {
+                secret: "s3cret".into(),
+                issuer: None,
+                audience: None,
+                budget: None,
+            }),
+        })
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_api_key_budget_refills() {
+        let auth = auth();
+        let desk = Principal {
+            name: "desk".into(),
+            role: Role::Trader,
+        };
+
+        assert_eq!(auth.authenticate(Some("k1")), Ok(desk.clone()));
+        assert_eq!(auth.authenticate(Some("k1")), Ok(desk.clone()));
+        assert_eq!(
+            auth.authenticate(Some("k1")),
+            Err(AuthError::BudgetExceeded("desk".into()))
+        );
+
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert_eq!(auth.authenticate(Some("k1")), Ok(desk));
+
+        assert_eq!(auth.authenticate(None), Err(AuthError::Missing));
+        assert_eq!(auth.authenticate(Some("nope")), Err(AuthError::Invalid));
+    }
+
+    #[test]
+    fn test_jwt_and_headers() {
+        let auth = auth();
+        let exp = chrono::Utc::now().timestamp() + 60;
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &json!({ "sub": "bot", "role": "admin", "exp": exp }),
+            &EncodingKey::from_secret(b"s3cret"),
+        )
+        .unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+        let principal = auth.authenticate_headers(&headers).unwrap();
+        assert_eq!(principal.name, "bot");
+        assert_eq!(principal.role, Role::Admin);
+
+        let forged = jsonwebtoken::encode(
+            &Header::default(),
+            &json!({ "sub": "bot", "role": "admin", "exp": exp }),
+            &EncodingKey::from_secret(b"other"),
+        )
+        .unwrap();
+        assert_eq!(auth.authenticate(Some(&forged)), Err(AuthError::Invalid));
+
+        let payload = json!({ "apiKey": "k1" });
+        assert_eq!(auth.authenticate_payload(&payload).unwrap().name, "desk");
+    }
+
+    #[test]
+    fn test_budgets_per_credential_kind() {
+        let auth = Auth::new(AuthConfig {
+            keys: vec![ApiKey {
+                name: "desk".into(),
+                key: "k1".into(),
+                role: Role::Trader,
+                budget: Some(Budget {
+                    requests: 1,
+                    per_seconds: 3600,
+                }),
+            }],
+            jwt: Some(JwtConfig {
+                secret: "s3cret".into(),
+                issuer: None,
+                audience: None,
+                budget: Some(Budget {
+                    requests: 1,
+                    per_seconds: 3600,
+                }),
+            }),
+        });
+        let exp = chrono::Utc::now().timestamp() + 60;
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &json!({ "sub": "desk", "role": "reader", "exp": exp }),
+            &EncodingKey::from_secret(b"s3cret"),
+        )
+        .unwrap();
+
+        // a JWT subject named like an API key does not drain the key's budget
+        assert!(auth.authenticate(Some(&token)).is_ok());
+        assert!(auth.authenticate(Some("k1")).is_ok());
+        assert_eq!(
+            auth.authenticate(Some(&token)),
+            Err(AuthError::BudgetExceeded("desk".into()))
+        );
+    }
+
+    #[test]
+    fn test_role_for_action() {
+        let cancel: Action = serde_json::from_value(json!({
+            "type": "cancel",
+            "cancels": [{ "a": 0, "o": 1 }]
+        }))
+        .unwrap();
+        assert_eq!(Role::for_action(&cancel), Role::Trader);
+
+        let send: Action = serde_json::from_value(json!({
+            "type": "usdSend",
+            "signatureChainId": "0xa4b1",
+            "hyperliquidChain": "Mainnet",
+            "destination": "0x0d1d9635d0640821d15e323ac8adadfa9c111414",
+            "amount": "1",
+            "time": 1
+        }))
+        .unwrap();
+        assert_eq!(Role::for_action(&send), Role::Admin);
+
+        let transfer: Action = serde_json::from_value(json!({
+            "type": "subAccountTransfer",
+            "subAccountUser": "0x0d1d9635d0640821d15e323ac8adadfa9c111414",
+            "isDeposit": false,
+            "usd": 1000000
+        }))
+        .unwrap();
+        assert_eq!(Role::for_action(&transfer), Role::Admin);
+    }
+}
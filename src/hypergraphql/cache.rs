@@ -0,0 +1,178 @@
+//! Market metadata cache.
+//!
+//! Market lists change rarely but are needed by most queries. [`MetaCache`] keeps
+//! them for a configurable TTL so that nested or repeated fields do not call `/info`
+//! again, and can be refreshed manually (e.g. after a listing) with [`MetaCache::refresh`].
+
+use std::{collections::HashMap, future::Future, sync::Arc, time::Duration};
+
+use anyhow::Result;
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::hypercore::{Dex, HttpClient, PerpMarket, SpotMarket, SpotToken};
+
+/// Default time-to-live of cached metadata.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+/// A cached value and the time it was fetched.
+struct Entry<T> {
+    value: Mutex<Option<(Instant, T)>>,
+}
+
+impl<T> Default for Entry<T> {
+    fn default() -> Self {
+        Self {
+            value: Mutex::new(None),
+        }
+    }
+}
+
+impl<T: Clone> Entry<T> {
+    /// Returns the cached value, fetching it if missing or older than `ttl`.
+    ///
+    /// Concurrent callers wait for a single fetch instead of each calling the API.
+    async fn get_or_fetch<F>(&self, ttl: Duration, fetch: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        let mut value = self.value.lock().await;
+        if let Some((fetched_at, value)) = value.as_ref()
+            && fetched_at.elapsed() < ttl
+        {
+            return Ok(value.clone());
+        }
+
+        let fresh = fetch.await?;
+        *value = Some((Instant::now(), fresh.clone()));
+        Ok(fresh)
+    }
+
+    async fn clear(&self) {
+        *self.value.lock().await = None;
+    }
+}
+
+/// TTL cache of perpetual markets, spot markets, spot tokens and perp DEXes.
+///
+/// Registered as schema data by [`schema_builder`](super::schema_builder) with
+/// [`DEFAULT_TTL`]. Register another instance to change the TTL:
+///
+/// ```
+/// use std::time::Duration;
+/// use hypersdk::{hypercore, hypergraphql::{self, MetaCache}};
+///
+/// let client = hypercore::mainnet();
+/// let cache = MetaCache::new(client.clone(), Duration::from_secs(300));
+/// let schema = hypergraphql::schema_builder(client).data(cache).finish();
+/// ```
+pub struct MetaCache {
+    client: HttpClient,
+    ttl: Duration,
+    perp_markets: Entry<Vec<PerpMarket>>,
+    dex_markets: Mutex<HashMap<Dex, Arc<Entry<Vec<PerpMarket>>>>>,
+    spot_markets: Entry<Vec<SpotMarket>>,
+    spot_tokens: Entry<Vec<SpotToken>>,
+    perp_dexs: Entry<Vec<Dex>>,
+}
+
+impl MetaCache {
+    /// Creates an empty cache whose entries expire after `ttl`.
+    pub fn new(client: HttpClient, ttl: Duration) -> Self {
+        Self {
+            client,
+            ttl,
+            perp_markets: Entry::default(),
+            dex_markets: Mutex::default(),
+            spot_markets: Entry::default(),
+            spot_tokens: Entry::default(),
+            perp_dexs: Entry::default(),
+        }
+    }
+
+    /// Returns the time-to-live of cached entries.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Perpetual markets of the default DEX, see [`HttpClient::perps`].
+    pub async fn perp_markets(&self) -> Result<Vec<PerpMarket>> {
+        self.perp_markets
+            .get_or_fetch(self.ttl, self.client.perps())
+            .await
+    }
+
+    /// Perpetual markets of a HIP-3 DEX, see [`HttpClient::perps_from`].
+    pub async fn perp_markets_from(&self, dex: Dex) -> Result<Vec<PerpMarket>> {
+        let entry = self
+            .dex_markets
+            .lock()
+            .await
+            .entry(dex.clone())
+            .or_default()
+            .clone();
+        entry
+            .get_or_fetch(self.ttl, self.client.perps_from(dex))
+            .await
+    }
+
+    /// Spot markets, see [`HttpClient::spot`].
+    pub async fn spot_markets(&self) -> Result<Vec<SpotMarket>> {
+        self.spot_markets
+            .get_or_fetch(self.ttl, self.client.spot())
+            .await
+    }
+
+    /// Spot tokens, see [`HttpClient::spot_tokens`].
+    pub async fn spot_tokens(&self) -> Result<Vec<SpotToken>> {
+        self.spot_tokens
+            .get_or_fetch(self.ttl, self.client.spot_tokens())
+            .await
+    }
+
+    /// HIP-3 perpetual DEXes, see [`HttpClient::perp_dexs`].
+    pub async fn perp_dexs(&self) -> Result<Vec<Dex>> {
+        self.perp_dexs
+            .get_or_fetch(self.ttl, self.client.perp_dexs())
+            .await
+    }
+
+    /// Looks up a HIP-3 DEX by name.
+    pub async fn perp_dex(&self, name: &str) -> Result<Option<Dex>> {
+        let dexs = self.perp_dexs().await?;
+        Ok(dexs.into_iter().find(|dex| dex.name() == name))
+    }
+
+    /// Drops all cached entries, so the next access fetches fresh data.
+    pub async fn refresh(&self) {
+        self.perp_markets.clear().await;
+        self.dex_markets.lock().await.clear();
+        self.spot_markets.clear().await;
+        self.spot_tokens.clear().await;
+        self.perp_dexs.clear().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_entry_expires_after_ttl() {
+        let entry = Entry::default();
+        let fetches = AtomicUsize::new(0);
+        let fetch = || async { Ok(fetches.fetch_add(1, Ordering::Relaxed)) };
+
+        let ttl = Duration::from_secs(10);
+        assert_eq!(entry.get_or_fetch(ttl, fetch()).await.unwrap(), 0);
+        assert_eq!(entry.get_or_fetch(ttl, fetch()).await.unwrap(), 0);
+
+        tokio::time::advance(ttl).await;
+        assert_eq!(entry.get_or_fetch(ttl, fetch()).await.unwrap(), 1);
+
+        entry.clear().await;
+        assert_eq!(entry.get_or_fetch(ttl, fetch()).await.unwrap(), 2);
+        assert_eq!(fetches.load(Ordering::Relaxed), 3);
+    }
+}
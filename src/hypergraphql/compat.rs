@@ -0,0 +1,391 @@
+//! Breaking-change detection between two versions of a schema.
+//!
+//! [`diff`] compares two SDL documents, such as a released `schema.graphql` and the
+//! output of [`Schema::sdl`](async_graphql::Schema::sdl), and classifies every change:
+//!
+//! | Change | Severity |
+//! |---|---|
+//! | Type, field, argument, enum value or union member removed | Breaking |
+//! | Type kind changed, e.g. object to union | Breaking |
+//! | Output type made nullable, or input type made non-null | Breaking |
+//! | Other type change, e.g. `Int` to `String` | Breaking |
+//! | Required argument or input field added | Breaking |
+//! | Type, field, enum value or union member added | Safe |
+//! | Optional argument or input field added | Safe |
+//! | Output type made non-null, or input type made nullable | Safe |
+//!
+//! The schema shipped with this crate is checked against `src/hypergraphql/schema.graphql`
+//! by the tests, so breaking changes fail before a release. Regenerate the file after an
+//! intended change with:
+//!
+//! ```bash
+//! cargo run --bin hypersdk-graphql-server --features graphql -- schema --sdl > src/hypergraphql/schema.graphql
+//! ```
+
+use std::{collections::BTreeMap, fmt};
+
+use async_graphql::{
+    Name, Positioned,
+    parser::{
+        self, parse_schema,
+        types::{
+            BaseType, EnumValueDefinition, FieldDefinition, InputValueDefinition, Type, TypeKind,
+            TypeSystemDefinition,
+        },
+    },
+};
+
+/// Whether a change can break existing clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Operations valid against the old schema may fail or misbehave against the new one
+    Breaking,
+    /// Operations valid against the old schema keep working
+    Safe,
+}
+
+/// A difference between two schemas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    /// Whether the change can break existing clients
+    pub severity: Severity,
+    /// Changed element, e.g. `Query.user` or `Query.user(address:)`
+    pub path: String,
+    /// What changed
+    pub description: String,
+}
+
+impl Change {
+    /// Returns true if the change can break existing clients.
+    pub fn is_breaking(&self) -> bool {
+        self.severity == Severity::Breaking
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Breaking => "BREAKING",
+            Severity::Safe => "safe",
+        };
+        write!(f, "{severity} {}: {}", self.path, self.description)
+    }
+}
+
+/// Compares two SDL documents, returning the changes from `old` to `new`.
+///
+/// Changes to descriptions and directives are ignored.
+///
+/// ```
+/// use hypersdk::hypergraphql::compat;
+///
+/// let old = "type Query { mid(coin: String!): String! }";
+/// let new = "type Query { mid(coin: String!): String }";
+/// let changes = compat::diff(old, new).unwrap();
+/// assert!(changes[0].is_breaking());
+/// assert_eq!(changes[0].path, "Query.mid");
+/// ```
+pub fn diff(old: &str, new: &str) -> parser::Result<Vec<Change>> {
+    let old = parse_schema(old)?;
+    let new = parse_schema(new)?;
+    let old = types(&old.definitions);
+    let new = types(&new.definitions);
+
+    let mut changes = Changes::default();
+    for (name, old_kind) in &old {
+        match new.get(name) {
+            Some(new_kind) => changes.ty(name, old_kind, new_kind),
+            None => changes.breaking(name, "type removed"),
+        }
+    }
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        changes.safe(name, "type added");
+    }
+    Ok(changes.0)
+}
+
+/// Returns the types defined by a document, by name.
+fn types(definitions: &[TypeSystemDefinition]) -> BTreeMap<&str, &TypeKind> {
+    definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            TypeSystemDefinition::Type(ty) => Some((ty.node.name.node.as_str(), &ty.node.kind)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct Changes(Vec<Change>);
+
+impl Changes {
+    fn push(&mut self, severity: Severity, path: &str, description: impl Into<String>) {
+        self.0.push(Change {
+            severity,
+            path: path.to_owned(),
+            description: description.into(),
+        });
+    }
+
+    fn breaking(&mut self, path: &str, description: impl Into<String>) {
+        self.push(Severity::Breaking, path, description);
+    }
+
+    fn safe(&mut self, path: &str, description: impl Into<String>) {
+        self.push(Severity::Safe, path, description);
+    }
+
+    fn ty(&mut self, name: &str, old: &TypeKind, new: &TypeKind) {
+        match (old, new) {
+            (TypeKind::Scalar, TypeKind::Scalar) => {}
+            (TypeKind::Object(old), TypeKind::Object(new)) => {
+                self.members(
+                    name,
+                    "interface",
+                    names(&old.implements),
+                    names(&new.implements),
+                );
+                self.fields(name, &old.fields, &new.fields);
+            }
+            (TypeKind::Interface(old), TypeKind::Interface(new)) => {
+                self.members(
+                    name,
+                    "interface",
+                    names(&old.implements),
+                    names(&new.implements),
+                );
+                self.fields(name, &old.fields, &new.fields);
+            }
+            (TypeKind::Union(old), TypeKind::Union(new)) => {
+                self.members(name, "member", names(&old.members), names(&new.members));
+            }
+            (TypeKind::Enum(old), TypeKind::Enum(new)) => {
+                self.members(name, "value", values(&old.values), values(&new.values));
+            }
+            (TypeKind::InputObject(old), TypeKind::InputObject(new)) => {
+                self.inputs(name, "input field", &old.fields, &new.fields);
+            }
+            _ => self.breaking(
+                name,
+                format!("kind changed from {} to {}", kind(old), kind(new)),
+            ),
+        }
+    }
+
+    /// Compares named members: implemented interfaces, union members or enum values.
+    fn members(&mut self, name: &str, what: &str, old: Vec<&str>, new: Vec<&str>) {
+        for member in old.iter().filter(|member| !new.contains(member)) {
+            self.breaking(name, format!("{what} `{member}` removed"));
+        }
+        for member in new.iter().filter(|member| !old.contains(member)) {
+            self.safe(name, format!("{what} `{member}` added"));
+        }
+    }
+
+    fn fields(
+        &mut self,
+        name: &str,
+        old: &[Positioned<FieldDefinition>],
+        new: &[Positioned<FieldDefinition>],
+    ) {
+        for old in old {
+            let field = old.node.name.node.as_str();
+            let path = format!("{name}.{field}");
+            let Some(new) = new.iter().find(|new| new.node.name.node == field) else {
+                self.breaking(&path, "field removed");
+                continue;
+            };
+
+            let (old_ty, new_ty) = (&old.node.ty.node, &new.node.ty.node);
+            if old_ty != new_ty {
+                let description = format!("type changed from `{old_ty}` to `{new_ty}`");
+                if is_safe_output(old_ty, new_ty) {
+                    self.safe(&path, description);
+                } else {
+                    self.breaking(&path, description);
+                }
+            }
+            self.inputs(&path, "argument", &old.node.arguments, &new.node.arguments);
+        }
+        for new in new {
+            let field = new.node.name.node.as_str();
+            if !old.iter().any(|old| old.node.name.node == field) {
+                self.safe(&format!("{name}.{field}"), "field added");
+            }
+        }
+    }
+
+    /// Compares the arguments of a field or the fields of an input object.
+    fn inputs(
+        &mut self,
+        name: &str,
+        what: &str,
+        old: &[Positioned<InputValueDefinition>],
+        new: &[Positioned<InputValueDefinition>],
+    ) {
+        let path = |input: &str| match what {
+            "argument" => format!("{name}({input}:)"),
+            _ => format!("{name}.{input}"),
+        };
+
+        for old in old {
+            let input = old.node.name.node.as_str();
+            let Some(new) = new.iter().find(|new| new.node.name.node == input) else {
+                self.breaking(&path(input), format!("{what} removed"));
+                continue;
+            };
+
+            let (old_ty, new_ty) = (&old.node.ty.node, &new.node.ty.node);
+            if old_ty != new_ty {
+                let description = format!("type changed from `{old_ty}` to `{new_ty}`");
+                if is_safe_input(old_ty, new_ty) {
+                    self.safe(&path(input), description);
+                } else {
+                    self.breaking(&path(input), description);
+                }
+            }
+            let old_default = old.node.default_value.as_ref().map(|value| &value.node);
+            let new_default = new.node.default_value.as_ref().map(|value| &value.node);
+            if old_default != new_default {
+                self.safe(&path(input), "default value changed");
+            }
+        }
+        for new in new {
+            let input = new.node.name.node.as_str();
+            if old.iter().any(|old| old.node.name.node == input) {
+                continue;
+            }
+            if new.node.ty.node.nullable || new.node.default_value.is_some() {
+                self.safe(&path(input), format!("optional {what} added"));
+            } else {
+                self.breaking(&path(input), format!("required {what} added"));
+            }
+        }
+    }
+}
+
+/// Returns true if clients reading `old` can read `new`: the same type, possibly with
+/// fewer nulls.
+fn is_safe_output(old: &Type, new: &Type) -> bool {
+    if !old.nullable && new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old), BaseType::Named(new)) => old == new,
+        (BaseType::List(old), BaseType::List(new)) => is_safe_output(old, new),
+        _ => false,
+    }
+}
+
+/// Returns true if values clients send as `old` are accepted as `new`: the same type,
+/// possibly accepting nulls.
+fn is_safe_input(old: &Type, new: &Type) -> bool {
+    if old.nullable && !new.nullable {
+        return false;
+    }
+    match (&old.base, &new.base) {
+        (BaseType::Named(old), BaseType::Named(new)) => old == new,
+        (BaseType::List(old), BaseType::List(new)) => is_safe_input(old, new),
+        _ => false,
+    }
+}
+
+fn names(names: &[Positioned<Name>]) -> Vec<&str> {
+    names.iter().map(|name| name.node.as_str()).collect()
+}
+
+fn values(values: &[Positioned<EnumValueDefinition>]) -> Vec<&str> {
+    values
+        .iter()
+        .map(|value| value.node.value.node.as_str())
+        .collect()
+}
+
+fn kind(kind: &TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Scalar => "scalar",
+        TypeKind::Object(_) => "object",
+        TypeKind::Interface(_) => "interface",
+        TypeKind::Union(_) => "union",
+        TypeKind::Enum(_) => "enum",
+        TypeKind::InputObject(_) => "input object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hypercore, hypergraphql::build_schema};
+
+    const OLD: &str = r#"
+        type Query {
+            user(address: String!): User
+            mids(dex: String): [String!]!
+            legacy: Int
+        }
+        type User { address: String! role: Role fills(first: Int): [Int!] }
+        enum Role { USER VAULT }
+        input OrderInput { coin: String! size: String! }
+        union Event = User
+    "#;
+
+    const NEW: &str = r#"
+        type Query {
+            user(address: String!, dex: String): User!
+            mids(dex: Int): [String]!
+            added: Int
+        }
+        type User { address: String role: Role fills(first: Int!): [Int!] }
+        enum Role { USER }
+        input OrderInput { coin: String! size: String! reduceOnly: Boolean! }
+        scalar Event
+    "#;
+
+    #[test]
+    fn test_classifies_changes() {
+        let changes: Vec<_> = diff(OLD, NEW)
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "BREAKING Event: kind changed from union to scalar",
+                "BREAKING OrderInput.reduceOnly: required input field added",
+                "safe Query.user: type changed from `User` to `User!`",
+                "safe Query.user(dex:): optional argument added",
+                "BREAKING Query.mids: type changed from `[String!]!` to `[String]!`",
+                "BREAKING Query.mids(dex:): type changed from `String` to `Int`",
+                "BREAKING Query.legacy: field removed",
+                "safe Query.added: field added",
+                "BREAKING Role: value `VAULT` removed",
+                "BREAKING User.address: type changed from `String!` to `String`",
+                "BREAKING User.fills(first:): type changed from `Int` to `Int!`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identical_schemas() {
+        assert!(diff(OLD, OLD).unwrap().is_empty());
+        assert!(diff(OLD, "type Query {").is_err());
+    }
+
+    /// Fails when the schema breaks clients of `schema.graphql`, see the module docs.
+    #[test]
+    fn test_schema_is_backward_compatible() {
+        let sdl = build_schema(hypercore::mainnet()).sdl();
+        let breaking: Vec<_> = diff(include_str!("schema.graphql"), &sdl)
+            .unwrap()
+            .into_iter()
+            .filter(Change::is_breaking)
+            .map(|change| change.to_string())
+            .collect();
+        assert!(
+            breaking.is_empty(),
+            "breaking schema changes:\n{}",
+            breaking.join("\n")
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Structured GraphQL errors for SDK failures.
+//!
+//! Errors returned by the [`HttpClient`](crate::hypercore::HttpClient) surface with
+//! extensions clients can branch on instead of matching messages:
+//!
+//! ```json
+//! {
+//!   "message": "Network error: error sending request",
+//!   "extensions": { "code": "NETWORK_ERROR", "retryable": true }
+//! }
+//! ```
+//!
+//! `code` is [`hypercore::Error::code`] and `retryable` is
+//! [`hypercore::Error::is_retryable`]. Rejected order actions ([`ActionError`]) use the
+//! `ACTION_ERROR` code and list the affected `oids` and/or `cloids`.
+
+use async_graphql::{Error, ErrorExtensions, Result};
+use either::Either;
+
+use crate::hypercore::{self, ActionError, Cloid, OidOrCloid};
+
+/// Code of [`ActionError`]s.
+pub const ACTION_ERROR: &str = "ACTION_ERROR";
+
+impl ErrorExtensions for hypercore::Error {
+    fn extend(&self) -> Error {
+        Error::new(self.to_string()).extend_with(|_, ext| {
+            ext.set("code", self.code());
+            ext.set("retryable", self.is_retryable());
+        })
+    }
+}
+
+impl ErrorExtensions for ActionError<Cloid> {
+    fn extend(&self) -> Error {
+        action_error(self.message(), &[], self.ids())
+    }
+}
+
+impl ErrorExtensions for ActionError<u64> {
+    fn extend(&self) -> Error {
+        action_error(self.message(), self.ids(), &[])
+    }
+}
+
+impl ErrorExtensions for ActionError<OidOrCloid> {
+    fn extend(&self) -> Error {
+        let mut oids = vec![];
+        let mut cloids = vec![];
+        for id in self.ids() {
+            match id {
+                Either::Left(oid) => oids.push(*oid),
+                Either::Right(cloid) => cloids.push(*cloid),
+            }
+        }
+        action_error(self.message(), &oids, &cloids)
+    }
+}
+
+fn action_error(message: &str, oids: &[u64], cloids: &[Cloid]) -> Error {
+    Error::new(message).extend_with(|_, ext| {
+        ext.set("code", ACTION_ERROR);
+        ext.set("retryable", false);
+        if !oids.is_empty() {
+            ext.set("oids", oids.to_vec());
+        }
+        if !cloids.is_empty() {
+            ext.set(
+                "cloids",
+                cloids.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            );
+        }
+    })
+}
+
+/// Converts an SDK error into a GraphQL error with `code` and `retryable` extensions.
+///
+/// Order action failures keep their ids; any other error is classified through
+/// [`hypercore::Error`].
+pub fn upstream(err: anyhow::Error) -> Error {
+    let err = match err.downcast::<ActionError<Cloid>>() {
+        Ok(err) => return err.extend(),
+        Err(err) => err,
+    };
+    let err = match err.downcast::<ActionError<u64>>() {
+        Ok(err) => return err.extend(),
+        Err(err) => err,
+    };
+    match err.downcast::<ActionError<OidOrCloid>>() {
+        Ok(err) => err.extend(),
+        Err(err) => hypercore::Error::from(err).extend(),
+    }
+}
+
+/// Converts the error of an SDK result with [`upstream`].
+pub(crate) trait UpstreamExt<T> {
+    fn upstream(self) -> Result<T>;
+}
+
+impl<T> UpstreamExt<T> for anyhow::Result<T> {
+    fn upstream(self) -> Result<T> {
+        self.map_err(upstream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hypergraphql::build_schema;
+
+    #[test]
+    fn test_extensions() {
+        let err = upstream(hypercore::Error::Timeout.into());
+        let ext = serde_json::to_value(&err.extensions).unwrap();
+        assert_eq!(
+            ext,
+            serde_json::json!({ "code": "TIMEOUT", "retryable": true })
+        );
+
+        let ids = vec![Either::Left(7), Either::Right(Cloid::repeat_byte(1))];
+        let err =
+            upstream(ActionError::<OidOrCloid>::new(ids, "Order was never placed".into()).into());
+        assert_eq!(err.message, "Order was never placed");
+        let ext = serde_json::to_value(&err.extensions).unwrap();
+        assert_eq!(ext["code"], ACTION_ERROR);
+        assert_eq!(ext["oids"], serde_json::json!([7]));
+        assert_eq!(
+            ext["cloids"],
+            serde_json::json!(["0x01010101010101010101010101010101"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolver_errors_carry_code() {
+        // nothing listens on port 1
+        let client = hypercore::mainnet().with_url("http://127.0.0.1:1".parse().unwrap());
+        let res = build_schema(client).execute("{ allMids { coin } }").await;
+        assert_eq!(res.errors.len(), 1);
+        let ext = serde_json::to_value(&res.errors[0].extensions).unwrap();
+        assert_eq!(
+            ext,
+            serde_json::json!({ "code": "NETWORK_ERROR", "retryable": true })
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! HyperEVM data source.
+//!
+//! The `morphoMarket`, `morphoVault`, `uniswapPositions` and `uniswapPool` query fields
+//! read HyperEVM contracts through the [`Evm`] registered as schema data, so a single
+//! endpoint serves both HyperCore and HyperEVM data.
+
+use std::collections::HashMap;
+
+use url::Url;
+
+use crate::{
+    Address,
+    hyperevm::{
+        DEFAULT_RPC_URL, DynProvider, Provider, ProviderBuilder,
+        morpho::{self, MORPHO_ADDRESS},
+        uniswap::{self, Contracts, prjx},
+    },
+};
+
+/// Name of the Uniswap V3 deployment used when a field's `dex` is omitted.
+pub const DEFAULT_DEX: &str = "prjx";
+
+/// HyperEVM provider and the contract deployments queried through it.
+///
+/// Registered by [`schema_builder`](super::schema_builder) for mainnet clients, using
+/// [`DEFAULT_RPC_URL`]. Register another instance to use a different RPC endpoint or
+/// additional Uniswap V3 deployments:
+///
+/// ```
+/// use hypersdk::{hypercore, hypergraphql::{self, Evm}};
+///
+/// let evm = Evm::with_url("http://localhost:8545".parse().unwrap());
+/// let schema = hypergraphql::schema_builder(hypercore::mainnet())
+///     .data(evm)
+///     .finish();
+/// ```
+#[derive(Clone)]
+pub struct Evm {
+    provider: DynProvider,
+    morpho: Address,
+    dexes: HashMap<String, Contracts>,
+}
+
+impl Evm {
+    /// Creates a source reading through the given provider.
+    ///
+    /// Morpho Blue defaults to [`MORPHO_ADDRESS`] and the PRJX deployment is registered
+    /// as [`DEFAULT_DEX`].
+    pub fn new(provider: impl Provider) -> Self {
+        Self {
+            provider: DynProvider::new(provider),
+            morpho: MORPHO_ADDRESS,
+            dexes: HashMap::from([(DEFAULT_DEX.to_owned(), prjx::CONTRACTS)]),
+        }
+    }
+
+    /// Creates a source for the given HTTP RPC endpoint.
+    ///
+    /// No connection is made until the first call.
+    pub fn with_url(url: Url) -> Self {
+        Self::new(ProviderBuilder::new().connect_http(url))
+    }
+
+    /// Creates a source for the public HyperEVM mainnet RPC.
+    pub fn mainnet() -> Self {
+        Self::with_url(DEFAULT_RPC_URL.parse().expect("valid default RPC URL"))
+    }
+
+    /// Sets the Morpho Blue contract used by `morphoMarket` when none is given.
+    #[must_use]
+    pub fn with_morpho(self, morpho: Address) -> Self {
+        Self { morpho, ..self }
+    }
+
+    /// Registers a Uniswap V3 deployment under `name`, replacing any previous one.
+    #[must_use]
+    pub fn with_dex(mut self, name: impl Into<String>, contracts: Contracts) -> Self {
+        self.dexes.insert(name.into(), contracts);
+        self
+    }
+
+    /// Returns the underlying provider.
+    pub fn provider(&self) -> &DynProvider {
+        &self.provider
+    }
+
+    /// Returns the default Morpho Blue contract.
+    pub fn morpho_address(&self) -> Address {
+        self.morpho
+    }
+
+    /// Returns a Morpho Blue client.
+    pub fn morpho(&self) -> morpho::Client<DynProvider> {
+        morpho::Client::new(self.provider.clone())
+    }
+
+    /// Returns a MetaMorpho vault client.
+    pub fn meta_morpho(&self) -> morpho::MetaClient<DynProvider> {
+        morpho::MetaClient::new(self.provider.clone())
+    }
+
+    /// Returns a Uniswap V3 client for a registered deployment.
+    pub fn uniswap(&self, dex: &str) -> Option<uniswap::Client<DynProvider>> {
+        let contracts = self.dexes.get(dex)?;
+        Some(uniswap::Client::new(self.provider.clone(), *contracts))
+    }
+
+    /// Returns the names of the registered Uniswap V3 deployments.
+    pub fn dexes(&self) -> impl Iterator<Item = &str> {
+        self.dexes.keys().map(String::as_str)
+    }
+}
@@ -0,0 +1,147 @@
+//! DataLoaders for per-user lookups.
+//!
+//! Fields such as `User.clearinghouseState` are resolved once per user, so a query over
+//! many users (vault followers, subaccounts) would issue one `/info` call per field and
+//! user. The [`UserLoader`] collects the keys requested while a query executes, drops
+//! duplicates and fetches each remaining key once, with bounded concurrency.
+//!
+//! The info API has no multi-user endpoints, so a batch is still one request per unique
+//! key. Loaders are created per request by the [`Loaders`] extension, so results are
+//! never shared between queries.
+
+use std::{collections::HashMap, future::Future, hash::Hash, sync::Arc};
+
+use async_graphql::{
+    Context, Error, Request, Result, ServerResult,
+    dataloader::{DataLoader, HashMapCache, Loader},
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest},
+};
+use futures::{StreamExt, TryStreamExt, stream};
+
+use super::error::UpstreamExt;
+use crate::{
+    Address,
+    hypercore::{
+        HttpClient,
+        types::{BasicOrder, ClearinghouseState, UserBalance, UserRole},
+    },
+};
+
+/// Maximum number of concurrent `/info` calls per batch.
+const CONCURRENCY: usize = 8;
+
+/// Key of [`HttpClient::clearinghouse_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClearinghouseStateOf(pub Address);
+
+/// Key of [`HttpClient::open_orders`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OpenOrdersOf(pub Address);
+
+/// Key of [`HttpClient::user_balances`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BalancesOf(pub Address);
+
+/// Key of [`HttpClient::user_role`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RoleOf(pub Address);
+
+/// Batches and deduplicates per-user `/info` lookups.
+///
+/// Implements [`Loader`] once per key type, so a single `DataLoader<UserLoader>` serves
+/// all of them.
+pub struct UserLoader {
+    client: HttpClient,
+}
+
+impl UserLoader {
+    /// Creates a loader fetching through the given client.
+    pub fn new(client: HttpClient) -> Self {
+        Self { client }
+    }
+
+    /// Fetches each key with bounded concurrency.
+    async fn fetch<K, V, F, Fut>(&self, keys: &[K], fetch: F) -> Result<HashMap<K, V>>
+    where
+        K: Copy + Eq + Hash,
+        F: Fn(HttpClient, K) -> Fut,
+        Fut: Future<Output = anyhow::Result<V>>,
+    {
+        stream::iter(keys.iter().copied())
+            .map(|key| {
+                let fut = fetch(self.client.clone(), key);
+                async move { Ok::<_, Error>((key, fut.await.upstream()?)) }
+            })
+            .buffer_unordered(CONCURRENCY)
+            .try_collect()
+            .await
+    }
+}
+
+/// Implements [`Loader`] for a key type by calling an [`HttpClient`] method.
+macro_rules! user_loader {
+    ($key:ident, $value:ty, $method:ident) => {
+        impl Loader<$key> for UserLoader {
+            type Value = $value;
+            type Error = Error;
+
+            async fn load(&self, keys: &[$key]) -> Result<HashMap<$key, Self::Value>> {
+                self.fetch(keys, |client, $key(user)| async move {
+                    client.$method(user).await
+                })
+                .await
+            }
+        }
+    };
+}
+
+user_loader!(
+    ClearinghouseStateOf,
+    ClearinghouseState,
+    clearinghouse_state
+);
+user_loader!(OpenOrdersOf, Vec<BasicOrder>, open_orders);
+user_loader!(BalancesOf, Vec<UserBalance>, user_balances);
+user_loader!(RoleOf, UserRole, user_role);
+
+/// Loads a single key through the request's [`UserLoader`].
+pub(crate) async fn load<K>(ctx: &Context<'_>, key: K) -> Result<<UserLoader as Loader<K>>::Value>
+where
+    K: Send + Sync + Hash + Eq + Clone + std::fmt::Debug + 'static,
+    UserLoader: Loader<K, Error = Error>,
+{
+    let loader = ctx.data::<DataLoader<UserLoader, HashMapCache>>()?;
+    loader
+        .load_one(key.clone())
+        .await?
+        .ok_or_else(|| Error::new(format!("no result for {key:?}")))
+}
+
+/// Extension adding a fresh `DataLoader<UserLoader>` to every request.
+///
+/// Added by [`schema_builder`](super::schema_builder).
+pub struct Loaders;
+
+impl ExtensionFactory for Loaders {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(Loaders)
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for Loaders {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let client = ctx.data_unchecked::<HttpClient>().clone();
+        let loader = DataLoader::with_cache(
+            UserLoader::new(client),
+            tokio::spawn,
+            HashMapCache::default(),
+        );
+        next.run(ctx, request.data(loader)).await
+    }
+}
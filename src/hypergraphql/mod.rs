@@ -0,0 +1,234 @@
+//! GraphQL API over HyperCore.
+//!
+//! This module exposes the SDK as an [async-graphql](https://docs.rs/async-graphql) schema
+//! and an [axum](https://docs.rs/axum) router, so the same types used for trading can be
+//! served to frontends and dashboards without hand-written REST glue.
+//!
+//! Only available with the `graphql` cargo feature:
+//!
+//! ```toml
+//! [dependencies]
+//! hypersdk = { version = "0.1", features = ["graphql"] }
+//! ```
+//!
+//! # Components
+//!
+//! - [`build_schema`]: Builds the executable schema around an [`HttpClient`]
+//! - [`router`]: axum router serving GraphiQL (`GET /`), the GraphQL endpoint (`POST /`)
+//!   and graphql-ws subscriptions (`/ws`); [`api_router`] serves the same without GraphiQL
+//! - [`schema`]: Root query type
+//! - [`mutation`]: Root mutation type, signing with a server-held [`Agent`]
+//! - [`subscription`]: Root subscription type and the shared WebSocket [`Feed`]
+//! - [`object`]: GraphQL-only objects and computed fields of SDK types
+//! - [`auth`]: API key and JWT authentication, role guards and request budgets
+//! - [`cache`]: TTL cache of market metadata
+//! - [`compat`]: Breaking-change detection between schema versions
+//! - [`error`]: Error codes and retry hints of SDK failures
+//! - [`evm`]: HyperEVM provider behind the Morpho and Uniswap fields
+//! - [`loader`]: DataLoaders batching and deduplicating per-user lookups
+//! - [`multisig`]: Multisig proposals collecting detached signatures
+//! - [`pagination`]: Relay connections over time-windowed histories
+//! - [`scalar`]: Lossless scalars for addresses, decimals, hashes and wei amounts
+//! - [`telemetry`]: Tracing spans and Prometheus metrics
+//! - [`weight`]: Query cost analysis in Hyperliquid request weights
+//!
+//! # Example
+//!
+//! ```no_run
+//! use hypersdk::{hypercore, hypergraphql};
+//! use tokio::net::TcpListener;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let schema = hypergraphql::build_schema(hypercore::mainnet());
+//! let app = hypergraphql::router(schema);
+//!
+//! let listener = TcpListener::bind("127.0.0.1:8000").await?;
+//! axum::serve(listener, app).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod auth;
+pub mod cache;
+pub mod compat;
+pub mod error;
+pub mod evm;
+pub mod loader;
+pub mod multisig;
+pub mod mutation;
+pub mod object;
+pub mod pagination;
+pub mod scalar;
+pub mod schema;
+pub mod subscription;
+pub mod telemetry;
+pub mod weight;
+
+use std::sync::Arc;
+
+use async_graphql::{
+    Data, Schema, SchemaBuilder,
+    http::{ALL_WEBSOCKET_PROTOCOLS, GraphiQLSource},
+};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
+use axum::{
+    Extension, Json, Router,
+    extract::{State, WebSocketUpgrade},
+    http::HeaderMap,
+    response::{Html, IntoResponse, Response},
+    routing::{get, post},
+};
+
+use crate::hypercore::HttpClient;
+use auth::{Auth, AuthError};
+pub use cache::MetaCache;
+pub use evm::Evm;
+pub use loader::Loaders;
+pub use multisig::Proposals;
+pub use mutation::{Agent, Mutation};
+pub use schema::Query;
+pub use subscription::{Feed, Subscription};
+
+/// The HyperCore GraphQL schema type.
+pub type HyperSchema = Schema<Query, Mutation, Subscription>;
+
+/// Returns a schema builder with the HTTP client, a [`MetaCache`], a WebSocket
+/// [`Feed`] and in-memory multisig [`Proposals`] registered as context data, and the
+/// [`Loaders`] extension enabled.
+///
+/// The feed connects to the WebSocket endpoint of the client's base URL on the first
+/// subscription. Mainnet clients also get an [`Evm`] source on the public HyperEVM RPC;
+/// on testnet the HyperEVM fields fail unless one is registered.
+///
+/// Use this instead of [`build_schema`] when you need to attach extensions,
+/// limits or additional context data before finishing the schema, such as the
+/// [`Agent`] that enables mutations:
+///
+/// ```
+/// use hypersdk::{hypercore::{self, PrivateKeySigner}, hypergraphql::{self, Agent}};
+///
+/// let agent = Agent::new(PrivateKeySigner::random());
+/// let schema = hypergraphql::schema_builder(hypercore::mainnet())
+///     .data(agent)
+///     .finish();
+/// ```
+pub fn schema_builder(client: HttpClient) -> SchemaBuilder<Query, Mutation, Subscription> {
+    let feed = Feed::new(client.websocket_url());
+    let cache = MetaCache::new(client.clone(), cache::DEFAULT_TTL);
+    let mut builder = Schema::build(Query, Mutation, Subscription);
+    if client.chain().is_mainnet() {
+        builder = builder.data(Evm::mainnet());
+    }
+    builder
+        .data(client)
+        .data(cache)
+        .data(feed)
+        .data(Proposals::in_memory())
+        .extension(Loaders)
+}
+
+/// Builds the GraphQL schema backed by the given HTTP client.
+///
+/// # Example
+///
+/// ```
+/// use hypersdk::{hypercore, hypergraphql};
+///
+/// let schema = hypergraphql::build_schema(hypercore::mainnet());
+/// assert!(schema.sdl().contains("arbitrumId"));
+/// ```
+pub fn build_schema(client: HttpClient) -> HyperSchema {
+    schema_builder(client).finish()
+}
+
+/// Creates an axum router for the schema.
+///
+/// - `GET /`: GraphiQL playground
+/// - `POST /`: GraphQL endpoint
+/// - `GET /ws`: GraphQL subscriptions over WebSocket (`graphql-ws` and `graphql-transport-ws`)
+///
+/// Add an [`Auth`] extension to require credentials on the GraphQL endpoints, see [`auth`].
+pub fn router(schema: HyperSchema) -> Router {
+    api_router(schema).route("/", get(graphiql))
+}
+
+/// Creates an axum router for the schema without the GraphiQL playground.
+///
+/// - `POST /`: GraphQL endpoint
+/// - `GET /ws`: GraphQL subscriptions over WebSocket (`graphql-ws` and `graphql-transport-ws`)
+pub fn api_router(schema: HyperSchema) -> Router {
+    Router::new()
+        .route("/", post(graphql))
+        .route("/ws", get(subscriptions))
+        .with_state(schema)
+}
+
+/// Executes a request, authenticated from its headers if an [`Auth`] is set.
+async fn graphql(
+    State(schema): State<HyperSchema>,
+    auth: Option<Extension<Arc<Auth>>>,
+    headers: HeaderMap,
+    req: GraphQLRequest,
+) -> Response {
+    let mut req = req.into_inner();
+    if let Some(Extension(auth)) = auth {
+        match auth.authenticate_headers(&headers) {
+            Ok(principal) => req = req.data(principal),
+            Err(err) => return rejection(err),
+        }
+    }
+    GraphQLResponse::from(schema.execute(req).await).into_response()
+}
+
+/// Serves subscriptions, authenticated from the upgrade request headers or else from
+/// the `connection_init` payload if an [`Auth`] is set.
+async fn subscriptions(
+    State(schema): State<HyperSchema>,
+    auth: Option<Extension<Arc<Auth>>>,
+    headers: HeaderMap,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let mut data = Data::default();
+    let mut pending = None;
+    if let Some(Extension(auth)) = auth {
+        match auth.authenticate_headers(&headers) {
+            Ok(principal) => data.insert(principal),
+            Err(AuthError::Missing) => pending = Some(auth),
+            Err(err) => return rejection(err),
+        }
+    }
+
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .on_connection_init(move |payload| async move {
+                    let mut data = Data::default();
+                    if let Some(auth) = pending {
+                        let principal = auth
+                            .authenticate_payload(&payload)
+                            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+                        data.insert(principal);
+                    }
+                    Ok(data)
+                })
+                .serve()
+        })
+}
+
+/// Responds to a failed authentication with a GraphQL error body.
+fn rejection(err: AuthError) -> Response {
+    let body = serde_json::json!({ "errors": [{ "message": err.to_string() }] });
+    (err.status(), Json(body)).into_response()
+}
+
+async fn graphiql() -> impl IntoResponse {
+    Html(
+        GraphiQLSource::build()
+            .endpoint("/")
+            .subscription_endpoint("/ws")
+            .finish(),
+    )
+}
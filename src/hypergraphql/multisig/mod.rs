@@ -0,0 +1,702 @@
+//! Multisig proposals signed by detached signers.
+//!
+//! [`HttpClient::multi_sig`] needs every signer in one process. Proposals let the
+//! authorized users of a multisig account sign from their own wallets instead:
+//!
+//! 1. `propose` records an action and a nonce for a multisig account, with the server
+//!    [`Agent`](super::Agent) as outer signer.
+//! 2. Each authorized user signs the proposal's `typedData` (its signing hash is
+//!    `prehash`) and sends the signature with `signProposal`. Signatures are checked
+//!    with [`MultiSigPayload::recover`] against the account's [`MultiSigConfig`].
+//! 3. Once `threshold` signatures are collected, the agent submits the action.
+//!    Failed submissions can be retried with `submitProposal`.
+//!
+//! The agent must itself be an authorized user of the multisig account, and the
+//! exchange only accepts nonces close to the current time, so proposals should be
+//! signed within a day or so.
+//!
+//! Proposals are kept in a [`ProposalStore`]. [`schema_builder`](super::schema_builder)
+//! registers [`Proposals`] over an in-memory [`SqliteStore`]; register another instance
+//! to persist them:
+//!
+//! ```no_run
+//! use hypersdk::{hypercore, hypergraphql::{self, multisig::{Proposals, SqliteStore}}};
+//!
+//! # fn example() -> anyhow::Result<()> {
+//! let proposals = Proposals::new(SqliteStore::open("proposals.db")?);
+//! let schema = hypergraphql::schema_builder(hypercore::mainnet())
+//!     .data(proposals)
+//!     .finish();
+//! # Ok(())
+//! # }
+//! ```
+
+mod sqlite;
+
+use std::{collections::HashMap, sync::Arc};
+
+use alloy::signers::Signer;
+use async_graphql::{ComplexObject, Context, Enum, Error, Json, Result, SimpleObject};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+pub use self::sqlite::SqliteStore;
+use super::{error::UpstreamExt, scalar};
+use crate::{
+    Address,
+    hypercore::{
+        HttpClient, Signature,
+        api::{OkResponse, Response},
+        types::{
+            Action, MultiSigAction, MultiSigConfig, MultiSigPayload, OrderResponseStatus,
+            TwapCancelStatus, TwapOrderStatus,
+        },
+    },
+};
+
+/// Lifecycle of a [`Proposal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "camelCase")]
+pub enum ProposalStatus {
+    /// Collecting signatures
+    Pending,
+    /// Accepted by the exchange
+    Submitted,
+    /// Rejected by the exchange or not delivered, see `error`
+    Failed,
+}
+
+impl ProposalStatus {
+    /// Returns the status name used by stores.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Submitted => "submitted",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl std::str::FromStr for ProposalStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "submitted" => Ok(Self::Submitted),
+            "failed" => Ok(Self::Failed),
+            _ => anyhow::bail!("unknown proposal status: {s}"),
+        }
+    }
+}
+
+/// Signature of an authorized user on a [`Proposal`].
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+#[serde(rename_all = "camelCase")]
+pub struct Approval {
+    /// Recovered signer
+    #[graphql(skip, derived(owned, name = "signer", into = "scalar::Address"))]
+    pub signer: Address,
+    /// Signature over the proposal's `prehash`
+    #[graphql(skip, derived(owned, name = "signature", into = "scalar::Signature"))]
+    pub signature: Signature,
+    /// Time the signature was accepted, in milliseconds
+    pub signed_at: u64,
+}
+
+/// An action awaiting the signatures of a multisig account's authorized users.
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+#[graphql(complex)]
+#[serde(rename_all = "camelCase")]
+pub struct Proposal {
+    /// Identifier assigned by the store
+    pub id: u64,
+    /// Multisig account executing the action
+    #[graphql(
+        skip,
+        derived(owned, name = "multi_sig_user", into = "scalar::Address")
+    )]
+    pub multi_sig_user: Address,
+    /// Account submitting the action, the server agent
+    #[graphql(skip, derived(owned, name = "outer_signer", into = "scalar::Address"))]
+    pub outer_signer: Address,
+    /// Action to execute
+    #[graphql(skip)]
+    pub action: Action,
+    /// Nonce signed by every signer
+    pub nonce: u64,
+    /// Signatures required, as of the last signature
+    pub threshold: usize,
+    /// Users allowed to sign, as of the last signature
+    #[graphql(
+        skip,
+        derived(
+            owned,
+            name = "authorized_users",
+            into = "Vec<scalar::Address>",
+            with = "scalar::list"
+        )
+    )]
+    pub authorized_users: Vec<Address>,
+    /// Signatures collected so far
+    pub approvals: Vec<Approval>,
+    /// Lifecycle status
+    pub status: ProposalStatus,
+    /// Why the last submission failed
+    pub error: Option<String>,
+    /// Creation time in milliseconds
+    pub created_at: u64,
+}
+
+impl Proposal {
+    /// Returns the payload signed by the authorized users.
+    pub fn payload(&self) -> MultiSigPayload {
+        MultiSigPayload {
+            multi_sig_user: self.multi_sig_user.to_string().to_lowercase(),
+            outer_signer: self.outer_signer.to_string().to_lowercase(),
+            action: Box::new(self.action.clone()),
+        }
+    }
+
+    /// Returns the approvals of users in `authorized_users`.
+    ///
+    /// Signatures of users removed from the account since they signed are kept but
+    /// neither counted nor submitted.
+    pub fn valid_approvals(&self) -> impl Iterator<Item = &Approval> {
+        self.approvals
+            .iter()
+            .filter(|approval| self.authorized_users.contains(&approval.signer))
+    }
+
+    /// Returns whether `threshold` signatures of authorized users were collected.
+    pub fn is_ready(&self) -> bool {
+        self.valid_approvals().count() >= self.threshold
+    }
+
+    /// Returns whether `user` already signed.
+    pub fn has_signed(&self, user: Address) -> bool {
+        self.approvals
+            .iter()
+            .any(|approval| approval.signer == user)
+    }
+}
+
+#[ComplexObject]
+impl Proposal {
+    /// Action to execute, in the exchange's JSON format
+    #[graphql(name = "action")]
+    async fn action_json(&self) -> Json<Action> {
+        Json(self.action.clone())
+    }
+
+    /// Authorized users that have not signed yet
+    async fn missing_signers(&self) -> Vec<scalar::Address> {
+        self.authorized_users
+            .iter()
+            .filter(|user| !self.has_signed(**user))
+            .map(Into::into)
+            .collect()
+    }
+
+    /// Whether enough signatures were collected to submit
+    async fn ready(&self) -> bool {
+        self.is_ready()
+    }
+
+    /// EIP-712 typed data to sign, e.g. with `eth_signTypedData_v4`
+    async fn typed_data(&self, ctx: &Context<'_>) -> Result<Json<serde_json::Value>> {
+        let client = ctx.data::<HttpClient>()?;
+        let typed_data = self.payload().typed_data(self.nonce, client.chain())?;
+        Ok(Json(serde_json::to_value(typed_data)?))
+    }
+
+    /// Signing hash of `typedData`
+    async fn prehash(&self, ctx: &Context<'_>) -> Result<scalar::B256> {
+        let client = ctx.data::<HttpClient>()?;
+        Ok(self.payload().prehash(self.nonce, client.chain())?.into())
+    }
+}
+
+/// Storage of [`Proposal`]s.
+///
+/// Implement it to keep proposals in a shared database when several servers sign for
+/// the same accounts. [`Proposals`] serializes the updates of each proposal made by one
+/// server.
+#[async_graphql::async_trait::async_trait]
+pub trait ProposalStore: Send + Sync {
+    /// Saves a new proposal and returns the identifier assigned to it.
+    ///
+    /// The `id` of `proposal` is ignored.
+    async fn insert(&self, proposal: &Proposal) -> anyhow::Result<u64>;
+
+    /// Returns a proposal by identifier.
+    async fn get(&self, id: u64) -> anyhow::Result<Option<Proposal>>;
+
+    /// Returns the proposals of a multisig account, newest first.
+    async fn list(&self, multi_sig_user: Address) -> anyhow::Result<Vec<Proposal>>;
+
+    /// Replaces a saved proposal.
+    async fn update(&self, proposal: &Proposal) -> anyhow::Result<()>;
+}
+
+/// Proposal workflow over a [`ProposalStore`], registered as schema data.
+pub struct Proposals {
+    store: Arc<dyn ProposalStore>,
+    // one lock per proposal being updated, so that a proposal is submitted once
+    locks: std::sync::Mutex<HashMap<u64, Arc<Mutex<()>>>>,
+}
+
+impl Proposals {
+    /// Creates a workflow keeping proposals in `store`.
+    pub fn new(store: impl ProposalStore + 'static) -> Self {
+        Self {
+            store: Arc::new(store),
+            locks: Default::default(),
+        }
+    }
+
+    /// Creates a workflow keeping proposals in memory, lost on restart.
+    pub fn in_memory() -> Self {
+        Self::new(SqliteStore::in_memory().expect("in-memory SQLite database"))
+    }
+
+    /// Returns the underlying store.
+    pub fn store(&self) -> &dyn ProposalStore {
+        self.store.as_ref()
+    }
+
+    /// Returns a proposal by identifier.
+    pub async fn get(&self, id: u64) -> Result<Option<Proposal>> {
+        self.store.get(id).await.map_err(store_error)
+    }
+
+    /// Returns the proposals of a multisig account, newest first.
+    pub async fn list(&self, multi_sig_user: Address) -> Result<Vec<Proposal>> {
+        self.store.list(multi_sig_user).await.map_err(store_error)
+    }
+
+    /// Records a proposal to execute `action` from `multi_sig_user`, submitted by `lead`.
+    pub async fn propose(
+        &self,
+        client: &HttpClient,
+        lead: Address,
+        multi_sig_user: Address,
+        action: Action,
+        nonce: u64,
+    ) -> Result<Proposal> {
+        if matches!(action, Action::MultiSig(_)) {
+            return Err(Error::new("a proposal cannot wrap a multisig action"));
+        }
+
+        let config = multi_sig_config(client, multi_sig_user).await?;
+        if !config.authorized_users.contains(&lead) {
+            return Err(Error::new(format!(
+                "agent {lead} is not an authorized user of {multi_sig_user}"
+            )));
+        }
+
+        let mut proposal = Proposal {
+            id: 0,
+            multi_sig_user,
+            outer_signer: lead,
+            action,
+            nonce,
+            threshold: config.threshold,
+            authorized_users: config.authorized_users,
+            approvals: vec![],
+            status: ProposalStatus::Pending,
+            error: None,
+            created_at: now(),
+        };
+        proposal.id = self.store.insert(&proposal).await.map_err(store_error)?;
+        Ok(proposal)
+    }
+
+    /// Adds the signature of an authorized user, submitting the proposal with `lead` once
+    /// the threshold is met. Proposals of another outer signer are left for it to
+    /// [`submit`](Self::submit).
+    pub async fn sign<S: Signer + Send + Sync>(
+        &self,
+        client: &HttpClient,
+        lead: &S,
+        id: u64,
+        signature: Signature,
+    ) -> Result<Proposal> {
+        let _guard = self.lock(id).await;
+        let mut proposal = self.pending(id).await?;
+
+        let signer = proposal
+            .payload()
+            .recover(&signature, proposal.nonce, client.chain())
+            .map_err(|err| Error::new(format!("invalid signature: {err}")))?;
+        let config = multi_sig_config(client, proposal.multi_sig_user).await?;
+        if !config.authorized_users.contains(&signer) {
+            return Err(Error::new(format!(
+                "{signer} is not an authorized user of {}",
+                proposal.multi_sig_user
+            )));
+        }
+        if proposal.has_signed(signer) {
+            return Err(Error::new(format!("{signer} already signed proposal {id}")));
+        }
+
+        proposal.threshold = config.threshold;
+        proposal.authorized_users = config.authorized_users;
+        proposal.approvals.push(Approval {
+            signer,
+            signature,
+            signed_at: now(),
+        });
+        if proposal.is_ready() && lead.address() == proposal.outer_signer {
+            self.submit_signed(client, lead, &mut proposal).await;
+        }
+        self.store.update(&proposal).await.map_err(store_error)?;
+        Ok(proposal)
+    }
+
+    /// Submits a proposal that has enough signatures, e.g. after a failed submission.
+    pub async fn submit<S: Signer + Send + Sync>(
+        &self,
+        client: &HttpClient,
+        lead: &S,
+        id: u64,
+    ) -> Result<Proposal> {
+        let _guard = self.lock(id).await;
+        let mut proposal = self.get(id).await?.ok_or_else(|| unknown(id))?;
+        if proposal.status == ProposalStatus::Submitted {
+            return Err(Error::new(format!("proposal {id} was already submitted")));
+        }
+        if !proposal.is_ready() {
+            return Err(Error::new(format!(
+                "proposal {id} has {} of {} signatures",
+                proposal.valid_approvals().count(),
+                proposal.threshold
+            )));
+        }
+        if lead.address() != proposal.outer_signer {
+            return Err(Error::new(format!(
+                "proposal {id} must be submitted by {}",
+                proposal.outer_signer
+            )));
+        }
+
+        self.submit_signed(client, lead, &mut proposal).await;
+        self.store.update(&proposal).await.map_err(store_error)?;
+        Ok(proposal)
+    }
+
+    /// Waits for the updates of proposal `id` in progress to finish.
+    async fn lock(&self, id: u64) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.locks.lock().unwrap();
+            // drop the locks nobody holds or waits for
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            locks.entry(id).or_default().clone()
+        };
+        lock.lock_owned().await
+    }
+
+    /// Returns a proposal still collecting signatures.
+    async fn pending(&self, id: u64) -> Result<Proposal> {
+        let proposal = self.get(id).await?.ok_or_else(|| unknown(id))?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(Error::new(format!(
+                "proposal {id} is {}, not pending",
+                proposal.status.as_str()
+            )));
+        }
+        Ok(proposal)
+    }
+
+    /// Sends the signed action and records the outcome in the proposal.
+    async fn submit_signed<S: Signer + Send + Sync>(
+        &self,
+        client: &HttpClient,
+        lead: &S,
+        proposal: &mut Proposal,
+    ) {
+        let action = MultiSigAction {
+            signature_chain_id: client.chain().arbitrum_id().to_owned(),
+            signatures: proposal
+                .valid_approvals()
+                .map(|approval| approval.signature)
+                .collect(),
+            payload: proposal.payload(),
+        };
+        let res = async {
+            let req = Action::MultiSig(action)
+                .sign(lead, proposal.nonce, None, None, client.chain())
+                .await?;
+            client.send(req).await
+        }
+        .await;
+
+        let error = match res {
+            Ok(Response::Ok(resp)) => rejection(&resp),
+            Ok(Response::Err(err)) => Some(err),
+            Err(err) => Some(err.to_string()),
+        };
+        proposal.status = match error {
+            None => ProposalStatus::Submitted,
+            Some(_) => ProposalStatus::Failed,
+        };
+        proposal.error = error;
+    }
+}
+
+/// Returns the errors of an accepted action whose orders, cancels or TWAP were
+/// rejected.
+fn rejection(resp: &OkResponse) -> Option<String> {
+    let errors: Vec<_> = match resp {
+        OkResponse::Order { statuses } | OkResponse::Cancel { statuses } => statuses
+            .iter()
+            .filter_map(OrderResponseStatus::error)
+            .collect(),
+        OkResponse::TwapOrder {
+            status: TwapOrderStatus::Error(err),
+        }
+        | OkResponse::TwapCancel {
+            status: TwapCancelStatus::Error(err),
+        } => vec![err.as_str()],
+        _ => vec![],
+    };
+    (!errors.is_empty()).then(|| errors.join("; "))
+}
+
+async fn multi_sig_config(client: &HttpClient, user: Address) -> Result<MultiSigConfig> {
+    let config = client.multi_sig_config(user).await.upstream()?;
+    if config.authorized_users.is_empty() {
+        return Err(Error::new(format!("{user} is not a multisig account")));
+    }
+    Ok(config)
+}
+
+fn store_error(err: anyhow::Error) -> Error {
+    Error::new(format!("proposal store: {err}"))
+}
+
+fn unknown(id: u64) -> Error {
+    Error::new(format!("unknown proposal: {id}"))
+}
+
+fn now() -> u64 {
+    Utc::now().timestamp_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{Json, Router, routing::post};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::hypercore::{self, PrivateKeySigner, types::ScheduleCancel};
+
+    /// Serves a 2-of-2 multisig config and accepts every exchange action.
+    async fn mock_api(users: [Address; 2], submitted: Arc<AtomicUsize>) -> HttpClient {
+        let config = serde_json::json!({ "authorizedUsers": users, "threshold": 2 });
+        let app = Router::new()
+            .route("/info", post(move || async move { Json(config) }))
+            .route(
+                "/exchange",
+                post(move || async move {
+                    submitted.fetch_add(1, Ordering::Relaxed);
+                    Json(serde_json::json!({ "status": "ok", "response": { "type": "default" } }))
+                }),
+            );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        hypercore::mainnet().with_url(url.parse().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_submits_at_threshold() {
+        let agent = PrivateKeySigner::random();
+        let cosigner = PrivateKeySigner::random();
+        let submitted = Arc::new(AtomicUsize::new(0));
+        let client = mock_api([agent.address(), cosigner.address()], submitted.clone()).await;
+
+        let proposals = Proposals::in_memory();
+        let multi_sig_user = Address::repeat_byte(9);
+        let action = Action::ScheduleCancel(ScheduleCancel { time: None });
+        let proposal = proposals
+            .propose(&client, agent.address(), multi_sig_user, action, 1)
+            .await
+            .unwrap();
+        let payload = proposal.payload();
+        let chain = client.chain();
+
+        let signature = payload.sign(&agent, 1, chain).await.unwrap();
+        let proposal = proposals
+            .sign(&client, &agent, proposal.id, signature)
+            .await
+            .unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Pending);
+        assert_eq!(proposal.approvals[0].signer, agent.address());
+        assert!(!proposal.is_ready());
+
+        let err = proposals
+            .sign(&client, &agent, proposal.id, signature)
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("already signed"));
+
+        let outsider = PrivateKeySigner::random();
+        let signature = payload.sign(&outsider, 1, chain).await.unwrap();
+        let err = proposals
+            .sign(&client, &agent, proposal.id, signature)
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("not an authorized user"));
+        assert_eq!(submitted.load(Ordering::Relaxed), 0);
+
+        let signature = payload.sign(&cosigner, 1, chain).await.unwrap();
+        let proposal = proposals
+            .sign(&client, &agent, proposal.id, signature)
+            .await
+            .unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Submitted);
+        assert_eq!(submitted.load(Ordering::Relaxed), 1);
+
+        let stored = proposals.get(proposal.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, ProposalStatus::Submitted);
+        assert_eq!(stored.approvals.len(), 2);
+    }
+
+    #[test]
+    fn test_rejected_statuses_fail() {
+        let resp: Response = serde_json::from_value(serde_json::json!({
+            "status": "ok",
+            "response": { "type": "order", "data": { "statuses": [
+                { "resting": { "oid": 1 } },
+                { "error": "Insufficient margin to place order." },
+            ] } },
+        }))
+        .unwrap();
+        let Response::Ok(resp) = resp else {
+            panic!("unexpected response: {resp:?}");
+        };
+        assert_eq!(
+            rejection(&resp).as_deref(),
+            Some("Insufficient margin to place order.")
+        );
+
+        assert_eq!(rejection(&OkResponse::Default), None);
+        let resp = OkResponse::Cancel {
+            statuses: vec![OrderResponseStatus::Success],
+        };
+        assert_eq!(rejection(&resp), None);
+    }
+
+    #[test]
+    fn test_ready_counts_authorized_signers() {
+        let approval = |byte| Approval {
+            signer: Address::repeat_byte(byte),
+            signature: Signature {
+                r: Default::default(),
+                s: Default::default(),
+                v: 27,
+            },
+            signed_at: 0,
+        };
+        let mut proposal = Proposal {
+            id: 1,
+            multi_sig_user: Address::repeat_byte(9),
+            outer_signer: Address::repeat_byte(1),
+            action: Action::ScheduleCancel(ScheduleCancel { time: None }),
+            nonce: 1,
+            threshold: 2,
+            authorized_users: vec![Address::repeat_byte(1), Address::repeat_byte(2)],
+            approvals: vec![approval(1), approval(2)],
+            status: ProposalStatus::Pending,
+            error: None,
+            created_at: 0,
+        };
+        assert!(proposal.is_ready());
+
+        // user 2 was replaced by user 3
+        proposal.authorized_users[1] = Address::repeat_byte(3);
+        assert!(!proposal.is_ready());
+        assert_eq!(proposal.valid_approvals().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_locks_each_proposal() {
+        let proposals = Proposals::in_memory();
+        let first = proposals.lock(1).await;
+        let timeout = std::time::Duration::from_millis(50);
+        assert!(
+            tokio::time::timeout(timeout, proposals.lock(2))
+                .await
+                .is_ok()
+        );
+        assert!(
+            tokio::time::timeout(timeout, proposals.lock(1))
+                .await
+                .is_err()
+        );
+        drop(first);
+        assert!(
+            tokio::time::timeout(timeout, proposals.lock(1))
+                .await
+                .is_ok()
+        );
+        assert!(proposals.locks.lock().unwrap().len() <= 1);
+    }
+
+    #[tokio::test]
+    async fn test_other_lead_does_not_submit() {
+        let agent = PrivateKeySigner::random();
+        let other = PrivateKeySigner::random();
+        let submitted = Arc::new(AtomicUsize::new(0));
+        let client = mock_api([agent.address(), other.address()], submitted.clone()).await;
+
+        let proposals = Proposals::in_memory();
+        let action = Action::ScheduleCancel(ScheduleCancel { time: None });
+        let proposal = proposals
+            .propose(&client, agent.address(), Address::repeat_byte(9), action, 1)
+            .await
+            .unwrap();
+        let payload = proposal.payload();
+        for signer in [&agent, &other] {
+            let signature = payload.sign(signer, 1, client.chain()).await.unwrap();
+            proposals
+                .sign(&client, &other, proposal.id, signature)
+                .await
+                .unwrap();
+        }
+
+        let proposal = proposals.get(proposal.id).await.unwrap().unwrap();
+        assert!(proposal.is_ready());
+        assert_eq!(proposal.status, ProposalStatus::Pending);
+        assert_eq!(submitted.load(Ordering::Relaxed), 0);
+
+        let err = proposals
+            .submit(&client, &other, proposal.id)
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("must be submitted by"));
+        let proposal = proposals
+            .submit(&client, &agent, proposal.id)
+            .await
+            .unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Submitted);
+    }
+
+    #[tokio::test]
+    async fn test_agent_must_be_authorized() {
+        let agent = PrivateKeySigner::random();
+        let users = [Address::repeat_byte(1), Address::repeat_byte(2)];
+        let client = mock_api(users, Arc::default()).await;
+
+        let action = Action::ScheduleCancel(ScheduleCancel { time: None });
+        let err = Proposals::in_memory()
+            .propose(&client, agent.address(), Address::repeat_byte(9), action, 1)
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("is not an authorized user"));
+    }
+}
@@ -0,0 +1,225 @@
+//! Embedded SQLite [`ProposalStore`].
+
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Context;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+
+use super::{Proposal, ProposalStore};
+use crate::Address;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS proposals (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    multi_sig_user TEXT NOT NULL,
+    outer_signer TEXT NOT NULL,
+    action TEXT NOT NULL,
+    nonce INTEGER NOT NULL,
+    threshold INTEGER NOT NULL,
+    authorized_users TEXT NOT NULL,
+    approvals TEXT NOT NULL,
+    status TEXT NOT NULL,
+    error TEXT,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS proposals_multi_sig_user ON proposals (multi_sig_user);
+";
+
+const COLUMNS: &str = "id, multi_sig_user, outer_signer, action, nonce, threshold, \
+    authorized_users, approvals, status, error, created_at";
+
+/// [`ProposalStore`] in a SQLite database.
+///
+/// Queries run on the blocking thread pool.
+#[derive(Clone)]
+pub struct SqliteStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteStore {
+    /// Opens or creates the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let conn = Connection::open(path).with_context(|| format!("opening {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    /// Creates a database in memory, lost when the store is dropped.
+    pub fn in_memory() -> anyhow::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> anyhow::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Runs `f` with the connection on the blocking thread pool.
+    async fn call<T, F>(&self, f: F) -> anyhow::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> anyhow::Result<T> + Send + 'static,
+    {
+        let conn = self.conn.clone();
+        tokio::task::spawn_blocking(move || f(&conn.lock().unwrap())).await?
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl ProposalStore for SqliteStore {
+    async fn insert(&self, proposal: &Proposal) -> anyhow::Result<u64> {
+        let proposal = proposal.clone();
+        self.call(move |conn| {
+            conn.execute(
+                "INSERT INTO proposals (multi_sig_user, outer_signer, action, nonce, threshold, \
+                 authorized_users, approvals, status, error, created_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    address(proposal.multi_sig_user),
+                    address(proposal.outer_signer),
+                    serde_json::to_string(&proposal.action)?,
+                    proposal.nonce as i64,
+                    proposal.threshold as i64,
+                    serde_json::to_string(&proposal.authorized_users)?,
+                    serde_json::to_string(&proposal.approvals)?,
+                    proposal.status.as_str(),
+                    proposal.error,
+                    proposal.created_at as i64,
+                ],
+            )?;
+            Ok(conn.last_insert_rowid() as u64)
+        })
+        .await
+    }
+
+    async fn get(&self, id: u64) -> anyhow::Result<Option<Proposal>> {
+        self.call(move |conn| {
+            conn.query_row(
+                &format!("SELECT {COLUMNS} FROM proposals WHERE id = ?1"),
+                [id as i64],
+                |row| Ok(from_row(row)),
+            )
+            .optional()?
+            .transpose()
+        })
+        .await
+    }
+
+    async fn list(&self, multi_sig_user: Address) -> anyhow::Result<Vec<Proposal>> {
+        self.call(move |conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {COLUMNS} FROM proposals WHERE multi_sig_user = ?1 ORDER BY id DESC"
+            ))?;
+            let rows = stmt.query_map([address(multi_sig_user)], |row| Ok(from_row(row)))?;
+            rows.map(|row| row?).collect()
+        })
+        .await
+    }
+
+    async fn update(&self, proposal: &Proposal) -> anyhow::Result<()> {
+        let proposal = proposal.clone();
+        self.call(move |conn| {
+            let updated = conn.execute(
+                "UPDATE proposals SET threshold = ?2, authorized_users = ?3, approvals = ?4, \
+                 status = ?5, error = ?6 WHERE id = ?1",
+                params![
+                    proposal.id as i64,
+                    proposal.threshold as i64,
+                    serde_json::to_string(&proposal.authorized_users)?,
+                    serde_json::to_string(&proposal.approvals)?,
+                    proposal.status.as_str(),
+                    proposal.error,
+                ],
+            )?;
+            anyhow::ensure!(updated == 1, "unknown proposal: {}", proposal.id);
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Formats an address the same way for inserts and lookups.
+fn address(address: Address) -> String {
+    address.to_string().to_lowercase()
+}
+
+fn from_row(row: &Row<'_>) -> anyhow::Result<Proposal> {
+    Ok(Proposal {
+        id: row.get::<_, i64>(0)? as u64,
+        multi_sig_user: row.get::<_, String>(1)?.parse()?,
+        outer_signer: row.get::<_, String>(2)?.parse()?,
+        action: serde_json::from_str(&row.get::<_, String>(3)?)?,
+        nonce: row.get::<_, i64>(4)? as u64,
+        threshold: row.get::<_, i64>(5)? as usize,
+        authorized_users: serde_json::from_str(&row.get::<_, String>(6)?)?,
+        approvals: serde_json::from_str(&row.get::<_, String>(7)?)?,
+        status: row.get::<_, String>(8)?.parse()?,
+        error: row.get(9)?,
+        created_at: row.get::<_, i64>(10)? as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        hypercore::{
+            Signature,
+            types::{Action, ScheduleCancel},
+        },
+        hypergraphql::multisig::{Approval, ProposalStatus},
+    };
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let store = SqliteStore::in_memory().unwrap();
+        let user = Address::repeat_byte(1);
+        let mut proposal = Proposal {
+            id: 0,
+            multi_sig_user: user,
+            outer_signer: Address::repeat_byte(2),
+            action: Action::ScheduleCancel(ScheduleCancel { time: Some(1) }),
+            nonce: 1_700_000_000_000,
+            threshold: 2,
+            authorized_users: vec![Address::repeat_byte(2), Address::repeat_byte(3)],
+            approvals: vec![],
+            status: ProposalStatus::Pending,
+            error: None,
+            created_at: 1_700_000_000_000,
+        };
+
+        proposal.id = store.insert(&proposal).await.unwrap();
+        let second = store.insert(&proposal).await.unwrap();
+        assert_ne!(proposal.id, second);
+
+        proposal.approvals.push(Approval {
+            signer: Address::repeat_byte(3),
+            signature: format!("0x{}{}1b", "11".repeat(32), "22".repeat(32))
+                .parse::<Signature>()
+                .unwrap(),
+            signed_at: 1_700_000_000_001,
+        });
+        proposal.status = ProposalStatus::Failed;
+        proposal.error = Some("nonce too old".into());
+        store.update(&proposal).await.unwrap();
+
+        let stored = store.get(proposal.id).await.unwrap().unwrap();
+        assert_eq!(stored.approvals[0].signer, Address::repeat_byte(3));
+        assert_eq!(stored.status, ProposalStatus::Failed);
+        assert_eq!(stored.error.as_deref(), Some("nonce too old"));
+        assert!(matches!(stored.action, Action::ScheduleCancel(_)));
+
+        let listed = store.list(user).await.unwrap();
+        assert_eq!(
+            listed.iter().map(|p| p.id).collect::<Vec<_>>(),
+            [second, proposal.id]
+        );
+        assert!(store.get(42).await.unwrap().is_none());
+        assert!(store.list(Address::ZERO).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,584 @@
+//! Root mutation type for trading.
+//!
+//! Mutations sign actions with a server-held [`Agent`] registered as schema data.
+//! Without an agent the trading fields are still part of the schema, but fail.
+//!
+//! Order actions return an [`OrderActionResult`]: either one status per order, or the
+//! exchange error together with the ids of the orders it applies to.
+
+use std::sync::Arc;
+
+use async_graphql::{Context, Error, Guard, InputObject, Json, Object, OneofObject, Result};
+use chrono::{DateTime, Utc};
+use either::Either;
+
+use super::{
+    MetaCache,
+    auth::{Role, RoleGuard},
+    error::UpstreamExt,
+    multisig::{Proposal, Proposals},
+    object::OrderActionResult,
+    scalar,
+    weight::weight,
+};
+use crate::{
+    Address,
+    hypercore::{
+        Cloid, HttpClient, NonceHandler, PrivateKeySigner,
+        rate_limit::{ACTIONS_PER_WEIGHT, EXCHANGE_WEIGHT, INFO_WEIGHT},
+        types::{
+            Action, BatchCancel, BatchCancelCloid, BatchModify, BatchOrder, Cancel, CancelByCloid,
+            Modify, OrderGrouping, OrderRequest, OrderTypePlacement, TimeInForce, TpSl,
+        },
+    },
+};
+
+/// Signer and nonce source used by mutations.
+///
+/// Usually an API agent approved by the trading account, loaded once at server start.
+/// All mutations draw their nonces from the same [`NonceHandler`].
+pub struct Agent {
+    signer: PrivateKeySigner,
+    nonces: Arc<NonceHandler>,
+}
+
+impl Agent {
+    /// Creates an agent with its own nonce handler.
+    pub fn new(signer: PrivateKeySigner) -> Self {
+        Self {
+            signer,
+            nonces: Arc::default(),
+        }
+    }
+
+    /// Uses a nonce handler shared with other users of the same signer.
+    #[must_use]
+    pub fn with_nonces(self, nonces: Arc<NonceHandler>) -> Self {
+        Self { nonces, ..self }
+    }
+
+    /// Returns the address of the signer.
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+}
+
+/// Limit order parameters.
+#[derive(Debug, Clone, InputObject)]
+pub struct LimitInput {
+    /// Time in force
+    pub tif: TimeInForce,
+}
+
+/// Trigger (take profit / stop loss) order parameters.
+#[derive(Debug, Clone, InputObject)]
+pub struct TriggerInput {
+    /// Execute as a market order when triggered
+    pub is_market: bool,
+    /// Trigger price
+    pub trigger_px: scalar::Decimal,
+    /// Take profit or stop loss
+    pub tpsl: TpSl,
+}
+
+/// Order type, exactly one of `limit` or `trigger`.
+#[derive(Debug, Clone, OneofObject)]
+pub enum OrderTypeInput {
+    Limit(LimitInput),
+    Trigger(TriggerInput),
+}
+
+impl From<OrderTypeInput> for OrderTypePlacement {
+    fn from(input: OrderTypeInput) -> Self {
+        match input {
+            OrderTypeInput::Limit(limit) => Self::Limit { tif: limit.tif },
+            OrderTypeInput::Trigger(trigger) => Self::Trigger {
+                is_market: trigger.is_market,
+                trigger_px: trigger.trigger_px.into(),
+                tpsl: trigger.tpsl,
+            },
+        }
+    }
+}
+
+/// A new order, see [`OrderRequest`].
+#[derive(Debug, Clone, InputObject)]
+pub struct OrderInput {
+    /// Asset index
+    pub asset: usize,
+    /// `true` for a buy, `false` for a sell
+    pub is_buy: bool,
+    /// Limit price
+    pub limit_px: scalar::Decimal,
+    /// Size in base asset units
+    pub sz: scalar::Decimal,
+    /// Only reduce an existing position
+    #[graphql(default)]
+    pub reduce_only: bool,
+    /// Order type
+    pub order_type: OrderTypeInput,
+    /// Client order ID, a random one is generated if omitted
+    pub cloid: Option<scalar::Cloid>,
+}
+
+impl From<OrderInput> for OrderRequest {
+    fn from(input: OrderInput) -> Self {
+        Self {
+            asset: input.asset,
+            is_buy: input.is_buy,
+            limit_px: input.limit_px.into(),
+            sz: input.sz.into(),
+            reduce_only: input.reduce_only,
+            order_type: input.order_type.into(),
+            cloid: input.cloid.map_or_else(Cloid::random, Into::into),
+        }
+    }
+}
+
+/// Cancellation by order ID.
+#[derive(Debug, Clone, InputObject)]
+pub struct CancelInput {
+    /// Asset index
+    pub asset: usize,
+    /// Order ID
+    pub oid: u64,
+}
+
+/// Cancellation by client order ID.
+#[derive(Debug, Clone, InputObject)]
+pub struct CancelByCloidInput {
+    /// Asset index
+    pub asset: u32,
+    /// Client order ID
+    pub cloid: scalar::Cloid,
+}
+
+/// Modification of an order identified by exactly one of `oid` or `cloid`.
+#[derive(Debug, Clone, InputObject)]
+pub struct ModifyInput {
+    /// Order ID
+    pub oid: Option<u64>,
+    /// Client order ID
+    pub cloid: Option<scalar::Cloid>,
+    /// Replacement order
+    pub order: OrderInput,
+}
+
+impl TryFrom<ModifyInput> for Modify {
+    type Error = Error;
+
+    fn try_from(input: ModifyInput) -> Result<Self> {
+        let oid = match (input.oid, input.cloid) {
+            (Some(oid), None) => Either::Left(oid),
+            (None, Some(cloid)) => Either::Right(cloid.into()),
+            _ => return Err(Error::new("exactly one of `oid` or `cloid` must be set")),
+        };
+        Ok(Self {
+            oid,
+            order: input.order.into(),
+        })
+    }
+}
+
+/// Root mutation type.
+///
+/// Every field accepts an optional `vaultAddress` to trade on behalf of a vault or
+/// subaccount, and an optional `expiresAfter` timestamp in milliseconds.
+///
+/// With authentication enabled, trading fields require [`Role::Trader`] and
+/// administrative fields [`Role::Admin`].
+pub struct Mutation;
+
+#[Object]
+impl Mutation {
+    /// Places a batch of orders.
+    #[graphql(
+        guard = "RoleGuard::new(Role::Trader)",
+        directive = weight::apply(EXCHANGE_WEIGHT, Some(ACTIONS_PER_WEIGHT))
+    )]
+    async fn place(
+        &self,
+        ctx: &Context<'_>,
+        orders: Vec<OrderInput>,
+        #[graphql(default_with = "OrderGrouping::Na")] grouping: OrderGrouping,
+        vault_address: Option<scalar::Address>,
+        expires_after: Option<u64>,
+    ) -> Result<OrderActionResult> {
+        let (client, agent) = signing(ctx)?;
+        let batch = BatchOrder {
+            orders: orders.into_iter().map(Into::into).collect(),
+            grouping,
+        };
+        let res = client
+            .place(
+                &agent.signer,
+                batch,
+                agent.nonces.next(),
+                vault_address.map(Into::into),
+                timestamp(expires_after)?,
+            )
+            .await;
+        Ok(res.into())
+    }
+
+    /// Cancels a batch of orders by order ID.
+    #[graphql(
+        guard = "RoleGuard::new(Role::Trader)",
+        directive = weight::apply(EXCHANGE_WEIGHT, Some(ACTIONS_PER_WEIGHT))
+    )]
+    async fn cancel(
+        &self,
+        ctx: &Context<'_>,
+        cancels: Vec<CancelInput>,
+        vault_address: Option<scalar::Address>,
+        expires_after: Option<u64>,
+    ) -> Result<OrderActionResult> {
+        let (client, agent) = signing(ctx)?;
+        let batch = BatchCancel {
+            cancels: cancels
+                .into_iter()
+                .map(|cancel| Cancel {
+                    asset: cancel.asset,
+                    oid: cancel.oid,
+                })
+                .collect(),
+        };
+        let res = client
+            .cancel(
+                &agent.signer,
+                batch,
+                agent.nonces.next(),
+                vault_address.map(Into::into),
+                timestamp(expires_after)?,
+            )
+            .await;
+        Ok(res.into())
+    }
+
+    /// Cancels a batch of orders by client order ID.
+    #[graphql(
+        guard = "RoleGuard::new(Role::Trader)",
+        directive = weight::apply(EXCHANGE_WEIGHT, Some(ACTIONS_PER_WEIGHT))
+    )]
+    async fn cancel_by_cloid(
+        &self,
+        ctx: &Context<'_>,
+        cancels: Vec<CancelByCloidInput>,
+        vault_address: Option<scalar::Address>,
+        expires_after: Option<u64>,
+    ) -> Result<OrderActionResult> {
+        let (client, agent) = signing(ctx)?;
+        let batch = BatchCancelCloid {
+            cancels: cancels
+                .into_iter()
+                .map(|cancel| CancelByCloid {
+                    asset: cancel.asset,
+                    cloid: cancel.cloid.into(),
+                })
+                .collect(),
+        };
+        let res = client
+            .cancel_by_cloid(
+                &agent.signer,
+                batch,
+                agent.nonces.next(),
+                vault_address.map(Into::into),
+                timestamp(expires_after)?,
+            )
+            .await;
+        Ok(res.into())
+    }
+
+    /// Modifies a batch of orders.
+    #[graphql(
+        guard = "RoleGuard::new(Role::Trader)",
+        directive = weight::apply(EXCHANGE_WEIGHT, Some(ACTIONS_PER_WEIGHT))
+    )]
+    async fn modify(
+        &self,
+        ctx: &Context<'_>,
+        modifies: Vec<ModifyInput>,
+        vault_address: Option<scalar::Address>,
+        expires_after: Option<u64>,
+    ) -> Result<OrderActionResult> {
+        let (client, agent) = signing(ctx)?;
+        let batch = BatchModify {
+            modifies: modifies
+                .into_iter()
+                .map(Modify::try_from)
+                .collect::<Result<_>>()?,
+        };
+        let res = client
+            .modify(
+                &agent.signer,
+                batch,
+                agent.nonces.next(),
+                vault_address.map(Into::into),
+                timestamp(expires_after)?,
+            )
+            .await;
+        Ok(res.into())
+    }
+
+    /// Schedules the cancellation of all open orders (dead man's switch).
+    #[graphql(
+        guard = "RoleGuard::new(Role::Trader)",
+        directive = weight::apply(EXCHANGE_WEIGHT, None)
+    )]
+    async fn schedule_cancel(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Cancellation time in milliseconds")] time: u64,
+        vault_address: Option<scalar::Address>,
+        expires_after: Option<u64>,
+    ) -> Result<bool> {
+        let (client, agent) = signing(ctx)?;
+        let when = timestamp(Some(time))?.unwrap_or_default();
+        client
+            .schedule_cancel(
+                &agent.signer,
+                agent.nonces.next(),
+                when,
+                vault_address.map(Into::into),
+                timestamp(expires_after)?,
+            )
+            .await
+            .upstream()?;
+        Ok(true)
+    }
+
+    /// Proposes an action for a multisig account, see [`multisig`](super::multisig).
+    ///
+    /// The agent submits it once enough authorized users signed it with `signProposal`.
+    /// Proposing requires the role of the action, see [`Role::for_action`]: trading
+    /// actions need `trader`, transfers and account management `admin`.
+    #[graphql(
+        guard = "RoleGuard::new(Role::for_action(&action.0))",
+        directive = weight::apply(INFO_WEIGHT, None)
+    )]
+    async fn propose(
+        &self,
+        ctx: &Context<'_>,
+        multi_sig_user: scalar::Address,
+        #[graphql(desc = "Action in the exchange JSON format, e.g. `{\"type\": \"order\", ...}`")]
+        action: Json<Action>,
+        #[graphql(desc = "Nonce to sign, the agent's next nonce if omitted")] nonce: Option<u64>,
+    ) -> Result<Proposal> {
+        let (client, agent) = signing(ctx)?;
+        let nonce = nonce.unwrap_or_else(|| agent.nonces.next());
+        ctx.data::<Proposals>()?
+            .propose(
+                client,
+                agent.address(),
+                multi_sig_user.into(),
+                action.0,
+                nonce,
+            )
+            .await
+    }
+
+    /// Adds the signature of an authorized user to a proposal, submitting it once the
+    /// threshold is met. Requires the role of the proposed action, as `propose`.
+    #[graphql(
+        guard = "RoleGuard::new(Role::Trader)",
+        directive = weight::apply(INFO_WEIGHT + EXCHANGE_WEIGHT, None)
+    )]
+    async fn sign_proposal(
+        &self,
+        ctx: &Context<'_>,
+        id: u64,
+        #[graphql(desc = "Signature of the proposal's `typedData`")] signature: scalar::Signature,
+    ) -> Result<Proposal> {
+        let proposals = ctx.data::<Proposals>()?;
+        check_proposal_role(ctx, proposals, id).await?;
+        let (client, agent) = signing(ctx)?;
+        proposals
+            .sign(client, &agent.signer, id, signature.into())
+            .await
+    }
+
+    /// Submits a proposal with enough signatures again, after a failed submission.
+    /// Requires the role of the proposed action, as `propose`.
+    #[graphql(
+        guard = "RoleGuard::new(Role::Trader)",
+        directive = weight::apply(EXCHANGE_WEIGHT, None)
+    )]
+    async fn submit_proposal(&self, ctx: &Context<'_>, id: u64) -> Result<Proposal> {
+        let proposals = ctx.data::<Proposals>()?;
+        check_proposal_role(ctx, proposals, id).await?;
+        let (client, agent) = signing(ctx)?;
+        proposals.submit(client, &agent.signer, id).await
+    }
+
+    /// Drops the cached market metadata, so the next queries fetch fresh data.
+    ///
+    /// Does not require an agent.
+    #[graphql(guard = "RoleGuard::new(Role::Admin)")]
+    async fn refresh_metadata(&self, ctx: &Context<'_>) -> Result<bool> {
+        ctx.data::<MetaCache>()?.refresh().await;
+        Ok(true)
+    }
+}
+
+/// Returns the client and the agent, failing if no agent is configured.
+fn signing<'a>(ctx: &Context<'a>) -> Result<(&'a HttpClient, &'a Agent)> {
+    let client = ctx.data::<HttpClient>()?;
+    let agent = ctx
+        .data_opt::<Agent>()
+        .ok_or_else(|| Error::new("mutations are disabled: no agent signer configured"))?;
+    Ok((client, agent))
+}
+
+/// Checks that the caller has the role of the action of proposal `id`, see
+/// [`Role::for_action`].
+async fn check_proposal_role(ctx: &Context<'_>, proposals: &Proposals, id: u64) -> Result<()> {
+    match proposals.get(id).await? {
+        Some(proposal) => {
+            RoleGuard::new(Role::for_action(&proposal.action))
+                .check(ctx)
+                .await
+        }
+        None => Err(Error::new(format!("unknown proposal: {id}"))),
+    }
+}
+
+/// Converts a timestamp in milliseconds.
+fn timestamp(millis: Option<u64>) -> Result<Option<DateTime<Utc>>> {
+    millis
+        .map(|millis| {
+            i64::try_from(millis)
+                .ok()
+                .and_then(DateTime::from_timestamp_millis)
+                .ok_or_else(|| Error::new(format!("invalid timestamp: {millis}")))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{Request, Variables};
+
+    use super::*;
+    use crate::{
+        dec, hypercore,
+        hypergraphql::{auth::Principal, build_schema, multisig::ProposalStatus, schema_builder},
+    };
+
+    #[test]
+    fn test_order_input_generates_cloid() {
+        let input = OrderInput {
+            asset: 0,
+            is_buy: true,
+            limit_px: dec!(100).into(),
+            sz: dec!(0.1).into(),
+            reduce_only: false,
+            order_type: OrderTypeInput::Limit(LimitInput {
+                tif: TimeInForce::Gtc,
+            }),
+            cloid: None,
+        };
+
+        let a = OrderRequest::from(input.clone());
+        let b = OrderRequest::from(input);
+        assert_ne!(a.cloid, b.cloid);
+        assert_eq!(a.limit_px, dec!(100));
+    }
+
+    #[tokio::test]
+    async fn test_trading_requires_trader_role() {
+        let schema = build_schema(hypercore::mainnet());
+        let reader = Principal {
+            name: "viewer".into(),
+            role: Role::Reader,
+        };
+        let req = Request::new("mutation { scheduleCancel(time: 1700000000000) }").data(reader);
+        let res = schema.execute(req).await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("trader role required"));
+    }
+
+    #[tokio::test]
+    async fn test_propose_requires_action_role() {
+        let schema = build_schema(hypercore::mainnet());
+        let propose = |action: serde_json::Value| {
+            let trader = Principal {
+                name: "desk".into(),
+                role: Role::Trader,
+            };
+            Request::new(
+                r#"mutation($action: JSON!) {
+                    propose(multiSigUser: "0xdac17f958d2ee523a2206206994597c13d831ec7", action: $action) { id }
+                }"#,
+            )
+            .variables(Variables::from_json(serde_json::json!({ "action": action })))
+            .data(trader)
+        };
+
+        let res = schema
+            .execute(propose(
+                serde_json::json!({ "type": "createSubAccount", "name": "desk" }),
+            ))
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("admin role required"));
+
+        let res = schema
+            .execute(propose(serde_json::json!({ "type": "noop" })))
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("no agent signer"));
+    }
+
+    #[tokio::test]
+    async fn test_proposal_signing_requires_action_role() {
+        let proposals = Proposals::in_memory();
+        let proposal = Proposal {
+            id: 0,
+            multi_sig_user: Address::repeat_byte(9),
+            outer_signer: Address::repeat_byte(1),
+            action: serde_json::from_value(
+                serde_json::json!({ "type": "createSubAccount", "name": "desk" }),
+            )
+            .unwrap(),
+            nonce: 1,
+            threshold: 2,
+            authorized_users: vec![Address::repeat_byte(1), Address::repeat_byte(2)],
+            approvals: vec![],
+            status: ProposalStatus::Pending,
+            error: None,
+            created_at: 0,
+        };
+        let id = proposals.store().insert(&proposal).await.unwrap();
+        let schema = schema_builder(hypercore::mainnet())
+            .data(proposals)
+            .finish();
+
+        let signature = format!("0x{}1b", "11".repeat(64));
+        for mutation in [
+            format!(r#"mutation {{ signProposal(id: {id}, signature: "{signature}") {{ id }} }}"#),
+            format!("mutation {{ submitProposal(id: {id}) {{ id }} }}"),
+        ] {
+            let trader = Principal {
+                name: "desk".into(),
+                role: Role::Trader,
+            };
+            let res = schema.execute(Request::new(mutation).data(trader)).await;
+            assert_eq!(res.errors.len(), 1);
+            assert!(
+                res.errors[0].message.contains("admin role required"),
+                "{:?}",
+                res.errors
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mutations_require_agent() {
+        let schema = build_schema(hypercore::mainnet());
+        let res = schema
+            .execute("mutation { scheduleCancel(time: 1700000000000) }")
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("no agent signer"));
+    }
+}
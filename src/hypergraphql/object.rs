@@ -0,0 +1,867 @@
+//! GraphQL-only object types and computed fields of SDK types.
+//!
+//! Most SDK types derive [`SimpleObject`] directly. Fields that have no direct
+//! GraphQL representation (tuples, maps, enums carrying data) are resolved here,
+//! together with the [`User`] graph that links accounts to their data.
+
+use async_graphql::{
+    ComplexObject, Context, Enum, Object, Result, SimpleObject, Union, connection::Connection,
+};
+use either::Either;
+use rust_decimal::RoundingStrategy;
+
+use super::{
+    error::UpstreamExt,
+    loader::{self, BalancesOf, ClearinghouseStateOf, OpenOrdersOf, RoleOf},
+    pagination::{self, TimeCursor, Window},
+    scalar,
+    weight::weight,
+};
+use crate::{
+    Address, Decimal, U256,
+    hypercore::{
+        ActionError, Cloid, Dex, HttpClient, OidOrCloid, PerpMarket, PriceTick, SpotMarket,
+        SpotToken,
+        rate_limit::{INFO_LIGHT_WEIGHT, INFO_WEIGHT, ITEMS_PER_WEIGHT, USER_ROLE_WEIGHT},
+        types::{
+            ApiAgent, BasicOrder, Bbo, BookLevel, ClearinghouseState, Fill, L2Book, MultiSigConfig,
+            OrderResponseStatus, PositionData, Side, SubAccount, UserBalance, UserRoleType,
+            UserVaultEquity, VaultDetails, VaultFollower, VaultFollowerUser, VaultPortfolio,
+        },
+    },
+    hyperevm::{
+        from_wei,
+        morpho::{PoolApy, VaultApy, VaultSupply},
+    },
+};
+
+/// A HyperCore account.
+///
+/// Each field calls the matching [`HttpClient`] method only when it is selected,
+/// so a query only pays for the data it asks for. Fields backed by a [`UserLoader`](loader::UserLoader)
+/// are fetched once per user and query, however many times they are selected.
+#[derive(Debug, Clone, Copy)]
+pub struct User {
+    address: Address,
+}
+
+impl User {
+    /// Creates a user for the given address.
+    pub fn new(address: Address) -> Self {
+        Self { address }
+    }
+}
+
+#[Object]
+impl User {
+    /// Account address
+    async fn address(&self) -> scalar::Address {
+        self.address.into()
+    }
+
+    /// Perpetuals account state
+    #[graphql(directive = weight::apply(INFO_LIGHT_WEIGHT, None))]
+    async fn clearinghouse_state(&self, ctx: &Context<'_>) -> Result<ClearinghouseState> {
+        loader::load(ctx, ClearinghouseStateOf(self.address)).await
+    }
+
+    /// Open perpetual positions
+    #[graphql(directive = weight::apply(INFO_LIGHT_WEIGHT, None))]
+    async fn positions(&self, ctx: &Context<'_>) -> Result<Vec<PositionData>> {
+        let state = loader::load(ctx, ClearinghouseStateOf(self.address)).await?;
+        Ok(state
+            .asset_positions
+            .into_iter()
+            .map(|asset| asset.position)
+            .collect())
+    }
+
+    /// Open orders
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn open_orders(&self, ctx: &Context<'_>) -> Result<Vec<BasicOrder>> {
+        loader::load(ctx, OpenOrdersOf(self.address)).await
+    }
+
+    /// Fills in ascending time order
+    #[graphql(directive = weight::apply(INFO_WEIGHT, Some(ITEMS_PER_WEIGHT)))]
+    async fn fills(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Start time in milliseconds")] start_time: Option<u64>,
+        #[graphql(desc = "End time in milliseconds, defaults to now")] end_time: Option<u64>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<TimeCursor, Fill>> {
+        let client = ctx.data::<HttpClient>()?;
+        let window = Window::new(start_time.unwrap_or_default(), end_time);
+        pagination::user_fills(client, self.address, window, after, first).await
+    }
+
+    /// Spot token balances
+    #[graphql(directive = weight::apply(INFO_LIGHT_WEIGHT, None))]
+    async fn spot_balances(&self, ctx: &Context<'_>) -> Result<Vec<UserBalance>> {
+        loader::load(ctx, BalancesOf(self.address)).await
+    }
+
+    /// Vault deposits
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn vault_equities(&self, ctx: &Context<'_>) -> Result<Vec<UserVaultEquity>> {
+        let client = ctx.data::<HttpClient>()?;
+        client.user_vault_equities(self.address).await.upstream()
+    }
+
+    /// Subaccounts of this account
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn subaccounts(&self, ctx: &Context<'_>) -> Result<Vec<SubAccount>> {
+        let client = ctx.data::<HttpClient>()?;
+        client.subaccounts(self.address).await.upstream()
+    }
+
+    /// Account role
+    #[graphql(directive = weight::apply(USER_ROLE_WEIGHT, None))]
+    async fn role(&self, ctx: &Context<'_>) -> Result<UserRoleType> {
+        Ok(loader::load(ctx, RoleOf(self.address)).await?.role)
+    }
+
+    /// Approved API agents
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn api_agents(&self, ctx: &Context<'_>) -> Result<Vec<ApiAgent>> {
+        let client = ctx.data::<HttpClient>()?;
+        client.api_agents(self.address).await.upstream()
+    }
+
+    /// Multi-sig configuration
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn multi_sig_config(&self, ctx: &Context<'_>) -> Result<MultiSigConfig> {
+        let client = ctx.data::<HttpClient>()?;
+        client.multi_sig_config(self.address).await.upstream()
+    }
+}
+
+/// Mid price of a market.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Mid {
+    /// Market symbol
+    pub coin: String,
+    /// Mid price
+    pub px: scalar::Decimal,
+}
+
+/// HIP-3 perpetual DEX.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PerpDex {
+    /// DEX name, used to query its markets with `perpsFrom`
+    pub name: String,
+    /// DEX index
+    pub index: usize,
+}
+
+impl From<Dex> for PerpDex {
+    fn from(dex: Dex) -> Self {
+        Self {
+            name: dex.name().to_owned(),
+            index: dex.index(),
+        }
+    }
+}
+
+/// A point of a vault history series.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct HistoryPoint {
+    /// Timestamp in milliseconds
+    pub time: u64,
+    /// Value at that time
+    pub value: String,
+}
+
+/// Vault portfolio for a time period (e.g. "day", "week", "allTime").
+#[derive(Debug, Clone, SimpleObject)]
+pub struct VaultPortfolioPeriod {
+    /// Period name
+    pub period: String,
+    /// Portfolio data for the period
+    pub portfolio: VaultPortfolio,
+}
+
+fn history(points: &[(u64, String)]) -> Vec<HistoryPoint> {
+    points
+        .iter()
+        .map(|(time, value)| HistoryPoint {
+            time: *time,
+            value: value.clone(),
+        })
+        .collect()
+}
+
+#[ComplexObject]
+impl VaultDetails {
+    /// The vault leader as a user
+    async fn leader_user(&self) -> User {
+        User::new(self.leader)
+    }
+
+    /// Portfolio data per time period
+    async fn portfolio(&self) -> Vec<VaultPortfolioPeriod> {
+        self.portfolio
+            .iter()
+            .map(|(period, portfolio)| VaultPortfolioPeriod {
+                period: period.clone(),
+                portfolio: portfolio.clone(),
+            })
+            .collect()
+    }
+}
+
+#[ComplexObject]
+impl VaultPortfolio {
+    /// Historical account values
+    async fn account_value_history(&self) -> Vec<HistoryPoint> {
+        history(&self.account_value_history)
+    }
+
+    /// Historical PnL values
+    async fn pnl_history(&self) -> Vec<HistoryPoint> {
+        history(&self.pnl_history)
+    }
+}
+
+#[ComplexObject]
+impl SubAccount {
+    /// The subaccount as a user
+    async fn user(&self) -> User {
+        User::new(self.sub_account_user)
+    }
+
+    /// The master account as a user
+    async fn master_user(&self) -> User {
+        User::new(self.master)
+    }
+}
+
+#[ComplexObject]
+impl VaultFollower {
+    /// Follower as a user, `null` for the vault leader
+    async fn user(&self) -> Option<User> {
+        match self.user {
+            VaultFollowerUser::Leader => None,
+            VaultFollowerUser::Address(address) => Some(User::new(address)),
+        }
+    }
+
+    /// Follower address, `null` for the vault leader
+    async fn address(&self) -> Option<scalar::Address> {
+        match self.user {
+            VaultFollowerUser::Leader => None,
+            VaultFollowerUser::Address(address) => Some(address.into()),
+        }
+    }
+
+    /// Whether the follower is the vault leader
+    async fn is_leader(&self) -> bool {
+        self.user == VaultFollowerUser::Leader
+    }
+}
+
+#[ComplexObject]
+impl L2Book {
+    /// Bid levels, from highest to lowest price
+    #[graphql(name = "bids")]
+    async fn bid_levels(&self) -> &[BookLevel] {
+        self.bids()
+    }
+
+    /// Ask levels, from lowest to highest price
+    #[graphql(name = "asks")]
+    async fn ask_levels(&self) -> &[BookLevel] {
+        self.asks()
+    }
+}
+
+#[ComplexObject]
+impl Bbo {
+    /// Best bid, `null` if the bid side is empty
+    #[graphql(name = "bid")]
+    async fn best_bid(&self) -> Option<BookLevel> {
+        self.bid().copied()
+    }
+
+    /// Best ask, `null` if the ask side is empty
+    #[graphql(name = "ask")]
+    async fn best_ask(&self) -> Option<BookLevel> {
+        self.ask().copied()
+    }
+}
+
+#[ComplexObject]
+impl PerpMarket {
+    /// Tick size at the given price, see [`PriceTick::tick_for`]
+    #[graphql(name = "tickFor")]
+    async fn tick_size(&self, price: scalar::Decimal) -> Option<scalar::Decimal> {
+        self.tick_for(price.into()).map(Into::into)
+    }
+
+    /// Price rounded to a valid tick.
+    ///
+    /// Without a side, rounds to the nearest tick. With a side, rounds away from the
+    /// market when `conservative` (the default), towards it otherwise.
+    #[graphql(name = "roundPrice")]
+    async fn rounded_price(
+        &self,
+        price: scalar::Decimal,
+        side: Option<Side>,
+        #[graphql(default = true)] conservative: bool,
+    ) -> Option<scalar::Decimal> {
+        round_price(&self.table, price.into(), side, conservative)
+    }
+}
+
+#[ComplexObject]
+impl SpotMarket {
+    /// Tick size at the given price, see [`PriceTick::tick_for`]
+    #[graphql(name = "tickFor")]
+    async fn tick_size(&self, price: scalar::Decimal) -> Option<scalar::Decimal> {
+        self.tick_for(price.into()).map(Into::into)
+    }
+
+    /// Price rounded to a valid tick.
+    ///
+    /// Without a side, rounds to the nearest tick. With a side, rounds away from the
+    /// market when `conservative` (the default), towards it otherwise.
+    #[graphql(name = "roundPrice")]
+    async fn rounded_price(
+        &self,
+        price: scalar::Decimal,
+        side: Option<Side>,
+        #[graphql(default = true)] conservative: bool,
+    ) -> Option<scalar::Decimal> {
+        round_price(&self.table, price.into(), side, conservative)
+    }
+}
+
+fn round_price(
+    table: &PriceTick,
+    price: Decimal,
+    side: Option<Side>,
+    conservative: bool,
+) -> Option<scalar::Decimal> {
+    match side {
+        Some(side) => table.round_by_side(side, price, conservative),
+        None => table.round(price),
+    }
+    .map(Into::into)
+}
+
+#[ComplexObject]
+impl SpotToken {
+    /// Amount in wei on HyperEVM
+    #[graphql(name = "toWei")]
+    async fn amount_to_wei(&self, size: scalar::Decimal) -> scalar::U256 {
+        self.to_wei(size.into()).into()
+    }
+
+    /// Decimal amount of a wei value on HyperEVM
+    #[graphql(name = "fromWei")]
+    async fn amount_from_wei(&self, wei: scalar::U256) -> scalar::Decimal {
+        self.from_wei(wei.into()).into()
+    }
+
+    /// Whether the token can be bridged to HyperEVM
+    #[graphql(name = "isEvmLinked")]
+    async fn evm_linked(&self) -> bool {
+        self.is_evm_linked()
+    }
+
+    /// Destination of transfers from HyperCore to HyperEVM, `null` if not bridgeable
+    #[graphql(name = "bridgeAddress")]
+    async fn bridge(&self) -> Option<scalar::Address> {
+        self.bridge_address().map(Into::into)
+    }
+}
+
+/// Order accepted without further details.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct OrderSuccess {
+    /// Always `true`
+    pub ok: bool,
+}
+
+/// Order resting on the book.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct OrderResting {
+    /// Order ID
+    pub oid: u64,
+    /// Client order ID
+    pub cloid: Option<scalar::Cloid>,
+}
+
+/// Order filled immediately.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct OrderFilled {
+    /// Order ID
+    pub oid: u64,
+    /// Total filled size
+    pub total_sz: scalar::Decimal,
+    /// Average fill price
+    pub avg_px: scalar::Decimal,
+}
+
+/// Order rejected by the exchange.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct OrderRejected {
+    /// Rejection reason
+    pub message: String,
+}
+
+/// Status of a single order of a batch, see [`OrderResponseStatus`].
+#[derive(Debug, Clone, Union)]
+pub enum OrderResponse {
+    Success(OrderSuccess),
+    Resting(OrderResting),
+    Filled(OrderFilled),
+    Rejected(OrderRejected),
+}
+
+impl From<OrderResponseStatus> for OrderResponse {
+    fn from(status: OrderResponseStatus) -> Self {
+        match status {
+            OrderResponseStatus::Success => Self::Success(OrderSuccess { ok: true }),
+            OrderResponseStatus::Resting { oid, cloid } => Self::Resting(OrderResting {
+                oid,
+                cloid: cloid.map(Into::into),
+            }),
+            OrderResponseStatus::Filled {
+                total_sz,
+                avg_px,
+                oid,
+            } => Self::Filled(OrderFilled {
+                oid,
+                total_sz: total_sz.into(),
+                avg_px: avg_px.into(),
+            }),
+            OrderResponseStatus::Error(message) => Self::Rejected(OrderRejected { message }),
+        }
+    }
+}
+
+/// Batch accepted by the exchange, with one status per order in request order.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct OrderActionSuccess {
+    /// Per-order statuses
+    pub statuses: Vec<OrderResponse>,
+}
+
+/// Batch rejected as a whole, see [`ActionError`].
+#[derive(Debug, Clone, SimpleObject)]
+pub struct OrderActionFailure {
+    /// Error message from the exchange
+    pub message: String,
+    /// Order IDs of the affected orders
+    pub oids: Vec<u64>,
+    /// Client order IDs of the affected orders
+    pub cloids: Vec<scalar::Cloid>,
+}
+
+impl From<ActionError<Cloid>> for OrderActionFailure {
+    fn from(err: ActionError<Cloid>) -> Self {
+        Self {
+            message: err.message().to_owned(),
+            oids: vec![],
+            cloids: scalar::list(err.into_ids()),
+        }
+    }
+}
+
+impl From<ActionError<u64>> for OrderActionFailure {
+    fn from(err: ActionError<u64>) -> Self {
+        Self {
+            message: err.message().to_owned(),
+            oids: err.into_ids(),
+            cloids: vec![],
+        }
+    }
+}
+
+impl From<ActionError<OidOrCloid>> for OrderActionFailure {
+    fn from(err: ActionError<OidOrCloid>) -> Self {
+        let mut failure = Self {
+            message: err.message().to_owned(),
+            oids: vec![],
+            cloids: vec![],
+        };
+        for id in err.into_ids() {
+            match id {
+                Either::Left(oid) => failure.oids.push(oid),
+                Either::Right(cloid) => failure.cloids.push(cloid.into()),
+            }
+        }
+        failure
+    }
+}
+
+/// Result of an order action (place, cancel or modify).
+#[derive(Debug, Clone, Union)]
+pub enum OrderActionResult {
+    Success(OrderActionSuccess),
+    Failure(OrderActionFailure),
+}
+
+impl<T> From<Result<Vec<OrderResponseStatus>, ActionError<T>>> for OrderActionResult
+where
+    OrderActionFailure: From<ActionError<T>>,
+{
+    fn from(res: Result<Vec<OrderResponseStatus>, ActionError<T>>) -> Self {
+        match res {
+            Ok(statuses) => Self::Success(OrderActionSuccess {
+                statuses: scalar::list(statuses),
+            }),
+            Err(err) => Self::Failure(err.into()),
+        }
+    }
+}
+
+/// A Morpho Blue lending market.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MorphoMarket {
+    /// Market ID
+    pub id: scalar::B256,
+    /// Token lent and borrowed
+    pub loan_token: scalar::Address,
+    /// Token posted as collateral
+    pub collateral_token: scalar::Address,
+    /// Price oracle
+    pub oracle: scalar::Address,
+    /// Interest rate model
+    pub irm: scalar::Address,
+    /// Liquidation loan-to-value (0.86 = 86%)
+    pub lltv: scalar::Decimal,
+    /// Fee taken on interest (0.1 = 10%)
+    pub fee: scalar::Decimal,
+    /// Supplied assets in loan token wei
+    pub total_supply_assets: scalar::U256,
+    /// Supply shares
+    pub total_supply_shares: scalar::U256,
+    /// Borrowed assets in loan token wei
+    pub total_borrow_assets: scalar::U256,
+    /// Borrow shares
+    pub total_borrow_shares: scalar::U256,
+    /// Borrow APY (0.05 = 5%)
+    pub borrow_apy: f64,
+    /// Supply APY (0.03 = 3%)
+    pub supply_apy: f64,
+}
+
+impl From<PoolApy<f64>> for MorphoMarket {
+    fn from(pool: PoolApy<f64>) -> Self {
+        let PoolApy {
+            params,
+            market,
+            borrow,
+            supply,
+        } = pool;
+        Self {
+            id: params.id().into(),
+            loan_token: params.loanToken.into(),
+            collateral_token: params.collateralToken.into(),
+            oracle: params.oracle.into(),
+            irm: params.irm.into(),
+            lltv: from_wei(params.lltv, 18).into(),
+            fee: from_wei(U256::from(market.fee), 18).into(),
+            total_supply_assets: U256::from(market.totalSupplyAssets).into(),
+            total_supply_shares: U256::from(market.totalSupplyShares).into(),
+            total_borrow_assets: U256::from(market.totalBorrowAssets).into(),
+            total_borrow_shares: U256::from(market.totalBorrowShares).into(),
+            borrow_apy: finite(borrow),
+            supply_apy: finite(supply),
+        }
+    }
+}
+
+/// A market a MetaMorpho vault supplies to.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MorphoVaultMarket {
+    /// Market state and rates
+    pub market: MorphoMarket,
+    /// Supply shares held by the vault
+    pub supplied_shares: scalar::U256,
+    /// Assets supplied by the vault in loan token wei
+    pub supplied_assets: scalar::U256,
+}
+
+impl From<VaultSupply<f64>> for MorphoVaultMarket {
+    fn from(supply: VaultSupply<f64>) -> Self {
+        let total_assets = U256::from(supply.pool.market.totalSupplyAssets);
+        let total_shares = U256::from(supply.pool.market.totalSupplyShares);
+        let supplied_assets = supply
+            .supplied_shares
+            .checked_mul(total_assets)
+            .and_then(|assets| assets.checked_div(total_shares))
+            .unwrap_or_default();
+        Self {
+            market: supply.pool.into(),
+            supplied_shares: supply.supplied_shares.into(),
+            supplied_assets: supplied_assets.into(),
+        }
+    }
+}
+
+/// A MetaMorpho vault.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MorphoVault {
+    /// Vault contract address
+    pub address: scalar::Address,
+    /// APY net of the vault fee (0.04 = 4%)
+    pub apy: f64,
+    /// Fee taken on interest (0.1 = 10%)
+    pub fee: scalar::Decimal,
+    /// Assets deposited in the vault in loan token wei
+    pub total_assets: scalar::U256,
+    /// Enabled markets of the supply queue
+    pub markets: Vec<MorphoVaultMarket>,
+}
+
+impl MorphoVault {
+    /// Creates a vault from its APY breakdown.
+    pub fn new(address: Address, mut vault: VaultApy<f64>) -> Self {
+        // `VaultApy::apy` panics on rates that do not fit an integer
+        for component in &mut vault.components {
+            component.supply_apy = finite(component.supply_apy).max(0.0);
+        }
+        let apy = vault.apy(f64::from);
+        Self {
+            address: address.into(),
+            apy: finite(apy),
+            fee: from_wei(vault.fee, 18).into(),
+            total_assets: vault.total_deposits.into(),
+            markets: vault.components.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Replaces the NaN and infinite rates of empty markets with zero.
+fn finite(rate: f64) -> f64 {
+    if rate.is_finite() { rate } else { 0.0 }
+}
+
+/// Rule broken by a proposed order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum OrderIssueCode {
+    /// The asset index matches no market
+    UnknownAsset,
+    /// The price is zero or negative
+    PriceNotPositive,
+    /// The price is not a multiple of the tick size
+    PriceOffTick,
+    /// The size is zero or negative
+    SizeNotPositive,
+    /// The size has more decimals than the market allows
+    SizeTooPrecise,
+    /// The leverage is zero or above the market maximum
+    LeverageOutOfRange,
+    /// Leverage was given for a spot market
+    LeverageUnsupported,
+}
+
+/// A broken rule and its explanation.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct OrderIssue {
+    /// Rule broken
+    pub code: OrderIssueCode,
+    /// Human readable explanation
+    pub message: String,
+}
+
+/// Result of checking a proposed order against its market rules.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct OrderValidation {
+    /// Whether the order passes every check
+    pub valid: bool,
+    /// Broken rules, empty when valid
+    pub issues: Vec<OrderIssue>,
+    /// Tick size at the proposed price
+    pub tick: Option<scalar::Decimal>,
+    /// Proposed price rounded conservatively for the order side
+    pub rounded_price: Option<scalar::Decimal>,
+    /// Proposed size truncated to the allowed decimals
+    pub rounded_size: Option<scalar::Decimal>,
+}
+
+impl OrderValidation {
+    /// Order on an unknown asset.
+    pub(crate) fn unknown_asset(asset: usize) -> Self {
+        Self::from_issues(
+            vec![OrderIssue {
+                code: OrderIssueCode::UnknownAsset,
+                message: format!("no market with asset index {asset}"),
+            }],
+            None,
+            None,
+            None,
+        )
+    }
+
+    /// Checks an order against the tick, size decimals and max leverage of a perp market.
+    pub(crate) fn perp(
+        market: &PerpMarket,
+        is_buy: bool,
+        price: Decimal,
+        size: Decimal,
+        leverage: Option<u32>,
+    ) -> Self {
+        Self::check(
+            &market.table,
+            market.sz_decimals,
+            Some(market.max_leverage),
+            is_buy,
+            price,
+            size,
+            leverage,
+        )
+    }
+
+    /// Checks an order against the tick and size decimals of a spot market.
+    pub(crate) fn spot(
+        market: &SpotMarket,
+        is_buy: bool,
+        price: Decimal,
+        size: Decimal,
+        leverage: Option<u32>,
+    ) -> Self {
+        Self::check(
+            &market.table,
+            market.base().sz_decimals,
+            None,
+            is_buy,
+            price,
+            size,
+            leverage,
+        )
+    }
+
+    fn check(
+        table: &PriceTick,
+        sz_decimals: i64,
+        max_leverage: Option<u64>,
+        is_buy: bool,
+        price: Decimal,
+        size: Decimal,
+        leverage: Option<u32>,
+    ) -> Self {
+        let mut issues = vec![];
+        let side = if is_buy { Side::Bid } else { Side::Ask };
+
+        let mut tick = None;
+        let mut rounded_price = None;
+        if price > Decimal::ZERO {
+            tick = table.tick_for(price);
+            rounded_price = table.round_by_side(side, price, true);
+            if let Some(tick) = tick
+                && price.round_dp(tick.scale()) != price
+            {
+                issues.push(OrderIssue {
+                    code: OrderIssueCode::PriceOffTick,
+                    message: format!("price {price} is not a multiple of the tick size {tick}"),
+                });
+            }
+        } else {
+            issues.push(OrderIssue {
+                code: OrderIssueCode::PriceNotPositive,
+                message: format!("price {price} must be positive"),
+            });
+        }
+
+        let sz_decimals = sz_decimals.max(0) as u32;
+        let mut rounded_size = None;
+        if size > Decimal::ZERO {
+            let truncated = size.round_dp_with_strategy(sz_decimals, RoundingStrategy::ToZero);
+            if truncated != size {
+                issues.push(OrderIssue {
+                    code: OrderIssueCode::SizeTooPrecise,
+                    message: format!("size {size} has more than {sz_decimals} decimals"),
+                });
+            }
+            rounded_size = Some(truncated);
+        } else {
+            issues.push(OrderIssue {
+                code: OrderIssueCode::SizeNotPositive,
+                message: format!("size {size} must be positive"),
+            });
+        }
+
+        match (leverage, max_leverage) {
+            (None, _) => {}
+            (Some(_), None) => issues.push(OrderIssue {
+                code: OrderIssueCode::LeverageUnsupported,
+                message: "spot orders cannot use leverage".to_owned(),
+            }),
+            (Some(leverage), Some(max)) if leverage == 0 || u64::from(leverage) > max => {
+                issues.push(OrderIssue {
+                    code: OrderIssueCode::LeverageOutOfRange,
+                    message: format!("leverage {leverage} must be between 1 and {max}"),
+                });
+            }
+            (Some(_), Some(_)) => {}
+        }
+
+        Self::from_issues(
+            issues,
+            tick.map(Into::into),
+            rounded_price.map(Into::into),
+            rounded_size.map(Into::into),
+        )
+    }
+
+    fn from_issues(
+        issues: Vec<OrderIssue>,
+        tick: Option<scalar::Decimal>,
+        rounded_price: Option<scalar::Decimal>,
+        rounded_size: Option<scalar::Decimal>,
+    ) -> Self {
+        Self {
+            valid: issues.is_empty(),
+            issues,
+            tick,
+            rounded_price,
+            rounded_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dec, hypercore::fixtures::btc};
+
+    fn codes(validation: &OrderValidation) -> Vec<OrderIssueCode> {
+        validation.issues.iter().map(|issue| issue.code).collect()
+    }
+
+    #[test]
+    fn test_validate_order() {
+        let market = btc();
+        let ok = OrderValidation::perp(&market, true, dec!(97123), dec!(0.00123), Some(20));
+        assert!(ok.valid, "{:?}", ok.issues);
+
+        let bad = OrderValidation::perp(&market, true, dec!(97123.5), dec!(0.000123), Some(50));
+        assert!(!bad.valid);
+        assert_eq!(
+            codes(&bad),
+            [
+                OrderIssueCode::PriceOffTick,
+                OrderIssueCode::SizeTooPrecise,
+                OrderIssueCode::LeverageOutOfRange,
+            ]
+        );
+        // buys round down, sizes are truncated
+        assert_eq!(bad.rounded_price.unwrap().0, dec!(97123));
+        assert_eq!(bad.rounded_size.unwrap().0, dec!(0.00012));
+
+        let bad = OrderValidation::perp(&market, false, dec!(0), dec!(-1), None);
+        assert_eq!(
+            codes(&bad),
+            [
+                OrderIssueCode::PriceNotPositive,
+                OrderIssueCode::SizeNotPositive
+            ]
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Relay-style pagination over time-windowed endpoints.
+//!
+//! Fills, historical orders and funding rates are exposed as GraphQL connections in
+//! ascending time order. Edges carry a [`TimeCursor`], and each page is assembled by
+//! calling the upstream endpoint from the cursor's time onwards until `first` items
+//! are collected, so clients can scroll through long histories one page at a time.
+
+use std::{fmt, future::Future, num::ParseIntError, str::FromStr};
+
+use async_graphql::{
+    Error, OutputType, Result,
+    connection::{self, Connection, CursorType, Edge},
+};
+
+use super::error::UpstreamExt;
+use crate::{
+    Address,
+    hypercore::{
+        HttpClient,
+        types::{BasicOrder, Fill, FundingRate},
+    },
+};
+
+/// Maximum number of fills returned by `userFillsByTime`.
+const FILLS_LIMIT: usize = 2000;
+
+/// Maximum number of rates returned by `fundingHistory`.
+const FUNDING_LIMIT: usize = 500;
+
+/// Page size used when `first` is omitted.
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Largest accepted `first`.
+pub const MAX_PAGE_SIZE: usize = 2000;
+
+/// Position of an item in a time-ordered list.
+///
+/// `id` breaks ties between items of the same millisecond (trade id for fills, order id
+/// for orders). Encoded as `<time>:<id>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeCursor {
+    /// Timestamp in milliseconds
+    pub time: u64,
+    /// Tie breaker within the same timestamp
+    pub id: u64,
+}
+
+impl TimeCursor {
+    /// Creates a cursor.
+    pub const fn new(time: u64, id: u64) -> Self {
+        Self { time, id }
+    }
+}
+
+impl fmt::Display for TimeCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.time, self.id)
+    }
+}
+
+impl FromStr for TimeCursor {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (time, id) = s.split_once(':').unwrap_or((s, "0"));
+        Ok(Self {
+            time: time.parse()?,
+            id: id.parse()?,
+        })
+    }
+}
+
+impl CursorType for TimeCursor {
+    type Error = ParseIntError;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+
+    fn encode_cursor(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Time range of a connection, both ends inclusive.
+#[derive(Debug, Clone, Copy)]
+pub struct Window {
+    /// Start time in milliseconds
+    pub start: u64,
+    /// End time in milliseconds
+    pub end: u64,
+}
+
+impl Window {
+    /// Creates a window ending at `end` or now.
+    pub fn new(start: u64, end: Option<u64>) -> Self {
+        let end = end.unwrap_or_else(|| chrono::Utc::now().timestamp_millis() as u64);
+        Self { start, end }
+    }
+}
+
+/// Parses the `first`/`after` arguments and builds a page with [`page`].
+pub(crate) async fn paginate<T, C, F, Fut>(
+    window: Window,
+    after: Option<String>,
+    first: Option<i32>,
+    batch_limit: usize,
+    cursor: C,
+    fetch: F,
+) -> Result<Connection<TimeCursor, T>>
+where
+    T: OutputType,
+    C: Fn(&T) -> TimeCursor,
+    F: FnMut(u64, u64) -> Fut,
+    Fut: Future<Output = anyhow::Result<Vec<T>>>,
+{
+    connection::query(after, None, first, None, |after, _, first, _| async move {
+        let first = first.unwrap_or(DEFAULT_PAGE_SIZE);
+        if first > MAX_PAGE_SIZE {
+            return Err(Error::new(format!(
+                "`first` must not exceed {MAX_PAGE_SIZE}"
+            )));
+        }
+        page(window, after, first, batch_limit, cursor, fetch).await
+    })
+    .await
+}
+
+/// Collects up to `first` items after `after` within `window`.
+///
+/// `fetch(start, end)` returns items of the range in any order, at most `batch_limit` of
+/// them. A full batch means the range may hold more items, so the next call starts at the
+/// latest time seen; items already returned are skipped by their cursor.
+pub(crate) async fn page<T, C, F, Fut>(
+    window: Window,
+    after: Option<TimeCursor>,
+    first: usize,
+    batch_limit: usize,
+    cursor: C,
+    mut fetch: F,
+) -> Result<Connection<TimeCursor, T>>
+where
+    T: OutputType,
+    C: Fn(&T) -> TimeCursor,
+    F: FnMut(u64, u64) -> Fut,
+    Fut: Future<Output = anyhow::Result<Vec<T>>>,
+{
+    let mut items = Vec::new();
+    let mut last = after;
+    let mut start = after.map_or(window.start, |after| after.time.max(window.start));
+
+    while items.len() <= first && start <= window.end {
+        let mut batch = fetch(start, window.end).await.upstream()?;
+        let full = batch.len() >= batch_limit;
+        batch.sort_by_key(&cursor);
+        let latest = batch.last().map(|item| cursor(item).time);
+
+        for item in batch {
+            let at = cursor(&item);
+            if at.time < window.start || at.time > window.end || last.is_some_and(|l| at <= l) {
+                continue;
+            }
+            last = Some(at);
+            items.push(item);
+            if items.len() > first {
+                break;
+            }
+        }
+
+        match latest {
+            // more than `batch_limit` items in a single millisecond cannot be paged
+            Some(latest) if full => start = latest.max(start + 1),
+            _ => break,
+        }
+    }
+
+    let has_next_page = items.len() > first;
+    items.truncate(first);
+
+    let mut connection = Connection::new(after.is_some(), has_next_page);
+    connection
+        .edges
+        .extend(items.into_iter().map(|item| Edge::new(cursor(&item), item)));
+    Ok(connection)
+}
+
+/// Fills of a user, paged through [`HttpClient::user_fills_by_time`].
+pub(crate) async fn user_fills(
+    client: &HttpClient,
+    user: Address,
+    window: Window,
+    after: Option<String>,
+    first: Option<i32>,
+) -> Result<Connection<TimeCursor, Fill>> {
+    paginate(
+        window,
+        after,
+        first,
+        FILLS_LIMIT,
+        |fill: &Fill| TimeCursor::new(fill.time, fill.tid),
+        |start, end| client.user_fills_by_time(user, start, Some(end)),
+    )
+    .await
+}
+
+/// Historical orders of a user.
+///
+/// The upstream endpoint has no time range and returns the most recent orders at once,
+/// so they are fetched once per page and filtered by time.
+pub(crate) async fn historical_orders(
+    client: &HttpClient,
+    user: Address,
+    window: Window,
+    after: Option<String>,
+    first: Option<i32>,
+) -> Result<Connection<TimeCursor, BasicOrder>> {
+    paginate(
+        window,
+        after,
+        first,
+        usize::MAX,
+        |order: &BasicOrder| TimeCursor::new(order.timestamp, order.oid),
+        |_, _| client.historical_orders(user),
+    )
+    .await
+}
+
+/// Funding rates of a market, paged through [`HttpClient::funding_history`].
+pub(crate) async fn funding_history(
+    client: &HttpClient,
+    coin: String,
+    window: Window,
+    after: Option<String>,
+    first: Option<i32>,
+) -> Result<Connection<TimeCursor, FundingRate>> {
+    paginate(
+        window,
+        after,
+        first,
+        FUNDING_LIMIT,
+        |rate: &FundingRate| TimeCursor::new(rate.time, 0),
+        |start, end| client.funding_history(coin.clone(), start, Some(end)),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::SimpleObject;
+
+    use super::*;
+
+    #[derive(Debug, Clone, SimpleObject)]
+    struct Item {
+        time: u64,
+        id: u64,
+    }
+
+    fn cursor(item: &Item) -> TimeCursor {
+        TimeCursor::new(item.time, item.id)
+    }
+
+    /// Upstream returning at most 3 items from `start`, like a time-windowed endpoint.
+    async fn fetch(items: &[Item], start: u64, end: u64) -> anyhow::Result<Vec<Item>> {
+        Ok(items
+            .iter()
+            .filter(|item| item.time >= start && item.time <= end)
+            .take(3)
+            .cloned()
+            .collect())
+    }
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = TimeCursor::new(1700000000000, 42);
+        assert_eq!(cursor.encode_cursor(), "1700000000000:42");
+        assert_eq!(
+            TimeCursor::decode_cursor("1700000000000:42").unwrap(),
+            cursor
+        );
+        assert!(TimeCursor::decode_cursor("abc").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pages_through_batches_without_gaps() {
+        // several items share a timestamp across upstream batch boundaries
+        let items: Vec<_> = [
+            (1, 1),
+            (2, 2),
+            (2, 3),
+            (2, 4),
+            (3, 5),
+            (4, 6),
+            (4, 7),
+            (9, 8),
+        ]
+        .into_iter()
+        .map(|(time, id)| Item { time, id })
+        .collect();
+
+        let window = Window { start: 0, end: 5 };
+        let mut seen = vec![];
+        let mut after = None;
+        loop {
+            let conn = page(window, after, 2, 3, cursor, |start, end| {
+                fetch(&items, start, end)
+            })
+            .await
+            .unwrap();
+            seen.extend(conn.edges.iter().map(|edge| edge.node.id));
+            if !conn.has_next_page {
+                break;
+            }
+            after = conn.edges.last().map(|edge| edge.cursor);
+        }
+
+        assert_eq!(seen, [1, 2, 3, 4, 5, 6, 7]);
+    }
+}
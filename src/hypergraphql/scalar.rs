@@ -0,0 +1,319 @@
+//! Custom GraphQL scalars.
+//!
+//! The SDK uses foreign types (alloy primitives, [`rust_decimal::Decimal`]) that cannot
+//! implement [`ScalarType`] directly because of the orphan rule. Each of them gets a
+//! newtype here that serializes losslessly:
+//!
+//! | Scalar | Rust type | Representation |
+//! |--------|-----------|----------------|
+//! | [`Address`] | [`alloy::primitives::Address`] | EIP-55 checksummed hex |
+//! | [`Decimal`] | [`rust_decimal::Decimal`] | Decimal string (never a float) |
+//! | [`Cloid`] | [`hypercore::Cloid`](crate::hypercore::Cloid) | 16-byte `0x` hex |
+//! | [`B128`] | [`alloy::primitives::B128`] | 16-byte `0x` hex |
+//! | [`B256`] | [`alloy::primitives::B256`] | 32-byte `0x` hex |
+//! | [`U256`] | [`alloy::primitives::U256`] | Decimal string (wei amounts) |
+//! | [`Signature`] | [`hypercore::Signature`](crate::hypercore::Signature) | 65-byte `0x` hex (`r`, `s`, `v`) |
+//!
+//! SDK types expose their fields through these scalars with the
+//! `derived(owned, into = "...")` attribute, using [`option`] and [`list`] for wrapped values.
+
+use std::str::FromStr;
+
+use alloy::primitives;
+use async_graphql::{InputType, InputValueError, InputValueResult, Scalar, ScalarType, Value};
+
+/// Implements the conversions between a scalar newtype and the wrapped type.
+macro_rules! newtype {
+    ($name:ident, $inner:ty) => {
+        impl From<$inner> for $name {
+            fn from(value: $inner) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<&$inner> for $name {
+            fn from(value: &$inner) -> Self {
+                Self(*value)
+            }
+        }
+
+        impl From<$name> for $inner {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = $inner;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+    };
+}
+
+/// Ethereum address, serialized as EIP-55 checksummed hex.
+///
+/// Parsing accepts all-lowercase or all-uppercase hex, and mixed-case hex only
+/// when the checksum is valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Address(pub primitives::Address);
+
+newtype!(Address, primitives::Address);
+
+#[Scalar(name = "Address")]
+impl ScalarType for Address {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        let Value::String(s) = &value else {
+            return Err(InputValueError::expected_type(value));
+        };
+
+        let hex = s.strip_prefix("0x").unwrap_or(s);
+        let mixed_case = hex.chars().any(|c| c.is_ascii_lowercase())
+            && hex.chars().any(|c| c.is_ascii_uppercase());
+        let address = if mixed_case {
+            primitives::Address::parse_checksummed(s, None)
+                .map_err(|err| InputValueError::custom(format!("invalid address {s}: {err}")))?
+        } else {
+            primitives::Address::from_str(s)
+                .map_err(|err| InputValueError::custom(format!("invalid address {s}: {err}")))?
+        };
+
+        Ok(Self(address))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_checksum(None))
+    }
+}
+
+/// Arbitrary precision decimal, serialized as a string.
+///
+/// Parsing accepts strings and integers. Floating point numbers are rejected
+/// because they cannot represent prices and sizes exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decimal(pub rust_decimal::Decimal);
+
+newtype!(Decimal, rust_decimal::Decimal);
+
+#[Scalar(name = "Decimal")]
+impl ScalarType for Decimal {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => rust_decimal::Decimal::from_str_exact(s)
+                .map(Self)
+                .map_err(|err| InputValueError::custom(format!("invalid decimal {s}: {err}"))),
+            Value::Number(n) if n.is_i64() => Ok(Self(n.as_i64().unwrap_or_default().into())),
+            Value::Number(n) if n.is_u64() => Ok(Self(n.as_u64().unwrap_or_default().into())),
+            Value::Number(n) => Err(InputValueError::custom(format!(
+                "decimal {n} must be passed as a string to avoid precision loss"
+            ))),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// Parses a fixed-size `0x`-prefixed hex value.
+fn parse_fixed<const N: usize, S>(value: Value) -> InputValueResult<S>
+where
+    S: InputType + From<primitives::FixedBytes<N>>,
+{
+    let Value::String(s) = &value else {
+        return Err(InputValueError::expected_type(value));
+    };
+
+    if !s.starts_with("0x") {
+        return Err(InputValueError::custom(format!(
+            "{s} must be 0x-prefixed hex"
+        )));
+    }
+
+    primitives::FixedBytes::<N>::from_str(s)
+        .map(S::from)
+        .map_err(|err| InputValueError::custom(format!("invalid {N}-byte hex value {s}: {err}")))
+}
+
+/// Client order ID, serialized as 16-byte `0x` hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cloid(pub crate::hypercore::Cloid);
+
+/// 128-bit fixed bytes, serialized as 16-byte `0x` hex.
+///
+/// Used for token identifiers such as [`SpotToken::token_id`](crate::hypercore::SpotToken::token_id).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct B128(pub primitives::B128);
+
+/// 256-bit fixed bytes, serialized as 32-byte `0x` hex.
+///
+/// Used for hashes and Morpho market ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct B256(pub primitives::B256);
+
+newtype!(B128, primitives::B128);
+newtype!(B256, primitives::B256);
+
+newtype!(Cloid, crate::hypercore::Cloid);
+
+#[Scalar(name = "Cloid")]
+impl ScalarType for Cloid {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_fixed(value)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+#[Scalar(name = "B128")]
+impl ScalarType for B128 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_fixed(value)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+#[Scalar(name = "B256")]
+impl ScalarType for B256 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_fixed(value)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// 256-bit unsigned integer, serialized as a decimal string.
+///
+/// Used for raw wei amounts such as [`hyperevm::to_wei`](crate::hyperevm::to_wei) results.
+/// Parsing accepts decimal strings and non-negative integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct U256(pub primitives::U256);
+
+newtype!(U256, primitives::U256);
+
+#[Scalar(name = "U256")]
+impl ScalarType for U256 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => primitives::U256::from_str_radix(s, 10)
+                .map(Self)
+                .map_err(|err| InputValueError::custom(format!("invalid U256 {s}: {err}"))),
+            Value::Number(n) if n.is_u64() => {
+                Ok(Self(primitives::U256::from(n.as_u64().unwrap_or_default())))
+            }
+            Value::Number(n) => Err(InputValueError::custom(format!(
+                "U256 {n} must be a non-negative integer or a decimal string"
+            ))),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// ECDSA signature, serialized as 65-byte `0x` hex: `r`, `s`, then `v`.
+#[derive(Debug, Clone, Copy)]
+pub struct Signature(pub crate::hypercore::Signature);
+
+newtype!(Signature, crate::hypercore::Signature);
+
+#[Scalar(name = "Signature")]
+impl ScalarType for Signature {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        let Value::String(s) = &value else {
+            return Err(InputValueError::expected_type(value));
+        };
+        crate::hypercore::Signature::from_str(s)
+            .map(Self)
+            .map_err(|err| InputValueError::custom(format!("invalid signature {s}: {err}")))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// Converts an optional SDK value into its scalar, for `derived(owned, with = "...")`.
+pub fn option<T, S: From<T>>(value: Option<T>) -> Option<S> {
+    value.map(S::from)
+}
+
+/// Converts a list of SDK values into scalars, for `derived(owned, with = "...")`.
+pub fn list<T, S: From<T>>(value: Vec<T>) -> Vec<S> {
+    value.into_iter().map(S::from).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dec;
+
+    fn parse<S: ScalarType>(value: impl Into<Value>) -> InputValueResult<S> {
+        S::parse(value.into())
+    }
+
+    fn value<S: ScalarType>(scalar: S) -> Value {
+        scalar.to_value()
+    }
+
+    #[test]
+    fn test_address_checksum() {
+        let checksummed = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+
+        let addr: Address = parse(checksummed.to_lowercase()).unwrap();
+        assert_eq!(value(addr), Value::from(checksummed));
+        assert!(parse::<Address>(checksummed).is_ok());
+        assert!(parse::<Address>("0xdac17F958D2ee523a2206206994597C13D831ec7").is_err());
+        assert!(parse::<Address>("0x1234").is_err());
+        assert!(parse::<Address>(1234).is_err());
+    }
+
+    #[test]
+    fn test_decimal_rejects_floats() {
+        let px: Decimal = parse("0.1").unwrap();
+        assert_eq!(value(px), Value::from("0.1"));
+        assert_eq!(parse::<Decimal>(42).unwrap(), Decimal(dec!(42)));
+        assert!(parse::<Decimal>(0.1).is_err());
+        assert!(parse::<Decimal>("abc").is_err());
+    }
+
+    #[test]
+    fn test_fixed_bytes() {
+        let cloid = "0x000000000000000000000000000004d2";
+        assert_eq!(value(parse::<Cloid>(cloid).unwrap()), Value::from(cloid));
+        assert!(parse::<Cloid>("000000000000000000000000000004d2").is_err());
+        assert!(parse::<B128>("0x04d2").is_err());
+        assert!(parse::<B256>(cloid).is_err());
+    }
+
+    #[test]
+    fn test_u256() {
+        let wei = "100500000000000000000";
+        assert_eq!(value(parse::<U256>(wei).unwrap()), Value::from(wei));
+        assert!(parse::<U256>("-1").is_err());
+        assert!(parse::<U256>(-1).is_err());
+        assert!(parse::<U256>("0x10").is_err());
+    }
+
+    #[test]
+    fn test_signature() {
+        let sig = format!("0x{}{}1b", "11".repeat(32), "22".repeat(32));
+        assert_eq!(
+            value(parse::<Signature>(sig.as_str()).unwrap()),
+            Value::from(sig)
+        );
+        assert!(parse::<Signature>("0x1234").is_err());
+    }
+}
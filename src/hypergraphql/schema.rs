@@ -1,42 +1,499 @@
-use async_graphql::{
-    Context, InputValueError, InputValueResult, Object
+//! Root operation types of the GraphQL schema.
+//!
+//! Resolvers fetch data through the [`HttpClient`] registered as schema data
+//! by [`build_schema`](super::build_schema). Each root field maps to the
+//! [`HttpClient`] method of the same name. Market metadata is served from the
+//! [`MetaCache`]. Histories are paginated, see [`pagination`]. HyperEVM fields
+//! (`morpho*`, `uniswap*`) read contracts through the [`Evm`] source.
+
+use async_graphql::{Context, Error, Object, Result, connection::Connection};
+use either::Either;
+
+use super::{
+    Evm, MetaCache,
+    auth::{Role, RoleGuard},
+    error::UpstreamExt,
+    evm::DEFAULT_DEX,
+    multisig::{Proposal, Proposals},
+    object::{Mid, MorphoMarket, MorphoVault, OrderValidation, PerpDex, User},
+    pagination::{self, TimeCursor, Window},
+    scalar,
+    weight::weight,
 };
-use hypersdk::{
+use crate::{
     hypercore::{
-        HttpClient,
-        PerpMarket
-    }
+        AssetIndex, HttpClient, PerpMarket, SpotMarket, SpotToken,
+        rate_limit::{
+            CANDLES_PER_WEIGHT, INFO_LIGHT_WEIGHT, INFO_WEIGHT, ITEMS_PER_WEIGHT, USER_ROLE_WEIGHT,
+        },
+        types::{
+            ApiAgent, BasicOrder, Candle, CandleInterval, ClearinghouseState, Fill, FundingRate,
+            MultiSigConfig, OrderUpdate, SubAccount, UserBalance, UserRole, UserVaultEquity,
+            VaultDetails,
+        },
+    },
+    hyperevm::uniswap::{self, PoolState, Position, prjx},
 };
-use hypersdk;
-
-#[Scalar]
-impl ScalarType for hypersdk::Address {
-    fn parse(value: Value) -> InputValueResult<Self> {
-        if let Value::String(value) = &value {
-            // Parse the integer value
-            Ok(value.parse().map(Address)?)
-        } else {
-            Err(InputValueError::expected_type(value))
-        }
-    }
-}
 
+/// Root query type.
 pub struct Query;
 
 #[Object]
 impl Query {
-    async fn arbitrum_id<'ctx>(&self, ctx: &Context<'ctx>) -> Result<&'static str, async_graphql::Error> {
-        let client = ctx.data::<HttpClient>()?; // ? operator or .unwrap()
-        let chain = client.chain();
-        Ok(chain.arbitrum_id())
+    /// Arbitrum chain ID of the HyperCore chain the server is connected to.
+    async fn arbitrum_id(&self, ctx: &Context<'_>) -> Result<&'static str> {
+        let client = ctx.data::<HttpClient>()?;
+        Ok(client.chain().arbitrum_id())
+    }
+
+    /// Account of the given address. Its fields are fetched only when selected.
+    async fn user(&self, address: scalar::Address) -> User {
+        User::new(address.into())
     }
-    async fn perps<'ctx>(&self, ctx: &Context<'ctx>) -> Result<Vec<PerpMarket>, async_graphql::Error> {
+
+    /// Perpetual markets of the default DEX.
+    async fn perps(&self, ctx: &Context<'_>) -> Result<Vec<PerpMarket>> {
+        let cache = ctx.data::<MetaCache>()?;
+        cache.perp_markets().await.upstream()
+    }
+
+    /// Perpetual markets of a HIP-3 DEX.
+    async fn perps_from(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "DEX name, as returned by `perpDexs`")] dex: String,
+    ) -> Result<Vec<PerpMarket>> {
+        let cache = ctx.data::<MetaCache>()?;
+        let dex = cache
+            .perp_dex(&dex)
+            .await
+            .upstream()?
+            .ok_or_else(|| Error::new(format!("unknown perp dex: {dex}")))?;
+        cache.perp_markets_from(dex).await.upstream()
+    }
+
+    /// HIP-3 perpetual DEXes.
+    async fn perp_dexs(&self, ctx: &Context<'_>) -> Result<Vec<PerpDex>> {
+        let cache = ctx.data::<MetaCache>()?;
+        let dexs = cache.perp_dexs().await.upstream()?;
+        Ok(dexs.into_iter().map(PerpDex::from).collect())
+    }
+
+    /// Spot markets.
+    async fn spot(&self, ctx: &Context<'_>) -> Result<Vec<SpotMarket>> {
+        let cache = ctx.data::<MetaCache>()?;
+        cache.spot_markets().await.upstream()
+    }
+
+    /// Spot tokens.
+    async fn spot_tokens(&self, ctx: &Context<'_>) -> Result<Vec<SpotToken>> {
+        let cache = ctx.data::<MetaCache>()?;
+        cache.spot_tokens().await.upstream()
+    }
+
+    /// Checks a proposed order against the tick size, size decimals and max leverage
+    /// of its market, without placing it.
+    async fn validate_order(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Asset index, as in `placeOrders`")] asset: usize,
+        is_buy: bool,
+        limit_px: scalar::Decimal,
+        sz: scalar::Decimal,
+        #[graphql(desc = "Leverage the position will use, perps only")] leverage: Option<u32>,
+    ) -> Result<OrderValidation> {
+        let cache = ctx.data::<MetaCache>()?;
+        let (limit_px, sz) = (limit_px.into(), sz.into());
+        let markets = match AssetIndex::from_asset(asset) {
+            AssetIndex::Spot(_) => {
+                let markets = cache.spot_markets().await.upstream()?;
+                return Ok(match markets.iter().find(|market| market.index == asset) {
+                    Some(market) => OrderValidation::spot(market, is_buy, limit_px, sz, leverage),
+                    None => OrderValidation::unknown_asset(asset),
+                });
+            }
+            AssetIndex::Perp(_) => cache.perp_markets().await.upstream()?,
+            AssetIndex::DexPerp { dex: dex_index, .. } => {
+                let dexs = cache.perp_dexs().await.upstream()?;
+                match dexs.into_iter().find(|dex| dex.index() == dex_index) {
+                    Some(dex) => cache.perp_markets_from(dex).await.upstream()?,
+                    None => vec![],
+                }
+            }
+        };
+        Ok(match markets.iter().find(|market| market.index == asset) {
+            Some(market) => OrderValidation::perp(market, is_buy, limit_px, sz, leverage),
+            None => OrderValidation::unknown_asset(asset),
+        })
+    }
+
+    /// Mid prices of all markets, sorted by coin.
+    #[graphql(directive = weight::apply(INFO_LIGHT_WEIGHT, None))]
+    async fn all_mids(&self, ctx: &Context<'_>) -> Result<Vec<Mid>> {
         let client = ctx.data::<HttpClient>()?;
-        let markets = client.perps().await?;
-        Ok(markets)
+        let mut mids: Vec<_> = client
+            .all_mids()
+            .await
+            .upstream()?
+            .into_iter()
+            .map(|(coin, px)| Mid {
+                coin,
+                px: px.into(),
+            })
+            .collect();
+        mids.sort_by(|a, b| a.coin.cmp(&b.coin));
+        Ok(mids)
     }
+
+    /// Open orders of a user.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn open_orders(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+    ) -> Result<Vec<BasicOrder>> {
+        let client = ctx.data::<HttpClient>()?;
+        client.open_orders(user.into()).await.upstream()
+    }
+
+    /// Historical orders of a user in ascending time order.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, Some(ITEMS_PER_WEIGHT)))]
+    async fn historical_orders(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+        #[graphql(desc = "Start time in milliseconds")] start_time: Option<u64>,
+        #[graphql(desc = "End time in milliseconds, defaults to now")] end_time: Option<u64>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<TimeCursor, BasicOrder>> {
+        let client = ctx.data::<HttpClient>()?;
+        let window = Window::new(start_time.unwrap_or_default(), end_time);
+        pagination::historical_orders(client, user.into(), window, after, first).await
+    }
+
+    /// Fills of a user in ascending time order.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, Some(ITEMS_PER_WEIGHT)))]
+    async fn user_fills(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+        #[graphql(desc = "Start time in milliseconds")] start_time: Option<u64>,
+        #[graphql(desc = "End time in milliseconds, defaults to now")] end_time: Option<u64>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<TimeCursor, Fill>> {
+        let client = ctx.data::<HttpClient>()?;
+        let window = Window::new(start_time.unwrap_or_default(), end_time);
+        pagination::user_fills(client, user.into(), window, after, first).await
+    }
+
+    /// Status of an order, looked up by exactly one of `oid` or `cloid`.
+    ///
+    /// Returns `null` if the order is unknown.
+    #[graphql(directive = weight::apply(INFO_LIGHT_WEIGHT, None))]
+    async fn order_status(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+        oid: Option<u64>,
+        cloid: Option<scalar::Cloid>,
+    ) -> Result<Option<OrderUpdate>> {
+        let id = match (oid, cloid) {
+            (Some(oid), None) => Either::Left(oid),
+            (None, Some(cloid)) => Either::Right(cloid.into()),
+            _ => return Err(Error::new("exactly one of `oid` or `cloid` must be set")),
+        };
+
+        let client = ctx.data::<HttpClient>()?;
+        client.order_status(user.into(), id).await.upstream()
+    }
+
+    /// Historical candles of a market. Only the most recent 5000 candles are available.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, Some(CANDLES_PER_WEIGHT)))]
+    async fn candle_snapshot(
+        &self,
+        ctx: &Context<'_>,
+        coin: String,
+        interval: CandleInterval,
+        #[graphql(desc = "Start time in milliseconds")] start_time: u64,
+        #[graphql(desc = "End time in milliseconds")] end_time: u64,
+    ) -> Result<Vec<Candle>> {
+        let client = ctx.data::<HttpClient>()?;
+        client
+            .candle_snapshot(coin, interval, start_time, end_time)
+            .await
+            .upstream()
+    }
+
+    /// Spot balances of a user.
+    #[graphql(directive = weight::apply(INFO_LIGHT_WEIGHT, None))]
+    async fn user_balances(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+    ) -> Result<Vec<UserBalance>> {
+        let client = ctx.data::<HttpClient>()?;
+        client.user_balances(user.into()).await.upstream()
+    }
+
+    /// Perpetuals account state of a user.
+    #[graphql(directive = weight::apply(INFO_LIGHT_WEIGHT, None))]
+    async fn clearinghouse_state(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+    ) -> Result<ClearinghouseState> {
+        let client = ctx.data::<HttpClient>()?;
+        client.clearinghouse_state(user.into()).await.upstream()
+    }
+
+    /// Historical funding rates of a market in ascending time order.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, Some(ITEMS_PER_WEIGHT)))]
+    async fn funding_history(
+        &self,
+        ctx: &Context<'_>,
+        coin: String,
+        #[graphql(desc = "Start time in milliseconds")] start_time: u64,
+        #[graphql(desc = "End time in milliseconds, defaults to now")] end_time: Option<u64>,
+        after: Option<String>,
+        first: Option<i32>,
+    ) -> Result<Connection<TimeCursor, FundingRate>> {
+        let client = ctx.data::<HttpClient>()?;
+        let window = Window::new(start_time, end_time);
+        pagination::funding_history(client, coin, window, after, first).await
+    }
+
+    /// Multi-sig configuration of a user.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn multi_sig_config(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+    ) -> Result<MultiSigConfig> {
+        let client = ctx.data::<HttpClient>()?;
+        client.multi_sig_config(user.into()).await.upstream()
+    }
+
+    /// API agents approved by a user.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn api_agents(&self, ctx: &Context<'_>, user: scalar::Address) -> Result<Vec<ApiAgent>> {
+        let client = ctx.data::<HttpClient>()?;
+        client.api_agents(user.into()).await.upstream()
+    }
+
+    /// Details of a vault, including the follower state of `user` if set.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn vault_details(
+        &self,
+        ctx: &Context<'_>,
+        vault_address: scalar::Address,
+        user: Option<scalar::Address>,
+    ) -> Result<VaultDetails> {
+        let client = ctx.data::<HttpClient>()?;
+        client
+            .vault_details(vault_address.into(), user.map(Into::into))
+            .await
+            .upstream()
+    }
+
+    /// Vault deposits of a user.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn user_vault_equities(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+    ) -> Result<Vec<UserVaultEquity>> {
+        let client = ctx.data::<HttpClient>()?;
+        client.user_vault_equities(user.into()).await.upstream()
+    }
+
+    /// Role of an address (user, agent, vault, subaccount or missing).
+    #[graphql(directive = weight::apply(USER_ROLE_WEIGHT, None))]
+    async fn user_role(&self, ctx: &Context<'_>, user: scalar::Address) -> Result<UserRole> {
+        let client = ctx.data::<HttpClient>()?;
+        client.user_role(user.into()).await.upstream()
+    }
+
+    /// Subaccounts of a master account.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, None))]
+    async fn subaccounts(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+    ) -> Result<Vec<SubAccount>> {
+        let client = ctx.data::<HttpClient>()?;
+        client.subaccounts(user.into()).await.upstream()
+    }
+
+    /// Rates and totals of a Morpho Blue market on HyperEVM.
+    async fn morpho_market(
+        &self,
+        ctx: &Context<'_>,
+        id: scalar::B256,
+        #[graphql(desc = "Morpho Blue contract, the server's default if omitted")] morpho: Option<
+            scalar::Address,
+        >,
+    ) -> Result<MorphoMarket> {
+        let evm = evm(ctx)?;
+        let morpho = morpho.map_or(evm.morpho_address(), Into::into);
+        let pool = evm
+            .morpho()
+            .apy::<f64, _>(morpho, id.into(), f64::exp)
+            .await
+            .upstream()?;
+        Ok(pool.into())
+    }
+
+    /// APY and allocation of a MetaMorpho vault on HyperEVM.
+    async fn morpho_vault(
+        &self,
+        ctx: &Context<'_>,
+        address: scalar::Address,
+    ) -> Result<MorphoVault> {
+        let evm = evm(ctx)?;
+        let vault = evm
+            .meta_morpho()
+            .apy::<f64, _>(address.into(), f64::exp)
+            .await
+            .upstream()?;
+        Ok(MorphoVault::new(address.into(), vault))
+    }
+
+    /// Uniswap V3 liquidity positions of an address on HyperEVM.
+    async fn uniswap_positions(
+        &self,
+        ctx: &Context<'_>,
+        owner: scalar::Address,
+        #[graphql(
+            desc = "Uniswap V3 deployment",
+            default_with = "DEFAULT_DEX.to_owned()"
+        )]
+        dex: String,
+    ) -> Result<Vec<Position>> {
+        let client = evm(ctx)?
+            .uniswap(&dex)
+            .ok_or_else(|| Error::new(format!("unknown uniswap dex: {dex}")))?;
+        client.positions(owner.into()).await.upstream()
+    }
+
+    /// Tokens, liquidity and price of a Uniswap V3 pool on HyperEVM.
+    async fn uniswap_pool(&self, ctx: &Context<'_>, address: scalar::Address) -> Result<PoolState> {
+        let evm = evm(ctx)?;
+        // reading a pool only needs its address, any deployment will do
+        let client = uniswap::Client::new(evm.provider().clone(), prjx::CONTRACTS);
+        client.pool_state(address.into()).await.upstream()
+    }
+
+    /// Multisig proposal by identifier.
+    #[graphql(guard = "RoleGuard::new(Role::Reader)")]
+    async fn proposal(&self, ctx: &Context<'_>, id: u64) -> Result<Option<Proposal>> {
+        ctx.data::<Proposals>()?.get(id).await
+    }
+
+    /// Multisig proposals of a multisig account, newest first.
+    #[graphql(guard = "RoleGuard::new(Role::Reader)")]
+    async fn proposals(
+        &self,
+        ctx: &Context<'_>,
+        multi_sig_user: scalar::Address,
+    ) -> Result<Vec<Proposal>> {
+        ctx.data::<Proposals>()?.list(multi_sig_user.into()).await
+    }
+}
+
+/// Returns the HyperEVM source, failing if none is configured.
+fn evm<'a>(ctx: &Context<'a>) -> Result<&'a Evm> {
+    ctx.data_opt::<Evm>()
+        .ok_or_else(|| Error::new("HyperEVM fields are disabled: no provider configured"))
 }
 
-// 
+#[cfg(test)]
+mod tests {
+    use crate::{hypercore, hypergraphql::build_schema};
+
+    #[test]
+    fn test_sdl_root_fields() {
+        let sdl = build_schema(hypercore::mainnet()).sdl();
+        for field in [
+            "spot:",
+            "spotTokens:",
+            "perpDexs:",
+            "perpsFrom(",
+            "allMids:",
+            "validateOrder(",
+            "openOrders(",
+            "historicalOrders(",
+            "userFills(",
+            "orderStatus(",
+            "candleSnapshot(",
+            "userBalances(",
+            "clearinghouseState(",
+            "fundingHistory(",
+            "multiSigConfig(",
+            "apiAgents(",
+            "vaultDetails(",
+            "userVaultEquities(",
+            "userRole(",
+            "subaccounts(",
+            "user(",
+            "morphoMarket(",
+            "morphoVault(",
+            "uniswapPositions(",
+            "uniswapPool(",
+            "proposal(",
+            "proposals(",
+        ] {
+            assert!(sdl.contains(field), "missing root field {field}");
+        }
+    }
 
+    #[tokio::test]
+    async fn test_evm_fields_require_provider() {
+        let schema = build_schema(hypercore::testnet());
+        let res = schema
+            .execute(
+                r#"{ uniswapPool(address: "0x0D6ECB912b6ee160e95Bc198b618Acc1bCb92525") { price } }"#,
+            )
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("no provider configured"));
+    }
 
+    #[tokio::test]
+    async fn test_invalid_address_is_rejected() {
+        let schema = build_schema(hypercore::mainnet());
+        let res = schema
+            .execute(r#"{ openOrders(user: "0x1234") { oid } }"#)
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("invalid address"));
+    }
+
+    #[tokio::test]
+    async fn test_user_fields_are_lazy() {
+        let schema = build_schema(hypercore::mainnet());
+        let res = schema
+            .execute(
+                r#"{ user(address: "0xdac17f958d2ee523a2206206994597c13d831ec7") { address } }"#,
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.to_string(),
+            r#"{user: {address: "0xdAC17F958D2ee523a2206206994597C13D831ec7"}}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_order_status_requires_one_id() {
+        let schema = build_schema(hypercore::mainnet());
+        let res = schema
+            .execute(
+                r#"{ orderStatus(user: "0x0000000000000000000000000000000000000000") { status } }"#,
+            )
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("exactly one"));
+    }
+}
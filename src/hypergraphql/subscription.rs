@@ -0,0 +1,471 @@
+//! Subscription root backed by the HyperCore WebSocket.
+//!
+//! All GraphQL subscriptions are bridged from a shared [`Feed`]: the first client of a
+//! channel subscribes upstream, later clients reuse the same upstream subscription and the
+//! last client to leave unsubscribes it.
+//!
+//! `orderUpdates` messages do not carry the user they belong to, so they cannot be
+//! demultiplexed on a shared connection. Each user with `orderUpdates` subscribers gets
+//! its own reference-counted upstream [`Connection`] instead, closed with its last client.
+//!
+//! The feed reports its upstream connections, upstream subscriptions and clients as the
+//! `hypersdk_ws_*` gauges, see [`telemetry`](super::telemetry).
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use async_graphql::{Context, Result, Subscription as GraphQLSubscription};
+use futures::{Stream, StreamExt, future, stream};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    mpsc::{UnboundedSender, unbounded_channel},
+};
+use url::Url;
+
+use super::{error::UpstreamExt, object::Mid, scalar, weight::weight};
+use crate::{
+    Address,
+    hypercore::{
+        HttpClient,
+        rate_limit::{INFO_WEIGHT, ITEMS_PER_WEIGHT},
+        types::{
+            Bbo, Candle, CandleInterval, Fill, Incoming, L2Book, OrderUpdate,
+            Subscription as Channel, Trade,
+        },
+        ws::Connection,
+    },
+};
+
+/// Number of messages buffered per client before it starts lagging behind.
+const CAPACITY: usize = 1024;
+
+/// Shared, reference-counted WebSocket feed.
+///
+/// Upstream connections are opened lazily on the first subscription, so a feed can be
+/// created outside of a Tokio runtime. Cloning a feed shares its connections.
+#[derive(Clone)]
+pub struct Feed {
+    url: Url,
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Connection shared by every channel except `orderUpdates`
+    shared: Option<Upstream>,
+    /// Per-user connections for `orderUpdates`
+    orders: HashMap<Address, Upstream>,
+    /// Number of clients of each upstream subscription
+    refs: HashMap<Channel, usize>,
+}
+
+/// A [`Connection`] fanned out to any number of clients.
+struct Upstream {
+    commands: UnboundedSender<(bool, Channel)>,
+    events: broadcast::Sender<Incoming>,
+}
+
+impl Upstream {
+    fn spawn(url: Url) -> Self {
+        metrics::gauge!("hypersdk_ws_connections").increment(1);
+        let (commands, mut rx) = unbounded_channel();
+        let (events, _) = broadcast::channel(CAPACITY);
+
+        let tx = events.clone();
+        tokio::spawn(async move {
+            let mut conn = Connection::new(url);
+            loop {
+                tokio::select! {
+                    msg = conn.next() => {
+                        let Some(msg) = msg else { return };
+                        let _ = tx.send(msg);
+                    }
+                    cmd = rx.recv() => match cmd {
+                        Some((true, sub)) => conn.subscribe(sub),
+                        Some((false, sub)) => conn.unsubscribe(sub),
+                        // all handles dropped, closes the connection
+                        None => return,
+                    },
+                }
+            }
+        });
+
+        Self { commands, events }
+    }
+
+    fn send(&self, is_sub: bool, sub: Channel) {
+        let _ = self.commands.send((is_sub, sub));
+    }
+}
+
+impl Drop for Upstream {
+    fn drop(&mut self) {
+        metrics::gauge!("hypersdk_ws_connections").decrement(1);
+    }
+}
+
+impl Feed {
+    /// Creates a feed for the given WebSocket URL.
+    ///
+    /// No connection is made until the first subscription.
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            state: Arc::default(),
+        }
+    }
+
+    /// Subscribes to a channel, returning the messages that belong to it.
+    ///
+    /// The upstream subscription is released when the returned stream is dropped.
+    pub fn subscribe(&self, sub: Channel) -> impl Stream<Item = Incoming> + Send + 'static {
+        let rx = {
+            let mut state = self.state.lock().unwrap();
+            let state = &mut *state;
+
+            let upstream = match &sub {
+                Channel::OrderUpdates { user } => state
+                    .orders
+                    .entry(*user)
+                    .or_insert_with(|| Upstream::spawn(self.url.clone())),
+                _ => state
+                    .shared
+                    .get_or_insert_with(|| Upstream::spawn(self.url.clone())),
+            };
+
+            // receive before subscribing so the initial snapshot is not missed
+            let rx = upstream.events.subscribe();
+            let refs = state.refs.entry(sub.clone()).or_default();
+            *refs += 1;
+            metrics::gauge!("hypersdk_ws_subscribers", "channel" => channel(&sub)).increment(1);
+            if *refs == 1 {
+                log::debug!("subscribing upstream to {sub}");
+                metrics::gauge!("hypersdk_ws_subscriptions", "channel" => channel(&sub))
+                    .increment(1);
+                upstream.send(true, sub.clone());
+            }
+            rx
+        };
+
+        let guard = Release {
+            sub,
+            state: self.state.clone(),
+        };
+
+        stream::unfold((rx, guard), |(mut rx, guard)| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(msg) if matches(&guard.sub, &msg) => return Some((msg, (rx, guard))),
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(n)) => {
+                        log::warn!("{} subscriber lagged, skipped {n} messages", guard.sub);
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+    }
+
+    /// Returns the number of clients of an upstream subscription.
+    pub fn subscribers(&self, sub: &Channel) -> usize {
+        let state = self.state.lock().unwrap();
+        state.refs.get(sub).copied().unwrap_or_default()
+    }
+}
+
+/// Releases a client's reference to an upstream subscription on drop.
+struct Release {
+    sub: Channel,
+    state: Arc<Mutex<State>>,
+}
+
+impl Drop for Release {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap();
+        let Some(refs) = state.refs.get_mut(&self.sub) else {
+            return;
+        };
+
+        *refs -= 1;
+        metrics::gauge!("hypersdk_ws_subscribers", "channel" => channel(&self.sub)).decrement(1);
+        if *refs > 0 {
+            return;
+        }
+
+        state.refs.remove(&self.sub);
+        log::debug!("unsubscribing upstream from {}", self.sub);
+        metrics::gauge!("hypersdk_ws_subscriptions", "channel" => channel(&self.sub)).decrement(1);
+        match &self.sub {
+            Channel::OrderUpdates { user } => {
+                // dropping the upstream closes its connection
+                state.orders.remove(user);
+            }
+            sub => {
+                if let Some(shared) = &state.shared {
+                    shared.send(false, sub.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Returns the `type` of a channel, the label of the WebSocket gauges.
+fn channel(sub: &Channel) -> &'static str {
+    match sub {
+        Channel::Bbo { .. } => "bbo",
+        Channel::Trades { .. } => "trades",
+        Channel::L2Book { .. } => "l2Book",
+        Channel::Candle { .. } => "candle",
+        Channel::AllMids { .. } => "allMids",
+        Channel::OrderUpdates { .. } => "orderUpdates",
+        Channel::UserFills { .. } => "userFills",
+        Channel::UserTwapSliceFills { .. } => "userTwapSliceFills",
+        Channel::UserTwapHistory { .. } => "userTwapHistory",
+    }
+}
+
+/// Returns true if the message belongs to the subscription.
+fn matches(sub: &Channel, msg: &Incoming) -> bool {
+    match (sub, msg) {
+        (Channel::Trades { coin }, Incoming::Trades(trades)) => {
+            trades.first().is_some_and(|trade| &trade.coin == coin)
+        }
+        (Channel::L2Book { coin }, Incoming::L2Book(book)) => &book.coin == coin,
+        (Channel::Bbo { coin }, Incoming::Bbo(bbo)) => &bbo.coin == coin,
+        (Channel::Candle { coin, interval }, Incoming::Candle(candle)) => {
+            &candle.coin == coin && &candle.interval == interval
+        }
+        (Channel::AllMids { dex }, Incoming::AllMids { dex: msg_dex, .. }) => dex == msg_dex,
+        // order updates have a dedicated connection per user
+        (Channel::OrderUpdates { .. }, Incoming::OrderUpdates(_)) => true,
+        (Channel::UserFills { user }, Incoming::UserFills { user: msg_user, .. }) => {
+            user == msg_user
+        }
+        (
+            Channel::UserTwapSliceFills { user },
+            Incoming::UserTwapSliceFills { user: msg_user, .. },
+        )
+        | (Channel::UserTwapHistory { user }, Incoming::UserTwapHistory { user: msg_user, .. }) => {
+            user == msg_user
+        }
+        _ => false,
+    }
+}
+
+/// Root subscription type.
+///
+/// Fields yield one item per upstream event, so `trades` emits each trade of a batch
+/// separately.
+pub struct Subscription;
+
+#[GraphQLSubscription]
+impl Subscription {
+    /// Trades of a market.
+    async fn trades(&self, ctx: &Context<'_>, coin: String) -> Result<impl Stream<Item = Trade>> {
+        let feed = ctx.data::<Feed>()?;
+        Ok(feed
+            .subscribe(Channel::Trades { coin })
+            .flat_map(|msg| match msg {
+                Incoming::Trades(trades) => stream::iter(trades),
+                _ => stream::iter(vec![]),
+            }))
+    }
+
+    /// Order book snapshots of a market.
+    async fn l2_book(&self, ctx: &Context<'_>, coin: String) -> Result<impl Stream<Item = L2Book>> {
+        let feed = ctx.data::<Feed>()?;
+        Ok(feed
+            .subscribe(Channel::L2Book { coin })
+            .filter_map(|msg| async move {
+                match msg {
+                    Incoming::L2Book(book) => Some(book),
+                    _ => None,
+                }
+            }))
+    }
+
+    /// Best bid and offer of a market.
+    async fn bbo(&self, ctx: &Context<'_>, coin: String) -> Result<impl Stream<Item = Bbo>> {
+        let feed = ctx.data::<Feed>()?;
+        Ok(feed
+            .subscribe(Channel::Bbo { coin })
+            .filter_map(|msg| async move {
+                match msg {
+                    Incoming::Bbo(bbo) => Some(bbo),
+                    _ => None,
+                }
+            }))
+    }
+
+    /// Candle updates of a market.
+    async fn candle(
+        &self,
+        ctx: &Context<'_>,
+        coin: String,
+        interval: CandleInterval,
+    ) -> Result<impl Stream<Item = Candle>> {
+        let feed = ctx.data::<Feed>()?;
+        let interval = interval.to_string();
+        Ok(feed
+            .subscribe(Channel::Candle { coin, interval })
+            .filter_map(|msg| async move {
+                match msg {
+                    Incoming::Candle(candle) => Some(candle),
+                    _ => None,
+                }
+            }))
+    }
+
+    /// Mid prices of all markets of a DEX, sorted by coin.
+    async fn all_mids(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "HIP-3 DEX name, the default DEX if omitted")] dex: Option<String>,
+    ) -> Result<impl Stream<Item = Vec<Mid>>> {
+        let feed = ctx.data::<Feed>()?;
+        Ok(feed
+            .subscribe(Channel::AllMids { dex })
+            .filter_map(|msg| async move {
+                let Incoming::AllMids { mids, .. } = msg else {
+                    return None;
+                };
+                let mut mids: Vec<_> = mids
+                    .into_iter()
+                    .map(|(coin, px)| Mid {
+                        coin,
+                        px: px.into(),
+                    })
+                    .collect();
+                mids.sort_by(|a, b| a.coin.cmp(&b.coin));
+                Some(mids)
+            }))
+    }
+
+    /// Order status changes of a user.
+    async fn order_updates(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+    ) -> Result<impl Stream<Item = OrderUpdate>> {
+        let feed = ctx.data::<Feed>()?;
+        Ok(feed
+            .subscribe(Channel::OrderUpdates { user: user.into() })
+            .flat_map(|msg| match msg {
+                Incoming::OrderUpdates(updates) => stream::iter(updates),
+                _ => stream::iter(vec![]),
+            }))
+    }
+
+    /// Fills of a user. The first items are a snapshot of recent fills in ascending
+    /// time order.
+    ///
+    /// The upstream snapshot only reaches the first client of a user, so each client
+    /// gets its own from the `userFills` info request instead.
+    #[graphql(directive = weight::apply(INFO_WEIGHT, Some(ITEMS_PER_WEIGHT)))]
+    async fn user_fills(
+        &self,
+        ctx: &Context<'_>,
+        user: scalar::Address,
+    ) -> Result<impl Stream<Item = Fill>> {
+        let feed = ctx.data::<Feed>()?;
+        let client = ctx.data::<HttpClient>()?;
+        let user = user.into();
+
+        // subscribe first so that no fill is lost between the snapshot and live fills
+        let live = feed.subscribe(Channel::UserFills { user });
+        let mut snapshot = client.user_fills(user).await.upstream()?;
+        snapshot.sort_by_key(|fill| fill.time);
+        let seen: HashSet<_> = snapshot.iter().map(|fill| fill.tid).collect();
+
+        let live = live
+            .flat_map(|msg| match msg {
+                Incoming::UserFills { fills, .. } => stream::iter(fills),
+                _ => stream::iter(vec![]),
+            })
+            // the upstream snapshot of the first client repeats the fetched one
+            .filter(move |fill| future::ready(!seen.contains(&fill.tid)));
+        Ok(stream::iter(snapshot).chain(live))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hypercore, hypergraphql::build_schema};
+
+    #[tokio::test]
+    async fn test_feed_ref_counts_subscriptions() {
+        let feed = Feed::new("ws://127.0.0.1:9/ws".parse().unwrap());
+        let sub = Channel::Trades { coin: "BTC".into() };
+
+        let first = feed.subscribe(sub.clone());
+        let second = feed.subscribe(sub.clone());
+        assert_eq!(feed.subscribers(&sub), 2);
+
+        drop(first);
+        assert_eq!(feed.subscribers(&sub), 1);
+        drop(second);
+        assert_eq!(feed.subscribers(&sub), 0);
+    }
+
+    #[tokio::test]
+    async fn test_order_updates_use_per_user_connections() {
+        let feed = Feed::new("ws://127.0.0.1:9/ws".parse().unwrap());
+        let sub = Channel::OrderUpdates {
+            user: Address::ZERO,
+        };
+
+        let stream = feed.subscribe(sub.clone());
+        assert_eq!(feed.state.lock().unwrap().orders.len(), 1);
+        drop(stream);
+        assert!(feed.state.lock().unwrap().orders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_user_fills_start_with_snapshot() {
+        use axum::{Json, Router, routing::post};
+
+        let fill = |tid: u64, time: u64| {
+            serde_json::json!({
+                "coin": "ETH", "px": "2000.0", "sz": "0.5", "side": "B", "time": time,
+                "startPosition": "0.0", "dir": "Open Long", "closedPnl": "0.0", "hash": "0x00",
+                "oid": 1, "crossed": true, "fee": "0.35", "tid": tid, "feeToken": "USDC"
+            })
+        };
+        let fills = serde_json::json!([fill(2, 1700000002000), fill(1, 1700000001000)]);
+        let app = Router::new().route("/info", post(move || async move { Json(fills) }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let schema = build_schema(hypercore::mainnet().with_url(url.parse().unwrap()));
+        let query = r#"subscription { userFills(user: "0x0000000000000000000000000000000000000001") { tid } }"#;
+        // every client gets the snapshot, not only the first one
+        for _ in 0..2 {
+            let items: Vec<_> = schema.execute_stream(query).take(2).collect().await;
+            let tids: Vec<_> = items
+                .into_iter()
+                .map(|res| res.data.into_json().unwrap()["userFills"]["tid"].clone())
+                .collect();
+            assert_eq!(tids, [1, 2]);
+        }
+    }
+
+    #[test]
+    fn test_sdl_subscription_fields() {
+        let sdl = build_schema(hypercore::mainnet()).sdl();
+        for field in [
+            "trades(",
+            "l2Book(",
+            "bbo(",
+            "candle(",
+            "allMids(",
+            "orderUpdates(",
+            "userFills(",
+        ] {
+            assert!(sdl.contains(field), "missing subscription field {field}");
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Tracing spans and Prometheus metrics.
+//!
+//! The [`Telemetry`] extension opens a `graphql.operation` span around each executed
+//! operation and a `graphql.resolve` span around each resolver returning an object or a
+//! list of objects; scalar fields are not traced. Spans go to whichever
+//! [tracing](https://docs.rs/tracing) subscriber the application installs.
+//!
+//! Metrics are recorded through the [metrics](https://docs.rs/metrics) facade.
+//! [`install_recorder`] installs a Prometheus recorder and [`metrics_router`] serves it
+//! at `GET /metrics`:
+//!
+//! | Metric | Type | Labels |
+//! |---|---|---|
+//! | `hypersdk_graphql_operation_duration_seconds` | histogram | `type`, `status` |
+//! | `hypersdk_upstream_request_duration_seconds` | histogram | `endpoint`, `type` |
+//! | `hypersdk_upstream_errors_total` | counter | `endpoint`, `type`, `code` |
+//! | `hypersdk_ws_connections` | gauge | |
+//! | `hypersdk_ws_subscriptions` | gauge | `channel` |
+//! | `hypersdk_ws_subscribers` | gauge | `channel` |
+//!
+//! Operations are labelled by `type` (`query`, `mutation` or `subscription`) and `status`
+//! (`ok` or `error`), not by name, which clients choose freely. The upstream metrics are
+//! recorded by the [`HttpClient`](crate::hypercore::HttpClient), see
+//! [`http`](crate::hypercore::http#metrics). The WebSocket gauges count the upstream
+//! connections of the [`Feed`](super::Feed), its upstream subscriptions, and the GraphQL
+//! subscriptions sharing them.
+//!
+//! ```no_run
+//! use hypersdk::{hypercore, hypergraphql::{self, telemetry::{self, Telemetry}}};
+//!
+//! # fn example() -> anyhow::Result<()> {
+//! let handle = telemetry::install_recorder()?;
+//! let schema = hypergraphql::schema_builder(hypercore::mainnet())
+//!     .extension(Telemetry)
+//!     .finish();
+//! let app = hypergraphql::router(schema).merge(telemetry::metrics_router(handle));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use async_graphql::{
+    Response, ServerResult, Value, Variables,
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery, NextResolve,
+        ResolveInfo,
+    },
+    parser::types::{ExecutableDocument, OperationType},
+    registry::MetaTypeName,
+};
+use axum::{Router, http::HeaderMap, response::IntoResponse, routing::get};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use tracing::Instrument;
+
+use super::auth::Auth;
+
+/// Histogram buckets of the latency metrics, in seconds.
+pub const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Installs a global Prometheus recorder and returns the handle rendering its metrics.
+///
+/// Latency metrics are exported as histograms with [`LATENCY_BUCKETS`]. Fails if a
+/// recorder is already installed.
+pub fn install_recorder() -> anyhow::Result<PrometheusHandle> {
+    Ok(PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Suffix("_seconds".into()), LATENCY_BUCKETS)?
+        .install_recorder()?)
+}
+
+/// Creates an axum router serving the metrics of `handle` in the Prometheus text format.
+///
+/// - `GET /metrics`: current metrics
+///
+/// Like the GraphQL endpoints, it requires credentials of any role if an
+/// [`Auth`] extension is set.
+pub fn metrics_router(handle: PrometheusHandle) -> Router {
+    Router::new().route(
+        "/metrics",
+        get(
+            move |auth: Option<axum::Extension<Arc<Auth>>>, headers: HeaderMap| async move {
+                if let Some(axum::Extension(auth)) = auth
+                    && let Err(err) = auth.authenticate_headers(&headers)
+                {
+                    return super::rejection(err);
+                }
+                handle.render().into_response()
+            },
+        ),
+    )
+}
+
+/// Extension tracing operations and resolvers and recording operation latency.
+///
+/// ```
+/// use hypersdk::{hypercore, hypergraphql::{self, telemetry::Telemetry}};
+///
+/// let schema = hypergraphql::schema_builder(hypercore::mainnet())
+///     .extension(Telemetry)
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Telemetry;
+
+impl ExtensionFactory for Telemetry {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(TelemetryExtension::default())
+    }
+}
+
+#[derive(Default)]
+struct TelemetryExtension {
+    /// Names and types of the operations of the parsed document
+    operations: Mutex<Vec<(Option<String>, OperationType)>>,
+}
+
+impl TelemetryExtension {
+    /// Returns the type of the operation `operation_name` of the parsed document.
+    fn operation_type(&self, operation_name: Option<&str>) -> &'static str {
+        let operations = self.operations.lock().unwrap();
+        let ty = match operation_name {
+            Some(name) => operations
+                .iter()
+                .find(|(op, _)| op.as_deref() == Some(name))
+                .map(|(_, ty)| *ty),
+            None if operations.len() == 1 => Some(operations[0].1),
+            None => None,
+        };
+        match ty {
+            Some(OperationType::Query) => "query",
+            Some(OperationType::Mutation) => "mutation",
+            Some(OperationType::Subscription) => "subscription",
+            None => "unknown",
+        }
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for TelemetryExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let doc = next.run(ctx, query, variables).await?;
+        *self.operations.lock().unwrap() = doc
+            .operations
+            .iter()
+            .map(|(name, op)| (name.map(ToString::to_string), op.node.ty))
+            .collect();
+        Ok(doc)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let ty = self.operation_type(operation_name);
+        let span = tracing::info_span!(
+            "graphql.operation",
+            operation.name = operation_name.unwrap_or_default(),
+            operation.type = ty,
+            errors = tracing::field::Empty,
+        );
+
+        let started = Instant::now();
+        let res = next.run(ctx, operation_name).instrument(span.clone()).await;
+        let status = if res.is_ok() {
+            "ok"
+        } else {
+            span.record("errors", res.errors.len());
+            "error"
+        };
+        metrics::histogram!(
+            "hypersdk_graphql_operation_duration_seconds",
+            "type" => ty,
+            "status" => status,
+        )
+        .record(started.elapsed());
+        res
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        let is_leaf = ctx
+            .schema_env
+            .registry
+            .types
+            .get(MetaTypeName::concrete_typename(info.return_type))
+            .is_some_and(|ty| ty.is_leaf());
+        if info.is_for_introspection || is_leaf {
+            return next.run(ctx, info).await;
+        }
+
+        let span = tracing::debug_span!(
+            "graphql.resolve",
+            path = %info.path_node,
+            parent_type = info.parent_type,
+            return_type = info.return_type,
+        );
+        next.run(ctx, info)
+            .instrument(span.clone())
+            .await
+            .inspect_err(|err| {
+                span.in_scope(|| tracing::debug!(error = %err.message, "resolver failed"));
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{EmptyMutation, EmptySubscription, Object, Schema};
+
+    use super::*;
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn ok(&self) -> bool {
+            true
+        }
+
+        async fn fail(&self) -> async_graphql::Result<bool> {
+            Err("boom".into())
+        }
+    }
+
+    #[test]
+    fn test_records_operation_latency() {
+        let recorder = PrometheusBuilder::new()
+            .set_buckets_for_metric(Matcher::Suffix("_seconds".into()), LATENCY_BUCKETS)
+            .unwrap()
+            .build_recorder();
+        let handle = recorder.handle();
+        let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+            .extension(Telemetry)
+            .finish();
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        metrics::with_local_recorder(&recorder, || {
+            rt.block_on(async {
+                assert!(schema.execute("query Named { ok }").await.is_ok());
+                assert!(!schema.execute("{ fail }").await.is_ok());
+            })
+        });
+
+        let rendered = handle.render();
+        for series in [
+            r#"hypersdk_graphql_operation_duration_seconds_count{type="query",status="ok"} 1"#,
+            r#"hypersdk_graphql_operation_duration_seconds_count{type="query",status="error"} 1"#,
+        ] {
+            assert!(rendered.contains(series), "missing {series} in {rendered}");
+        }
+        assert!(rendered.contains("hypersdk_graphql_operation_duration_seconds_bucket"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_require_credentials() {
+        use crate::hypergraphql::auth::{ApiKey, AuthConfig, Role};
+
+        let handle = PrometheusBuilder::new().build_recorder().handle();
+        let auth = Auth::new(AuthConfig {
+            keys: vec![ApiKey {
+                name: "prometheus".into(),
+                key: "k1".into(),
+                role: Role::Reader,
+                budget: None,
+            }],
+            jwt: None,
+        });
+        let app = metrics_router(handle).layer(axum::Extension(Arc::new(auth)));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/metrics", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let http = reqwest::Client::new();
+        let anonymous = http.get(&url).send().await.unwrap();
+        assert_eq!(anonymous.status(), 401);
+        let scraper = http
+            .get(&url)
+            .header("x-api-key", "k1")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(scraper.status(), 200);
+    }
+}
@@ -0,0 +1,449 @@
+//! Query cost analysis in Hyperliquid request weights.
+//!
+//! Hyperliquid rate limits by weight, not by request count (see
+//! [`rate_limit`](crate::hypercore::rate_limit)). Every field that calls the API is
+//! annotated with the `@weight` directive, visible in the SDL:
+//!
+//! ```graphql
+//! clearinghouseState(user: Address!): ClearinghouseState! @weight(value: 2)
+//! userFills(...): FillConnection! @weight(value: 20, perItems: 20)
+//! ```
+//!
+//! `value` is the upstream weight of the call and `perItems` the number of returned
+//! items costing one extra weight. The item count is estimated from the `first`
+//! argument of connections, from the length of the [`ITEM_ARGUMENTS`] of mutations
+//! such as the `orders` of `place`, or else as [`DEFAULT_PAGE_SIZE`]. Fields below a
+//! list are counted once per item. Fields without the directive, including market
+//! metadata served from the [`MetaCache`](super::MetaCache) and HyperEVM reads, are
+//! free.
+//!
+//! The [`QueryWeight`] extension sums the weights of the selected operation before
+//! executing it, rejects it if the total exceeds the configured limit or what the
+//! client's [`RateLimiter`] has left, and reports the estimate in the `weight`
+//! response extension.
+
+use std::{collections::HashSet, sync::Arc};
+
+use async_graphql::{
+    Error, ErrorExtensions, Name, Pos, Positioned, Response, ServerError, ServerResult,
+    TypeDirective, Value, Variables,
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery},
+    parser::types::{
+        DocumentOperations, ExecutableDocument, Field, OperationDefinition, OperationType,
+        Selection, SelectionSet,
+    },
+    registry::{MetaField, MetaTypeName, Registry},
+};
+
+use super::pagination::DEFAULT_PAGE_SIZE;
+use crate::hypercore::{HttpClient, RateLimiter};
+
+/// List arguments whose length is the number of items of a field.
+pub const ITEM_ARGUMENTS: &[&str] = &["orders", "modifies", "cancels"];
+
+/// Upstream weight of a field: `value`, plus one per `perItems` returned items.
+#[TypeDirective(location = "FieldDefinition")]
+pub fn weight(value: u32, per_items: Option<u32>) {}
+
+/// Estimates the weight of the operation `operation_name` of a document.
+///
+/// Returns `None` if the document has no such operation.
+pub fn estimate(
+    registry: &Registry,
+    doc: &ExecutableDocument,
+    operation_name: Option<&str>,
+    variables: &Variables,
+) -> Option<u32> {
+    let operation = operation(doc, operation_name)?;
+    let root = match operation.node.ty {
+        OperationType::Query => Some(registry.query_type.as_str()),
+        OperationType::Mutation => registry.mutation_type.as_deref(),
+        OperationType::Subscription => registry.subscription_type.as_deref(),
+    }?;
+
+    let estimator = Estimator {
+        registry,
+        doc,
+        variables,
+    };
+    Some(estimator.selection_set(
+        root,
+        &operation.node.selection_set.node,
+        None,
+        &mut HashSet::new(),
+    ))
+}
+
+fn operation<'a>(
+    doc: &'a ExecutableDocument,
+    operation_name: Option<&str>,
+) -> Option<&'a Positioned<OperationDefinition>> {
+    match (&doc.operations, operation_name) {
+        (DocumentOperations::Single(operation), _) => Some(operation),
+        (DocumentOperations::Multiple(operations), Some(name)) => operations.get(name),
+        (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => {
+            operations.values().next()
+        }
+        _ => None,
+    }
+}
+
+struct Estimator<'a> {
+    registry: &'a Registry,
+    doc: &'a ExecutableDocument,
+    variables: &'a Variables,
+}
+
+impl<'a> Estimator<'a> {
+    /// Sums the weights of a selection set on `ty`, expanding fragments. `items` is
+    /// the item count of the parent field, used by list fields without their own, such
+    /// as the `edges` of a connection. `fragments` holds the fragments being expanded,
+    /// so cycles are not followed.
+    fn selection_set(
+        &self,
+        ty: &str,
+        selection_set: &'a SelectionSet,
+        items: Option<u32>,
+        fragments: &mut HashSet<&'a Name>,
+    ) -> u32 {
+        let mut total = 0u32;
+        for selection in &selection_set.items {
+            let cost = match &selection.node {
+                Selection::Field(field) => self.field(ty, &field.node, items, fragments),
+                Selection::InlineFragment(fragment) => {
+                    let ty = fragment
+                        .node
+                        .type_condition
+                        .as_ref()
+                        .map_or(ty, |condition| condition.node.on.node.as_str());
+                    self.selection_set(ty, &fragment.node.selection_set.node, items, fragments)
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = &spread.node.fragment_name.node;
+                    match self.doc.fragments.get(name) {
+                        Some(fragment) if fragments.insert(name) => {
+                            let ty = fragment.node.type_condition.node.on.node.as_str();
+                            let cost = self.selection_set(
+                                ty,
+                                &fragment.node.selection_set.node,
+                                items,
+                                fragments,
+                            );
+                            fragments.remove(name);
+                            cost
+                        }
+                        _ => 0,
+                    }
+                }
+            };
+            total = total.saturating_add(cost);
+        }
+        total
+    }
+
+    /// Weight of a field and its selection set, counted once per item if the field is
+    /// a list.
+    fn field(
+        &self,
+        ty: &str,
+        field: &'a Field,
+        parent_items: Option<u32>,
+        fragments: &mut HashSet<&'a Name>,
+    ) -> u32 {
+        let Some(meta) = self
+            .registry
+            .types
+            .get(ty)
+            .and_then(|ty| ty.field_by_name(&field.name.node))
+        else {
+            return 0;
+        };
+        let items = self.items(field);
+        let own = self.own_weight(meta, items);
+
+        let child = MetaTypeName::concrete_typename(&meta.ty);
+        if MetaTypeName::create(&meta.ty).is_list() {
+            let items = items.or(parent_items).unwrap_or(DEFAULT_PAGE_SIZE as u32);
+            let children = self.selection_set(child, &field.selection_set.node, None, fragments);
+            own.saturating_add(children.saturating_mul(items))
+        } else {
+            let children = self.selection_set(child, &field.selection_set.node, items, fragments);
+            own.saturating_add(children)
+        }
+    }
+
+    /// Weight of the `@weight` directive of a field returning `items` items.
+    fn own_weight(&self, meta: &MetaField, items: Option<u32>) -> u32 {
+        let Some(directive) = meta
+            .directive_invocations
+            .iter()
+            .find(|directive| directive.name == "weight")
+        else {
+            return 0;
+        };
+        let value = directive.args.get("value").and_then(as_u32).unwrap_or(0);
+        let extra = match directive.args.get("perItems").and_then(as_u32) {
+            Some(per_items) if per_items > 0 => {
+                items.unwrap_or(DEFAULT_PAGE_SIZE as u32) / per_items
+            }
+            _ => 0,
+        };
+        value.saturating_add(extra)
+    }
+
+    /// Number of items returned by a field, from its `first` or [`ITEM_ARGUMENTS`]
+    /// argument.
+    fn items(&self, field: &Field) -> Option<u32> {
+        let arguments: Vec<_> = field
+            .arguments
+            .iter()
+            .filter_map(|(name, value)| {
+                let value = value
+                    .node
+                    .clone()
+                    .into_const_with(|name| self.variables.get(&name).cloned().ok_or(()))
+                    .ok()?;
+                Some((name.node.as_str(), value))
+            })
+            .collect();
+
+        if let Some(first) = arguments
+            .iter()
+            .find(|(name, _)| *name == "first")
+            .and_then(|(_, value)| as_u32(value))
+        {
+            return Some(first);
+        }
+        arguments
+            .iter()
+            .filter(|(name, _)| ITEM_ARGUMENTS.contains(name))
+            .find_map(|(_, value)| match value {
+                Value::List(items) => Some(items.len() as u32),
+                _ => None,
+            })
+    }
+}
+
+fn as_u32(value: &Value) -> Option<u32> {
+    match value {
+        Value::Number(number) => number.as_u64().map(|n| n.min(u32::MAX as u64) as u32),
+        _ => None,
+    }
+}
+
+/// Extension rejecting operations heavier than a limit or than the weight left in the
+/// client's [`RateLimiter`].
+///
+/// Rejected operations fail before any field runs, with an error whose `code`
+/// extension is `WEIGHT_LIMIT_EXCEEDED` or `RATE_LIMITED`, alongside the estimated
+/// `weight` and the `limit` or `available` weight. Executed operations report their
+/// estimate in the `weight` response extension.
+///
+/// ```
+/// use hypersdk::{hypercore, hypergraphql::{self, weight::QueryWeight}};
+///
+/// let schema = hypergraphql::schema_builder(hypercore::mainnet())
+///     .extension(QueryWeight::new().limit(200))
+///     .finish();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct QueryWeight {
+    limit: Option<u32>,
+}
+
+impl QueryWeight {
+    /// Creates the extension without a per-operation limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rejects operations estimated above `limit`.
+    #[must_use]
+    pub fn limit(self, limit: u32) -> Self {
+        Self { limit: Some(limit) }
+    }
+}
+
+impl ExtensionFactory for QueryWeight {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(QueryWeightExtension {
+            limit: self.limit,
+            document: Default::default(),
+        })
+    }
+}
+
+struct QueryWeightExtension {
+    limit: Option<u32>,
+    document: std::sync::Mutex<Option<(ExecutableDocument, Variables)>>,
+}
+
+#[async_graphql::async_trait::async_trait]
+impl Extension for QueryWeightExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let doc = next.run(ctx, query, variables).await?;
+        *self.document.lock().unwrap() = Some((doc.clone(), variables.clone()));
+        Ok(doc)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let estimate = self
+            .document
+            .lock()
+            .unwrap()
+            .take()
+            .and_then(|(doc, variables)| {
+                let cost = estimate(&ctx.schema_env.registry, &doc, operation_name, &variables)?;
+                let pos = operation(&doc, operation_name).map_or_else(Pos::default, |op| op.pos);
+                Some((cost, pos))
+            });
+        let Some((cost, pos)) = estimate else {
+            return next.run(ctx, operation_name).await;
+        };
+
+        if let Some(limit) = self.limit.filter(|limit| cost > *limit) {
+            return rejection(
+                pos,
+                format!("query weight {cost} exceeds the limit of {limit}"),
+                "WEIGHT_LIMIT_EXCEEDED",
+                cost,
+                ("limit", limit),
+            );
+        }
+        let rate_limiter = ctx
+            .data_opt::<HttpClient>()
+            .and_then(HttpClient::rate_limiter)
+            .map(Arc::as_ref);
+        if let Some(available) = rate_limiter
+            .map(RateLimiter::available)
+            .filter(|available| cost > *available)
+        {
+            return rejection(
+                pos,
+                format!("query weight {cost} exceeds the {available} weight left upstream"),
+                "RATE_LIMITED",
+                cost,
+                ("available", available),
+            );
+        }
+
+        next.run(ctx, operation_name)
+            .await
+            .extension("weight", Value::from(cost))
+    }
+}
+
+fn rejection(
+    pos: Pos,
+    message: String,
+    code: &str,
+    cost: u32,
+    (name, value): (&str, u32),
+) -> Response {
+    let err: ServerError = Error::new(message)
+        .extend_with(|_, ext| {
+            ext.set("code", code);
+            ext.set("weight", cost);
+            ext.set(name, value);
+        })
+        .into_server_error(pos);
+    Response::from_errors(vec![err])
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{hypercore, hypergraphql::schema_builder};
+
+    const USER: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+
+    #[test]
+    fn test_sdl_annotates_weights() {
+        let sdl = schema_builder(hypercore::mainnet()).finish().sdl();
+        assert!(sdl.contains("directive @weight(value: Int!, perItems: Int) on FIELD_DEFINITION"));
+        assert!(sdl.contains("ClearinghouseState! @weight(value: 2)"));
+        assert!(sdl.contains("UserRole! @weight(value: 60)"));
+        assert!(sdl.contains("@weight(value: 20, perItems: 60)"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_heavy_queries() {
+        let schema = schema_builder(hypercore::mainnet())
+            .extension(QueryWeight::new().limit(150))
+            .finish();
+        // fills: 20 + 2000 / 20, role: 60, twice through the fragment, address: free
+        let query = format!(
+            r#"{{ user(address: "{USER}") {{ address fills(first: 2000) {{ edges {{ cursor }} }} ...Role }} }}
+            fragment Role on User {{ role }}"#
+        );
+        let res = schema.execute(query).await;
+        assert_eq!(res.errors.len(), 1);
+        let ext = serde_json::to_value(&res.errors[0].extensions).unwrap();
+        assert_eq!(
+            ext,
+            serde_json::json!({ "code": "WEIGHT_LIMIT_EXCEEDED", "weight": 180, "limit": 150 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_counts_fields_once_per_list_item() {
+        let schema = schema_builder(hypercore::mainnet())
+            .extension(QueryWeight::new().limit(1000))
+            .finish();
+        // subaccounts: 20, role: 60 for each of the default page of subaccounts
+        let query = format!(r#"{{ subaccounts(user: "{USER}") {{ name user {{ role }} }} }}"#);
+        let res = schema.execute(query).await;
+        assert_eq!(res.errors.len(), 1);
+        let ext = serde_json::to_value(&res.errors[0].extensions).unwrap();
+        assert_eq!(ext["weight"], 20 + DEFAULT_PAGE_SIZE as u32 * 60);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_when_rate_limited() {
+        let limiter = Arc::new(RateLimiter::new(10, Duration::from_secs(60)));
+        limiter.charge(9);
+        let client = hypercore::mainnet().with_rate_limiter(limiter);
+        let schema = schema_builder(client)
+            .extension(QueryWeight::new())
+            .finish();
+
+        let res = schema.execute("{ allMids { coin } }").await;
+        assert_eq!(res.errors.len(), 1);
+        let ext = serde_json::to_value(&res.errors[0].extensions).unwrap();
+        assert_eq!(ext["code"], "RATE_LIMITED");
+        assert_eq!(ext["available"], 1);
+
+        // free fields still run and report their estimate
+        let res = schema.execute("{ arbitrumId }").await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(res.extensions["weight"], Value::from(0));
+    }
+
+    #[tokio::test]
+    async fn test_mutation_weight_grows_with_batch() {
+        let schema = schema_builder(hypercore::mainnet())
+            .extension(QueryWeight::new().limit(1))
+            .finish();
+        let cancels = vec![r#"{ asset: 0, oid: 1 }"#; 40].join(", ");
+        let res = schema
+            .execute(format!(
+                "mutation {{ cancel(cancels: [{cancels}]) {{ __typename }} }}"
+            ))
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("query weight 2 exceeds"));
+    }
+}
@@ -17,6 +17,7 @@
 //! | [`hypercore::signing`] | Signature utilities | Sign actions, recover addresses |
 //! | [`hyperevm::morpho`] | Morpho lending | Query APY, lending positions |
 //! | [`hyperevm::uniswap`] | Uniswap V3 | Pool prices, liquidity positions |
+//! | `hypergraphql` | GraphQL API (`graphql` feature) | Serve SDK data to frontends |
 //!
 //! ## Features
 //!
@@ -29,6 +30,7 @@
 //! - Accurate price tick rounding for orders
 //! - HIP-3 support for multi-DEX perpetuals
 //! - Multi-signature transaction support
+//! - Optional GraphQL schema and axum router (`graphql` feature)
 //!
 //! ## Getting Started
 //!
@@ -210,9 +212,11 @@
 //! - [`hyperevm`]: HyperEVM contract interactions
 //!   - [`hyperevm::morpho`]: Morpho lending protocol integration
 //!   - [`hyperevm::uniswap`]: Uniswap V3 DEX integration
+//! - `hypergraphql`: GraphQL schema and axum router over the SDK (requires the `graphql` feature)
 
 pub mod hypercore;
 pub mod hyperevm;
+#[cfg(feature = "graphql")]
 pub mod hypergraphql;
 
 /// Re-exported Ethereum address type from Alloy.